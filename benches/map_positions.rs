@@ -0,0 +1,89 @@
+// explicit returns are the house style
+#![allow(clippy::needless_return)]
+
+// Looks up every cell of the puzzle's own area, once the droid has explored all of it, against the
+// scan over all the nodes that lookups used to be. day15.input (or the path given) can be the
+// droid's program or a map saved by explore --save:
+//
+//     cargo bench --bench map_positions [-- <path>]
+
+use std::env;
+use std::fs;
+use std::process::exit;
+use std::time::Instant;
+
+use advent_of_code_2019::droid::{self, Explorer};
+use advent_of_code_2019::intcode::run_intcode_computer;
+use advent_of_code_2019::loader::{self, Format};
+use advent_of_code_2019::maze::Map;
+
+fn load(path: &str) -> Map {
+    let bytes = fs::read(path).unwrap_or_else(|e| {
+        eprintln!("could not read {}: {}", path, e);
+        exit(1);
+    });
+
+    if let Ok(program) = loader::parse_program(&bytes, Format::Auto) {
+        let mut explorer = Explorer::new(run_intcode_computer("ic".to_string(), program));
+        explorer.explore(droid::strategy("dfs").unwrap().as_mut(), |_| true);
+        return explorer.chart.map;
+    }
+
+    let text = String::from_utf8_lossy(&bytes);
+    return Map::from_text(&text).unwrap_or_else(|e| {
+        eprintln!("{} is neither a program nor a map: {}", path, e);
+        exit(1);
+    });
+}
+
+fn main() {
+    // cargo passes --bench along to every bench target
+    let path = env::args()
+        .skip(1)
+        .find(|arg| !arg.starts_with("--"))
+        .unwrap_or_else(|| "day15.input".to_string());
+
+    let started = Instant::now();
+    let map = load(&path);
+    let explored = started.elapsed();
+
+    let cells: Vec<(i32, i32)> = map
+        .graph
+        .node_indices()
+        .map(|i| (map.graph[i].x, map.graph[i].y))
+        .collect();
+
+    let scan = |x: i32, y: i32| {
+        return map
+            .graph
+            .node_indices()
+            .find(|i| map.graph[*i].x == x && map.graph[*i].y == y);
+    };
+
+    let rounds = 100;
+    let started = Instant::now();
+    for _ in 0..rounds {
+        for (x, y) in cells.iter() {
+            assert!(map.node_index(*x, *y).is_some());
+        }
+    }
+    let indexed = started.elapsed();
+
+    let started = Instant::now();
+    for _ in 0..rounds {
+        for (x, y) in cells.iter() {
+            assert!(scan(*x, *y).is_some());
+        }
+    }
+    let scanned = started.elapsed();
+
+    println!(
+        "{} nodes, {} edges: loaded in {:?}, {} rounds of lookups {:?} indexed, {:?} scanning",
+        map.graph.node_count(),
+        map.graph.edge_count(),
+        explored,
+        rounds,
+        indexed,
+        scanned
+    );
+}
@@ -0,0 +1,244 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+
+use crate::solution::Answer;
+
+// Known good answers, so that refactoring the Intcode VM or a search can be checked against every
+// day at once. Answers are keyed by day, part and a hash of the input they were computed from, and
+// kept in a plain text file with one answer per line:
+//
+//     <day> <part> <input hash as 16 hex digits> <answer>
+//
+// Blank lines and lines starting with '#' are ignored. Multi-line answers (pictures of letters)
+// are stored on one line with "\n" for each newline and "\\" for each backslash.
+
+pub const DEFAULT_PATH: &str = "answers.txt";
+
+// FNV-1a. Unlike std's DefaultHasher it is guaranteed not to change between Rust releases, which
+// matters because the hashes are written to disk.
+pub fn input_hash(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+
+    for b in bytes {
+        hash ^= *b as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+
+    return hash;
+}
+
+#[test]
+fn test_input_hash() {
+    assert_eq!(input_hash(b""), 0xcbf29ce484222325);
+    assert_eq!(input_hash(b"a"), 0xaf63dc4c8601ec8c);
+    assert_ne!(input_hash(b"1,2,3\n"), input_hash(b"1,2,4\n"));
+}
+
+fn escape(text: &str) -> String {
+    return text.replace('\\', "\\\\").replace('\n', "\\n");
+}
+
+fn unescape(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            output.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('n') => output.push('\n'),
+            Some(other) => output.push(other),
+            None => output.push('\\'),
+        }
+    }
+
+    return output;
+}
+
+#[test]
+fn test_escape() {
+    let picture = ".##.\n#..#\n\\ back\\slash";
+    assert_eq!(escape(picture), ".##.\\n#..#\\n\\\\ back\\\\slash");
+    assert_eq!(unescape(&escape(picture)), picture);
+    assert_eq!(unescape("42"), "42");
+}
+
+#[derive(Debug)]
+pub enum AnswersError {
+    Io(String, io::Error),
+
+    // line number (from 1) and the text that could not be understood
+    BadLine(usize, String),
+}
+
+impl fmt::Display for AnswersError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AnswersError::Io(path, e) => write!(f, "could not access {}: {}", path, e),
+            AnswersError::BadLine(line, text) => {
+                write!(
+                    f,
+                    "line {}: expected <day> <part> <hash> <answer>, got {:?}",
+                    line, text
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for AnswersError {}
+
+// How a fresh answer compares to what is on record for the same day, part and input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+
+    // an answer was recorded for this input, and it is not this one
+    Changed { expected: String },
+
+    // nothing recorded for this input yet
+    New,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    expected: BTreeMap<(u32, u32, u64), String>,
+}
+
+impl Answers {
+    pub fn parse(text: &str) -> Result<Answers, AnswersError> {
+        let mut answers = Answers::default();
+
+        for (i, line) in text.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let bad_line = || AnswersError::BadLine(i + 1, line.to_string());
+
+            let fields: Vec<&str> = line.splitn(4, ' ').collect();
+            if fields.len() != 4 {
+                return Err(bad_line());
+            }
+
+            let day = fields[0].parse::<u32>().map_err(|_| bad_line())?;
+            let part = fields[1].parse::<u32>().map_err(|_| bad_line())?;
+            let hash = u64::from_str_radix(fields[2], 16).map_err(|_| bad_line())?;
+
+            answers
+                .expected
+                .insert((day, part, hash), unescape(fields[3]));
+        }
+
+        return Ok(answers);
+    }
+
+    // a missing file is just an empty set of answers, nothing has been recorded yet
+    pub fn load(path: &str) -> Result<Answers, AnswersError> {
+        match fs::read_to_string(path) {
+            Ok(text) => return Answers::parse(&text),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Answers::default()),
+            Err(e) => return Err(AnswersError::Io(path.to_string(), e)),
+        }
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), AnswersError> {
+        return fs::write(path, self.to_string())
+            .map_err(|e| AnswersError::Io(path.to_string(), e));
+    }
+
+    pub fn len(&self) -> usize {
+        return self.expected.len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.expected.is_empty();
+    }
+
+    pub fn get(&self, day: u32, part: u32, input_hash: u64) -> Option<&str> {
+        return self
+            .expected
+            .get(&(day, part, input_hash))
+            .map(|s| s.as_str());
+    }
+
+    pub fn record(&mut self, day: u32, part: u32, input_hash: u64, answer: &Answer) {
+        self.expected
+            .insert((day, part, input_hash), answer.to_string());
+    }
+
+    pub fn check(&self, day: u32, part: u32, input_hash: u64, answer: &Answer) -> Verdict {
+        match self.get(day, part, input_hash) {
+            None => return Verdict::New,
+            Some(expected) if expected == answer.to_string() => return Verdict::Pass,
+            Some(expected) => {
+                return Verdict::Changed {
+                    expected: expected.to_string(),
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "# day part input-hash answer")?;
+
+        for ((day, part, hash), answer) in &self.expected {
+            writeln!(f, "{} {} {:016x} {}", day, part, hash, escape(answer))?;
+        }
+
+        return Ok(());
+    }
+}
+
+#[test]
+fn test_answers() {
+    let text = "# day part input-hash answer
+
+5 1 00000000000000ff 7692125
+11 2 0000000000000abc #..#\\n####
+";
+
+    let mut answers = Answers::parse(text).unwrap();
+    assert_eq!(answers.len(), 2);
+
+    assert_eq!(
+        answers.check(5, 1, 0xff, &Answer::Number(7692125)),
+        Verdict::Pass
+    );
+    assert_eq!(
+        answers.check(5, 1, 0xff, &Answer::Number(7692126)),
+        Verdict::Changed {
+            expected: "7692125".to_string()
+        }
+    );
+    assert_eq!(
+        answers.check(11, 2, 0xabc, &Answer::Text("#..#\n####".to_string())),
+        Verdict::Pass
+    );
+
+    // same day and part, different input
+    assert_eq!(
+        answers.check(5, 1, 0xfe, &Answer::Number(7692125)),
+        Verdict::New
+    );
+
+    answers.record(5, 2, 0xff, &Answer::Number(1));
+    assert_eq!(answers.get(5, 2, 0xff), Some("1"));
+
+    // saving and loading again gives back the same answers
+    assert_eq!(Answers::parse(&answers.to_string()).unwrap(), answers);
+
+    match Answers::parse("5 1 zz 7692125") {
+        Err(AnswersError::BadLine(1, _)) => {}
+        x => panic!("expected a bad line error, got {:?}", x),
+    }
+
+    assert!(Answers::load("does-not-exist.answers").unwrap().is_empty());
+}
@@ -0,0 +1,573 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::grid::Grid;
+#[cfg(test)]
+use crate::intcode::{Arg, Assembler};
+use crate::intcode::{Machine, MachineState};
+use crate::render::{Rgb, Style};
+
+// The arcade cabinet from day 13, run one joystick move at a time on the caller's thread. Because
+// the whole cabinet is a Machine plus what is on the screen, a clone of it is a snapshot of the
+// game that can be gone back to, and it can be saved to a file and carried on with later.
+
+// tile ids
+pub const EMPTY: i32 = 0;
+pub const WALL: i32 = 1;
+pub const BLOCK: i32 = 2;
+pub const PADDLE: i32 = 3;
+pub const BALL: i32 = 4;
+
+pub struct ScreenStyle;
+
+impl Style<i32> for ScreenStyle {
+    fn glyph(&self, cell: &i32) -> char {
+        match *cell {
+            EMPTY => return ' ',
+            WALL => return 'W',
+            BLOCK => return '#',
+            PADDLE => return '-',
+            BALL => return '*',
+            _ => panic!("bad value seen!"),
+        }
+    }
+
+    fn color(&self, cell: &i32) -> Rgb {
+        match *cell {
+            EMPTY => return Rgb::BLACK,
+            WALL => return Rgb(128, 128, 128),
+            BLOCK => return Rgb(230, 140, 40),
+            PADDLE => return Rgb::WHITE,
+            BALL => return Rgb(220, 40, 40),
+            _ => panic!("bad value seen!"),
+        }
+    }
+}
+
+// If the joystick is in the neutral position, provide 0. If the joystick is tilted to the left,
+// provide -1. If the joystick is tilted to the right, provide 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Joystick {
+    Left,
+    Neutral,
+    Right,
+}
+
+impl Joystick {
+    pub fn value(self) -> i64 {
+        match self {
+            Joystick::Left => return -1,
+            Joystick::Neutral => return 0,
+            Joystick::Right => return 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Arcade {
+    pub machine: Machine,
+    pub screen: Grid<i32>,
+
+    // from the last (-1, 0, score) output, if there has been one
+    pub score: Option<i64>,
+
+    // joystick moves so far
+    pub ticks: u64,
+
+    // an output instruction that hasn't got all three of its values yet
+    pending: Vec<i64>,
+
+    // kept up to date as tiles are drawn, so controllers don't have to search the screen
+    paddle: Option<(i32, i32)>,
+    ball: Option<(i32, i32)>,
+}
+
+impl Arcade {
+    // Memory address 0 represents the number of quarters that have been inserted; set it to 2 to
+    // play for free.
+    pub fn new(program: &[i64], free_play: bool) -> Arcade {
+        let mut machine = Machine::new(program);
+        if free_play {
+            machine.memory[0] = 2;
+        }
+
+        let mut arcade = Arcade {
+            machine,
+            screen: Grid::new(EMPTY),
+            score: None,
+            ticks: 0,
+            pending: Vec::new(),
+            paddle: None,
+            ball: None,
+        };
+        arcade.run();
+
+        return arcade;
+    }
+
+    // runs until the game wants the joystick or is over, drawing everything it outputs
+    fn run(&mut self) {
+        let outputs = self.machine.run();
+        self.pending.extend(outputs);
+
+        let whole = self.pending.len() - self.pending.len() % 3;
+        let outputs: Vec<i64> = self.pending.drain(..whole).collect();
+
+        // every three output instructions specify the x position, y position and tile id
+        for tile in outputs.chunks_exact(3) {
+            // When three output instructions specify X=-1, Y=0, the third output instruction is
+            // not a tile; the value instead specifies the new score to show in the segment display.
+            if tile[0] == -1 && tile[1] == 0 {
+                self.score = Some(tile[2]);
+            } else {
+                self.draw(tile[0] as i32, tile[1] as i32, tile[2] as i32);
+            }
+        }
+    }
+
+    fn draw(&mut self, x: i32, y: i32, tile_id: i32) {
+        self.screen.set(x, y, tile_id);
+
+        match tile_id {
+            PADDLE => self.paddle = Some((x, y)),
+            BALL => self.ball = Some((x, y)),
+            _ => {
+                // drawn over
+                if self.paddle == Some((x, y)) {
+                    self.paddle = None;
+                }
+                if self.ball == Some((x, y)) {
+                    self.ball = None;
+                }
+            }
+        }
+    }
+
+    // Moves the joystick and runs the game up to when it next reads it. Does nothing once the game
+    // is over.
+    pub fn tick(&mut self, joystick: Joystick) {
+        if self.is_over() {
+            return;
+        }
+
+        self.machine.send(joystick.value());
+        self.ticks += 1;
+        self.run();
+    }
+
+    pub fn state(&self) -> MachineState {
+        return self.machine.state();
+    }
+
+    pub fn is_over(&self) -> bool {
+        return self.machine.halted;
+    }
+
+    pub fn blocks(&self) -> usize {
+        return self.screen.count_where(|tile_id| *tile_id == BLOCK);
+    }
+
+    // the game was won if it ended with every block broken
+    pub fn won(&self) -> bool {
+        return self.is_over() && self.blocks() == 0;
+    }
+
+    pub fn paddle(&self) -> Option<(i32, i32)> {
+        return self.paddle;
+    }
+
+    pub fn ball(&self) -> Option<(i32, i32)> {
+        return self.ball;
+    }
+
+    pub fn to_save(&self) -> SaveGame {
+        let mut tiles: Vec<(i32, i32, i32)> = self
+            .screen
+            .iter()
+            .map(|((x, y), tile_id)| (x, y, *tile_id))
+            .collect();
+        tiles.sort();
+
+        return SaveGame {
+            machine: self.machine.clone(),
+            tiles,
+            score: self.score,
+            ticks: self.ticks,
+            pending: self.pending.clone(),
+        };
+    }
+
+    pub fn from_save(save: SaveGame) -> Arcade {
+        let mut screen = Grid::new(EMPTY);
+        for (x, y, tile_id) in save.tiles {
+            screen.set(x, y, tile_id);
+        }
+
+        return Arcade {
+            machine: save.machine,
+            paddle: screen.find(|tile_id| *tile_id == PADDLE),
+            ball: screen.find(|tile_id| *tile_id == BALL),
+            screen,
+            score: save.score,
+            ticks: save.ticks,
+            pending: save.pending,
+        };
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), ArcadeError> {
+        return write_json(path, &self.to_save());
+    }
+
+    pub fn load(path: &str) -> Result<Arcade, ArcadeError> {
+        let text = fs::read_to_string(path).map_err(|e| ArcadeError::Io(path.to_string(), e))?;
+        let save: SaveGame =
+            serde_json::from_str(&text).map_err(|e| ArcadeError::Json(path.to_string(), e))?;
+        return Ok(Arcade::from_save(save));
+    }
+}
+
+// A game as written to disk. The screen is kept as a list of tiles since JSON can't have pairs as
+// keys.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SaveGame {
+    pub machine: Machine,
+    pub tiles: Vec<(i32, i32, i32)>,
+    pub score: Option<i64>,
+    pub ticks: u64,
+    pub pending: Vec<i64>,
+}
+
+#[derive(Debug)]
+pub enum ArcadeError {
+    Io(String, io::Error),
+    Json(String, serde_json::Error),
+}
+
+impl fmt::Display for ArcadeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ArcadeError::Io(path, e) => write!(f, "could not access {}: {}", path, e),
+            ArcadeError::Json(path, e) => write!(f, "{} is not an arcade file: {}", path, e),
+        }
+    }
+}
+
+impl std::error::Error for ArcadeError {}
+
+fn write_json<T: Serialize>(path: &str, value: &T) -> Result<(), ArcadeError> {
+    let text =
+        serde_json::to_string_pretty(value).map_err(|e| ArcadeError::Json(path.to_string(), e))?;
+    return fs::write(path, text + "\n").map_err(|e| ArcadeError::Io(path.to_string(), e));
+}
+
+pub const HIGH_SCORES_PATH: &str = "highscores.json";
+
+// how many scores the table keeps
+const TABLE_SIZE: usize = 10;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HighScore {
+    pub score: i64,
+    pub ticks: u64,
+    pub won: bool,
+
+    // seconds since the epoch
+    pub timestamp: u64,
+}
+
+// The best scores so far, best first.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HighScores {
+    pub scores: Vec<HighScore>,
+}
+
+impl HighScores {
+    // a missing file is an empty table
+    pub fn load(path: &str) -> Result<HighScores, ArcadeError> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(HighScores::default()),
+            Err(e) => return Err(ArcadeError::Io(path.to_string(), e)),
+        };
+
+        return serde_json::from_str(&text).map_err(|e| ArcadeError::Json(path.to_string(), e));
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), ArcadeError> {
+        return write_json(path, self);
+    }
+
+    pub fn best(&self) -> Option<i64> {
+        return self.scores.first().map(|s| s.score);
+    }
+
+    // Puts the game's score in the table, returning where it came (from 0) if it made it in.
+    // Earlier scores stay ahead of later ones that tie with them.
+    pub fn add(&mut self, arcade: &Arcade) -> Option<usize> {
+        let score = arcade.score.unwrap_or_default();
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+
+        let place = self.scores.iter().take_while(|s| s.score >= score).count();
+        if place >= TABLE_SIZE {
+            return None;
+        }
+
+        self.scores.insert(
+            place,
+            HighScore {
+                score,
+                ticks: arcade.ticks,
+                won: arcade.won(),
+                timestamp,
+            },
+        );
+        self.scores.truncate(TABLE_SIZE);
+
+        return Some(place);
+    }
+}
+
+// A small game of breakout for trying controllers on. The playfield is walled in from x = 0 to 7 with
+// a ceiling at y = 0, there are six blocks along y = 2 worth 10 points each and the paddle moves
+// along y = 6. The ball starts at (3, 3) heading down and right, and bounces off walls, blocks and
+// the paddle (directly below it, or where it's heading). Each tick the paddle moves first, then the
+// ball. Breaking the last block wins; letting the ball past the paddle ends the game. Address 0 is
+// read as the number of quarters, as in the real cabinet, but any number will do.
+#[cfg(test)]
+pub fn breakout() -> Vec<i64> {
+    fn draw(a: &mut Assembler, x: impl Into<Arg>, y: impl Into<Arg>, tile: impl Into<Arg>) {
+        a.output(x);
+        a.output(y);
+        a.output(tile);
+    }
+
+    let mut a = Assembler::default();
+
+    // reading the quarters (or 2 there, for free play) as the first instruction leaves it an add
+    // or a multiply, either of which is harmless
+    a.add(Arg::Address(0), Arg::Address(0), "quarters");
+
+    // the ceiling, then the walls down each side
+    for x in 0..=7 {
+        draw(&mut a, x, 0, WALL);
+    }
+    for y in 1..=6 {
+        draw(&mut a, 0, y, WALL);
+        draw(&mut a, 7, y, WALL);
+    }
+
+    // the blocks, each with a variable saying whether it's still there
+    for x in 1..=6 {
+        draw(&mut a, x, 2, BLOCK);
+        a.copy(1, format!("block {}", x).as_str());
+    }
+    a.copy(6, "blocks");
+
+    a.copy(3, "paddle");
+    draw(&mut a, "paddle", 6, PADDLE);
+
+    a.copy(3, "ball x");
+    a.copy(3, "ball y");
+    a.copy(1, "dx");
+    a.copy(1, "dy");
+    draw(&mut a, "ball x", "ball y", BALL);
+
+    a.copy(0, "score");
+    draw(&mut a, -1, 0, "score");
+
+    // each tick the paddle moves with the joystick, unless that would put it in a wall
+    a.label("tick");
+    a.input("joystick");
+    a.add("paddle", "joystick", "moved");
+    a.less_than("moved", 1, "test");
+    a.jump_if_true("test", "ball");
+    a.less_than(6, "moved", "test");
+    a.jump_if_true("test", "ball");
+    draw(&mut a, "paddle", 6, EMPTY);
+    a.copy("moved", "paddle");
+    draw(&mut a, "paddle", 6, PADDLE);
+
+    // then the ball: off the side walls
+    a.label("ball");
+    a.add("ball x", "dx", "next x");
+    a.equals("next x", 0, "test");
+    a.jump_if_true("test", "bounce x");
+    a.equals("next x", 7, "test");
+    a.jump_if_true("test", "bounce x");
+    a.jump("ceiling");
+    a.label("bounce x");
+    a.mul("dx", -1, "dx");
+    a.add("ball x", "dx", "next x");
+
+    // off the ceiling
+    a.label("ceiling");
+    a.add("ball y", "dy", "next y");
+    a.equals("next y", 0, "test");
+    a.jump_if_false("test", "block row");
+    a.mul("dy", -1, "dy");
+    a.add("ball y", "dy", "next y");
+
+    // off a block that's still there, breaking it and scoring 10
+    a.label("block row");
+    a.equals("next y", 2, "test");
+    a.jump_if_false("test", "paddle row");
+    for x in 1..=6 {
+        let block = format!("block {}", x);
+        let next = format!("not block {}", x);
+        a.equals("next x", x, "test");
+        a.jump_if_false("test", &next);
+        a.jump_if_false(block.as_str(), &next);
+        a.copy(0, block.as_str());
+        draw(&mut a, x, 2, EMPTY);
+        a.add("score", 10, "score");
+        draw(&mut a, -1, 0, "score");
+        a.add("blocks", -1, "blocks");
+        a.mul("dy", -1, "dy");
+        a.copy("ball y", "next y");
+        a.jump("paddle row");
+        a.label(&next);
+    }
+
+    // off the paddle if it's below the ball or where the ball is heading, otherwise the ball is
+    // lost
+    a.label("paddle row");
+    a.equals("next y", 6, "test");
+    a.jump_if_false("test", "move");
+    a.equals("ball x", "paddle", "test");
+    a.jump_if_true("test", "bounce y");
+    a.equals("next x", "paddle", "test");
+    a.jump_if_true("test", "bounce y");
+    a.jump("lost");
+    a.label("bounce y");
+    a.mul("dy", -1, "dy");
+    a.add("ball y", "dy", "next y");
+
+    a.label("move");
+    draw(&mut a, "ball x", "ball y", EMPTY);
+    a.copy("next x", "ball x");
+    a.copy("next y", "ball y");
+    draw(&mut a, "ball x", "ball y", BALL);
+    a.jump_if_false("blocks", "over");
+    a.jump("tick");
+
+    // the ball is drawn where the paddle should have been
+    a.label("lost");
+    draw(&mut a, "ball x", "ball y", EMPTY);
+    draw(&mut a, "next x", "next y", BALL);
+
+    a.label("over");
+    a.halt();
+
+    return a.build();
+}
+
+// A tiny cabinet: a wall, a block, the paddle and the ball, then each joystick move plus 10 is
+// added to the score. The block breaks, and the game ends, on the second move.
+#[cfg(test)]
+fn tiny_cabinet() -> Vec<i64> {
+    #[rustfmt::skip]
+    return vec![
+        // draw wall at (0,0), block at (1,0), paddle at (1,2), ball at (1,1), score 0
+        104, 0, 104, 0, 104, 1,
+        104, 1, 104, 0, 104, 2,
+        104, 1, 104, 2, 104, 3,
+        104, 1, 104, 1, 104, 4,
+        104, -1, 104, 0, 104, 0,
+        // 30: read the joystick into 100, score += joystick + 10 into 101, show the score
+        3, 100,
+        1001, 100, 10, 102,
+        1, 101, 102, 101,
+        104, -1, 104, 0, 4, 101,
+        // 46: count moves in 103, on the second one break the block and halt
+        1001, 103, 1, 103,
+        1008, 103, 2, 104,
+        1005, 104, 60,
+        1105, 1, 30,
+        // 60:
+        104, 1, 104, 0, 104, 0,
+        99,
+    ];
+}
+
+#[test]
+fn test_arcade() {
+    let mut arcade = Arcade::new(&tiny_cabinet(), false);
+
+    assert_eq!(arcade.score, Some(0));
+    assert_eq!(arcade.blocks(), 1);
+    assert_eq!(arcade.paddle(), Some((1, 2)));
+    assert_eq!(arcade.ball(), Some((1, 1)));
+    assert_eq!(arcade.state(), MachineState::WaitingOnInput);
+
+    arcade.tick(Joystick::Right);
+    assert_eq!(arcade.score, Some(11));
+    assert!(!arcade.is_over());
+
+    arcade.tick(Joystick::Left);
+    assert_eq!(arcade.score, Some(20));
+    assert!(arcade.is_over());
+    assert!(arcade.won());
+    assert_eq!(arcade.ticks, 2);
+
+    // once it's over the joystick does nothing
+    arcade.tick(Joystick::Right);
+    assert_eq!(arcade.ticks, 2);
+}
+
+#[test]
+fn test_snapshots() {
+    let mut arcade = Arcade::new(&tiny_cabinet(), false);
+    arcade.tick(Joystick::Neutral);
+
+    // a clone can be gone back to
+    let snapshot = arcade.clone();
+    arcade.tick(Joystick::Right);
+    assert!(arcade.is_over());
+
+    let mut arcade = snapshot;
+    assert_eq!(arcade.score, Some(10));
+    arcade.tick(Joystick::Left);
+    assert_eq!(arcade.score, Some(19));
+
+    // and a save comes back the same, through JSON too
+    let arcade = Arcade::new(&tiny_cabinet(), false);
+    let save = arcade.to_save();
+    let json = serde_json::to_string(&save).unwrap();
+    let loaded: SaveGame = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded, save);
+
+    let mut loaded = Arcade::from_save(loaded);
+    assert_eq!(loaded.screen.get(1, 0), &BLOCK);
+    loaded.tick(Joystick::Right);
+    assert_eq!(loaded.score, Some(11));
+}
+
+#[test]
+fn test_high_scores() {
+    let mut arcade = Arcade::new(&tiny_cabinet(), false);
+    let mut table = HighScores::default();
+    assert_eq!(table.best(), None);
+
+    assert_eq!(table.add(&arcade), Some(0));
+    arcade.tick(Joystick::Right);
+    arcade.tick(Joystick::Right);
+    assert_eq!(table.add(&arcade), Some(0));
+    assert_eq!(table.best(), Some(22));
+    assert!(table.scores[0].won);
+
+    // a tie goes after the score that was there first
+    assert_eq!(table.add(&arcade), Some(1));
+
+    for _ in 0..TABLE_SIZE {
+        table.add(&arcade);
+    }
+    assert_eq!(table.scores.len(), TABLE_SIZE);
+    assert_eq!(table.add(&Arcade::new(&tiny_cabinet(), false)), None);
+}
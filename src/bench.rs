@@ -0,0 +1,305 @@
+use std::fmt;
+use std::fs;
+use std::hint;
+use std::io;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::loader::{LoadError, Source};
+use crate::solution::Solution;
+
+// Times each phase (parse, part 1, part 2) of a solution over a number of runs. Every benchmark
+// run is appended to a local JSON file so the next one can be compared against it.
+
+pub const DEFAULT_PATH: &str = "benchmarks.json";
+
+// phases quicker than this are mostly timer noise, don't call them regressions
+pub const NOISE_FLOOR_NS: u64 = 100_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PhaseTiming {
+    pub median_ns: u64,
+    pub min_ns: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DayTiming {
+    pub day: u32,
+    pub title: String,
+    pub parse: PhaseTiming,
+    pub part1: PhaseTiming,
+    pub part2: PhaseTiming,
+}
+
+impl DayTiming {
+    pub fn phases(&self) -> [(&'static str, PhaseTiming); 3] {
+        return [
+            ("parse", self.parse),
+            ("part 1", self.part1),
+            ("part 2", self.part2),
+        ];
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BenchRun {
+    // seconds since the unix epoch
+    pub timestamp: u64,
+
+    // how many times each phase was run
+    pub runs: usize,
+
+    pub days: Vec<DayTiming>,
+}
+
+impl BenchRun {
+    pub fn new(runs: usize) -> BenchRun {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+
+        return BenchRun {
+            timestamp,
+            runs,
+            days: Vec::new(),
+        };
+    }
+}
+
+pub fn summarize(samples: &[Duration]) -> PhaseTiming {
+    let mut ns: Vec<u64> = samples.iter().map(|d| d.as_nanos() as u64).collect();
+    ns.sort_unstable();
+
+    if ns.is_empty() {
+        return PhaseTiming {
+            median_ns: 0,
+            min_ns: 0,
+        };
+    }
+
+    let middle = ns.len() / 2;
+    let median_ns = if ns.len().is_multiple_of(2) {
+        (ns[middle - 1] + ns[middle]) / 2
+    } else {
+        ns[middle]
+    };
+
+    return PhaseTiming {
+        median_ns,
+        min_ns: ns[0],
+    };
+}
+
+#[test]
+fn test_summarize() {
+    let ms = Duration::from_millis;
+
+    assert_eq!(
+        summarize(&[ms(3), ms(1), ms(2)]),
+        PhaseTiming {
+            median_ns: 2_000_000,
+            min_ns: 1_000_000,
+        }
+    );
+    assert_eq!(
+        summarize(&[ms(4), ms(1), ms(2), ms(9)]),
+        PhaseTiming {
+            median_ns: 3_000_000,
+            min_ns: 1_000_000,
+        }
+    );
+    assert_eq!(summarize(&[]).median_ns, 0);
+}
+
+// the answer goes through black_box so the work that made it can't be optimized away
+fn time<T>(f: impl FnOnce() -> T) -> Duration {
+    let start = Instant::now();
+    hint::black_box(f());
+    return start.elapsed();
+}
+
+// Parse from the source and answer both parts, runs times over. The source is read again for
+// every parse, so it can't be stdin.
+pub fn bench_solution(
+    solution: &mut dyn Solution,
+    source: &Source,
+    runs: usize,
+) -> Result<DayTiming, LoadError> {
+    let mut parse: Vec<Duration> = Vec::with_capacity(runs);
+    let mut part1: Vec<Duration> = Vec::with_capacity(runs);
+    let mut part2: Vec<Duration> = Vec::with_capacity(runs);
+
+    for _ in 0..runs {
+        let start = Instant::now();
+        solution.parse(source)?;
+        parse.push(start.elapsed());
+
+        part1.push(time(|| solution.part1()));
+        part2.push(time(|| solution.part2()));
+    }
+
+    return Ok(DayTiming {
+        day: solution.day(),
+        title: solution.title().to_string(),
+        parse: summarize(&parse),
+        part1: summarize(&part1),
+        part2: summarize(&part2),
+    });
+}
+
+#[derive(Debug)]
+pub enum HistoryError {
+    Io(String, io::Error),
+    Json(String, serde_json::Error),
+}
+
+impl fmt::Display for HistoryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HistoryError::Io(path, e) => write!(f, "could not access {}: {}", path, e),
+            HistoryError::Json(path, e) => write!(f, "{} is not a benchmark history: {}", path, e),
+        }
+    }
+}
+
+impl std::error::Error for HistoryError {}
+
+// Every saved benchmark run, oldest first.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct History {
+    pub runs: Vec<BenchRun>,
+}
+
+impl History {
+    // a missing file is an empty history
+    pub fn load(path: &str) -> Result<History, HistoryError> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(History::default()),
+            Err(e) => return Err(HistoryError::Io(path.to_string(), e)),
+        };
+
+        return serde_json::from_str(&text).map_err(|e| HistoryError::Json(path.to_string(), e));
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), HistoryError> {
+        let text = serde_json::to_string_pretty(self)
+            .map_err(|e| HistoryError::Json(path.to_string(), e))?;
+        return fs::write(path, text + "\n").map_err(|e| HistoryError::Io(path.to_string(), e));
+    }
+
+    // the most recent saved timing of a day, if it was ever benchmarked
+    pub fn previous(&self, day: u32) -> Option<&DayTiming> {
+        return self
+            .runs
+            .iter()
+            .rev()
+            .flat_map(|run| run.days.iter())
+            .find(|timing| timing.day == day);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Regression {
+    pub day: u32,
+    pub phase: &'static str,
+    pub previous_ns: u64,
+    pub current_ns: u64,
+}
+
+// Phases whose median got slower than the previous timing by more than threshold (0.25 is 25%).
+pub fn regressions(previous: &DayTiming, current: &DayTiming, threshold: f64) -> Vec<Regression> {
+    let mut output: Vec<Regression> = Vec::new();
+
+    for ((phase, before), (_, after)) in previous.phases().iter().zip(current.phases().iter()) {
+        if after.median_ns < NOISE_FLOOR_NS {
+            continue;
+        }
+
+        if after.median_ns as f64 > before.median_ns as f64 * (1.0 + threshold) {
+            output.push(Regression {
+                day: current.day,
+                phase,
+                previous_ns: before.median_ns,
+                current_ns: after.median_ns,
+            });
+        }
+    }
+
+    return output;
+}
+
+#[test]
+fn test_regressions() {
+    let timing = |parse: u64, part1: u64, part2: u64| DayTiming {
+        day: 16,
+        title: "Flawed Frequency Transmission".to_string(),
+        parse: PhaseTiming {
+            median_ns: parse,
+            min_ns: parse,
+        },
+        part1: PhaseTiming {
+            median_ns: part1,
+            min_ns: part1,
+        },
+        part2: PhaseTiming {
+            median_ns: part2,
+            min_ns: part2,
+        },
+    };
+
+    let before = timing(1_000, 2_000_000, 9_000_000);
+
+    // parse tripled but is far below the noise floor, part 1 is only a little slower
+    let after = timing(3_000, 2_200_000, 20_000_000);
+
+    assert_eq!(
+        regressions(&before, &after, 0.25),
+        vec![Regression {
+            day: 16,
+            phase: "part 2",
+            previous_ns: 9_000_000,
+            current_ns: 20_000_000,
+        }]
+    );
+    assert_eq!(regressions(&after, &before, 0.25), vec![]);
+
+    // the most recent run that has the day wins
+    let mut history = History::default();
+    let mut run = BenchRun::new(5);
+    run.days.push(before.clone());
+    history.runs.push(run);
+    let mut run = BenchRun::new(5);
+    run.days.push(after.clone());
+    history.runs.push(run);
+    history.runs.push(BenchRun::new(5));
+
+    assert_eq!(history.previous(16), Some(&after));
+    assert_eq!(history.previous(1), None);
+
+    let text = serde_json::to_string(&history).unwrap();
+    assert_eq!(serde_json::from_str::<History>(&text).unwrap(), history);
+}
+
+pub fn format_duration(ns: u64) -> String {
+    if ns < 1_000 {
+        return format!("{}ns", ns);
+    }
+    if ns < 1_000_000 {
+        return format!("{:.1}µs", ns as f64 / 1e3);
+    }
+    if ns < 1_000_000_000 {
+        return format!("{:.1}ms", ns as f64 / 1e6);
+    }
+    return format!("{:.2}s", ns as f64 / 1e9);
+}
+
+#[test]
+fn test_format_duration() {
+    assert_eq!(format_duration(999), "999ns");
+    assert_eq!(format_duration(1_500), "1.5µs");
+    assert_eq!(format_duration(2_345_678), "2.3ms");
+    assert_eq!(format_duration(12_345_678_901), "12.35s");
+}
@@ -0,0 +1,458 @@
+use serde::Serialize;
+
+use crate::arcade::{Arcade, Joystick};
+
+// Ways of playing day 13's arcade game without a person at the joystick. Each tick a controller
+// looks at the game as it is and says which way to push the joystick; run plays a whole game
+// with one and reports how it went.
+
+pub trait Controller {
+    fn name(&self) -> &'static str;
+
+    // which way to push the joystick next
+    fn joystick(&mut self, arcade: &Arcade) -> Joystick;
+}
+
+// far more moves than any real game takes, so a controller that keeps the ball in play without
+// ever finishing still stops
+pub const MAX_TICKS: u64 = 1_000_000;
+
+// the controllers that can be chosen by name
+pub const NAMES: [&str; 3] = ["greedy", "predictive", "replay"];
+
+pub fn controller(name: &str) -> Option<Box<dyn Controller>> {
+    match name {
+        "greedy" => return Some(Box::new(Greedy)),
+        "predictive" => return Some(Box::new(Predictive::default())),
+        "replay" => return Some(Box::new(ReplayOptimal::default())),
+        _ => return None,
+    }
+}
+
+fn toward(from: i32, to: i32) -> Joystick {
+    if to < from {
+        return Joystick::Left;
+    }
+    if to > from {
+        return Joystick::Right;
+    }
+    return Joystick::Neutral;
+}
+
+// Chases the ball: moves the paddle one step toward where the ball is now.
+pub struct Greedy;
+
+impl Controller for Greedy {
+    fn name(&self) -> &'static str {
+        return "greedy";
+    }
+
+    fn joystick(&mut self, arcade: &Arcade) -> Joystick {
+        match (arcade.paddle(), arcade.ball()) {
+            (Some(p), Some(b)) => return toward(p.0, b.0),
+            _ => return Joystick::Neutral,
+        }
+    }
+}
+
+// Where a ball at position heading along velocity will be when it next comes down to row, if it
+// only bounces off the walls around the screen. Blocks aren't taken into account.
+pub fn predict_landing(
+    arcade: &Arcade,
+    position: (i32, i32),
+    velocity: (i32, i32),
+    row: i32,
+) -> i32 {
+    let bounds = match arcade.screen.bounds() {
+        Some(bounds) => bounds,
+        None => return position.0,
+    };
+
+    // inside the walls
+    let (left, right, top) = (bounds.min_x + 1, bounds.max_x - 1, bounds.min_y + 1);
+
+    let (mut x, mut y) = position;
+    let (mut dx, mut dy) = velocity;
+    if dy == 0 {
+        return x;
+    }
+
+    // down and back up, from anywhere, is never more than twice the height
+    for _ in 0..2 * bounds.height() {
+        if y == row && dy > 0 {
+            return x;
+        }
+
+        if x + dx < left || x + dx > right {
+            dx = -dx;
+        }
+        if y + dy < top {
+            dy = -dy;
+        }
+
+        x += dx;
+        y += dy;
+    }
+
+    return x;
+}
+
+// Works out where the ball is going from where it was last tick, follows its path off the walls
+// down to the paddle and heads there. Until it has seen the ball move it chases it.
+#[derive(Default)]
+pub struct Predictive {
+    last_ball: Option<(i32, i32)>,
+}
+
+impl Controller for Predictive {
+    fn name(&self) -> &'static str {
+        return "predictive";
+    }
+
+    fn joystick(&mut self, arcade: &Arcade) -> Joystick {
+        let (paddle, ball) = match (arcade.paddle(), arcade.ball()) {
+            (Some(p), Some(b)) => (p, b),
+            _ => return Joystick::Neutral,
+        };
+
+        let last = self.last_ball.replace(ball);
+
+        let velocity = match last {
+            Some(last) if last != ball => (ball.0 - last.0, ball.1 - last.1),
+            _ => return toward(paddle.0, ball.0),
+        };
+
+        // going up it'll come back down; the paddle is hit from the row above it
+        let target = predict_landing(arcade, ball, velocity, paddle.1 - 1);
+
+        return toward(paddle.0, target);
+    }
+}
+
+// Knows exactly where the ball will come down, blocks and all, by playing the game on from a
+// snapshot with the paddle left where it is until the ball reaches the paddle's row. Then it
+// heads there, and looks again once the ball is on its way back up.
+#[derive(Default)]
+pub struct ReplayOptimal {
+    target: Option<i32>,
+}
+
+// how far ahead to look for the ball coming down before giving up
+const LOOKAHEAD: u64 = 10_000;
+
+impl ReplayOptimal {
+    fn landing(arcade: &Arcade, row: i32) -> Option<i32> {
+        let mut ghost = arcade.clone();
+
+        for _ in 0..LOOKAHEAD {
+            match ghost.ball() {
+                Some(ball) if ball.1 == row => return Some(ball.0),
+                _ => {}
+            }
+            if ghost.is_over() {
+                return None;
+            }
+            ghost.tick(Joystick::Neutral);
+        }
+
+        return None;
+    }
+}
+
+impl Controller for ReplayOptimal {
+    fn name(&self) -> &'static str {
+        return "replay";
+    }
+
+    fn joystick(&mut self, arcade: &Arcade) -> Joystick {
+        let (paddle, ball) = match (arcade.paddle(), arcade.ball()) {
+            (Some(p), Some(b)) => (p, b),
+            _ => return Joystick::Neutral,
+        };
+        let row = paddle.1 - 1;
+
+        if self.target.is_none() {
+            self.target = ReplayOptimal::landing(arcade, row);
+        }
+
+        let target = self.target.unwrap_or(ball.0);
+
+        // it's about to bounce, so the next landing is looked for from the next tick
+        if ball.1 == row {
+            self.target = None;
+        }
+
+        return toward(paddle.0, target);
+    }
+}
+
+// The game after a joystick move, or at the start for tick 0.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Sample {
+    pub tick: u64,
+    pub blocks: usize,
+    pub score: i64,
+    pub joystick: i64,
+    pub paddle: Option<i32>,
+    pub ball: Option<(i32, i32)>,
+}
+
+// A move that scored: how many points, and how many blocks were broken for them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Increment {
+    pub tick: u64,
+    pub points: i64,
+    pub blocks: usize,
+}
+
+// How a game went.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Report {
+    pub controller: String,
+    pub score: i64,
+
+    // blocks on the screen before the first move, which is the part 1 answer
+    pub initial_blocks: usize,
+
+    // blocks left on the screen at the end
+    pub blocks: usize,
+    pub ticks: u64,
+    pub won: bool,
+
+    // moves that actually shifted the paddle
+    pub paddle_moves: u64,
+    pub increments: Vec<Increment>,
+
+    // every tick, for writing out as CSV
+    #[serde(skip)]
+    pub timeline: Vec<Sample>,
+}
+
+impl Report {
+    // (fewest, most, mean) points for one block
+    pub fn points_per_block(&self) -> Option<(i64, i64, f64)> {
+        let blocks: usize = self.increments.iter().map(|i| i.blocks).sum();
+        if blocks == 0 {
+            return None;
+        }
+
+        // a move that broke several blocks at once tells the total only, so it is left out of
+        // the fewest and most
+        let single: Vec<i64> = self
+            .increments
+            .iter()
+            .filter(|i| i.blocks == 1)
+            .map(|i| i.points)
+            .collect();
+        let points: i64 = self.increments.iter().map(|i| i.points).sum();
+
+        return Some((
+            single.iter().copied().min().unwrap_or(0),
+            single.iter().copied().max().unwrap_or(0),
+            points as f64 / blocks as f64,
+        ));
+    }
+
+    pub fn timeline_csv(&self) -> String {
+        let mut csv =
+            String::from("controller,tick,blocks,score,joystick,paddle_x,ball_x,ball_y\n");
+        let field = |v: Option<i32>| v.map(|v| v.to_string()).unwrap_or_default();
+
+        for s in &self.timeline {
+            csv.push_str(&format!(
+                "{},{},{},{},{},{},{},{}\n",
+                self.controller,
+                s.tick,
+                s.blocks,
+                s.score,
+                s.joystick,
+                field(s.paddle),
+                field(s.ball.map(|b| b.0)),
+                field(s.ball.map(|b| b.1)),
+            ));
+        }
+
+        return csv;
+    }
+}
+
+fn sample(arcade: &Arcade, joystick: Joystick) -> Sample {
+    return Sample {
+        tick: arcade.ticks,
+        blocks: arcade.blocks(),
+        score: arcade.score.unwrap_or_default(),
+        joystick: joystick.value(),
+        paddle: arcade.paddle().map(|p| p.0),
+        ball: arcade.ball(),
+    };
+}
+
+// Plays the game with a controller until it's over or max_ticks joystick moves have been made.
+// observe is shown the game before every move and once at the end, and stops the game early by
+// returning false.
+pub fn run(
+    mut arcade: Arcade,
+    controller: &mut dyn Controller,
+    max_ticks: u64,
+    mut observe: impl FnMut(&Arcade) -> bool,
+) -> Report {
+    let mut stopped = false;
+
+    let initial_blocks = arcade.blocks();
+    let mut timeline = vec![sample(&arcade, Joystick::Neutral)];
+    let mut increments: Vec<Increment> = Vec::new();
+    let mut paddle_moves = 0;
+
+    while !arcade.is_over() && arcade.ticks < max_ticks {
+        if !observe(&arcade) {
+            stopped = true;
+            break;
+        }
+
+        let joystick = controller.joystick(&arcade);
+        arcade.tick(joystick);
+
+        let before = timeline.last().unwrap();
+        let after = sample(&arcade, joystick);
+
+        if after.paddle != before.paddle {
+            paddle_moves += 1;
+        }
+        if after.score != before.score {
+            increments.push(Increment {
+                tick: after.tick,
+                points: after.score - before.score,
+                blocks: before.blocks.saturating_sub(after.blocks),
+            });
+        }
+
+        timeline.push(after);
+    }
+
+    if !stopped {
+        observe(&arcade);
+    }
+
+    return Report {
+        controller: controller.name().to_string(),
+        score: arcade.score.unwrap_or_default(),
+        initial_blocks,
+        blocks: arcade.blocks(),
+        ticks: arcade.ticks,
+        won: arcade.won(),
+        paddle_moves,
+        increments,
+        timeline,
+    };
+}
+
+#[cfg(test)]
+use crate::arcade;
+
+#[test]
+fn test_controllers() {
+    for name in NAMES.iter() {
+        let mut controller = controller(name).unwrap();
+        assert_eq!(controller.name(), *name);
+
+        let arcade = Arcade::new(&arcade::breakout(), false);
+        let report = run(arcade, controller.as_mut(), 1000, |_| true);
+
+        assert!(report.won, "{} lost: {:?}", name, report);
+        assert_eq!(report.score, 60);
+        assert_eq!(report.blocks, 0);
+    }
+
+    assert!(controller("random").is_none());
+}
+
+#[test]
+fn test_run_stops() {
+    // doing nothing loses
+    struct Idle;
+    impl Controller for Idle {
+        fn name(&self) -> &'static str {
+            return "idle";
+        }
+        fn joystick(&mut self, _arcade: &Arcade) -> Joystick {
+            return Joystick::Neutral;
+        }
+    }
+
+    let report = run(
+        Arcade::new(&arcade::breakout(), false),
+        &mut Idle,
+        1000,
+        |_| true,
+    );
+    assert!(!report.won);
+    assert!(report.blocks > 0);
+
+    // out of ticks
+    let report = run(
+        Arcade::new(&arcade::breakout(), false),
+        &mut Greedy,
+        3,
+        |_| true,
+    );
+    assert_eq!(report.ticks, 3);
+
+    // and stopped by whatever is watching, which then isn't shown the end
+    let mut seen = 0;
+    let report = run(
+        Arcade::new(&arcade::breakout(), false),
+        &mut Greedy,
+        1000,
+        |_| {
+            seen += 1;
+            return seen < 5;
+        },
+    );
+    assert_eq!(report.ticks, 4);
+}
+
+#[test]
+fn test_report() {
+    let report = run(
+        Arcade::new(&arcade::breakout(), true),
+        &mut Greedy,
+        1000,
+        |_| true,
+    );
+
+    assert_eq!(report.initial_blocks, 6);
+    assert_eq!(report.timeline.len() as u64, report.ticks + 1);
+    assert_eq!(report.timeline[0].blocks, 6);
+    assert_eq!(report.timeline.last().unwrap().blocks, 0);
+
+    // every block is worth 10, and they're broken one at a time
+    assert_eq!(report.increments.len(), 6);
+    assert!(report
+        .increments
+        .iter()
+        .all(|i| i.points == 10 && i.blocks == 1));
+    assert_eq!(report.points_per_block(), Some((10, 10, 10.0)));
+
+    // the paddle doesn't have to move every tick
+    assert!(report.paddle_moves > 0 && report.paddle_moves < report.ticks);
+
+    let csv = report.timeline_csv();
+    let mut lines = csv.lines();
+    assert_eq!(
+        lines.next(),
+        Some("controller,tick,blocks,score,joystick,paddle_x,ball_x,ball_y")
+    );
+    assert_eq!(lines.next(), Some("greedy,0,6,0,0,3,3,3"));
+    assert_eq!(lines.count() as u64, report.ticks);
+}
+
+#[test]
+fn test_predict_landing() {
+    let arcade = Arcade::new(&arcade::breakout(), false);
+
+    // the walls are at x = 0 and 7 and the ceiling at y = 0
+    assert_eq!(predict_landing(&arcade, (2, 3), (1, 1), 5), 4);
+    assert_eq!(predict_landing(&arcade, (5, 3), (1, 1), 5), 5);
+    assert_eq!(predict_landing(&arcade, (3, 2), (-1, -1), 5), 4);
+    assert_eq!(predict_landing(&arcade, (3, 5), (1, 1), 5), 3);
+}
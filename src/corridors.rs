@@ -0,0 +1,288 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use petgraph::graph::{DefaultIx, NodeIndex, UnGraph};
+use petgraph::visit::EdgeRef;
+
+use crate::grid::{Grid, OFFSETS4};
+
+// Mazes are mostly corridors, and a search only cares about where they meet and what's in them.
+// Contracting a grid keeps the interesting cells and the junctions, and turns each corridor between
+// them into one edge, weighted by its length. Dead ends with nothing interesting in them lead
+// nowhere, so they're dropped altogether, as are loops that don't go past anything kept.
+//
+// It looks at each cell a few times at most: dead ends are worn away from their ends with a queue,
+// and then each corridor is walked once from either end.
+//
+// Each edge remembers the cells it stands for, so a route found through the graph can be put back
+// into the grid a cell at a time.
+
+pub struct Corridors<N> {
+    pub graph: UnGraph<N, usize>,
+
+    // where each node came from
+    pub positions: HashMap<(i32, i32), NodeIndex<DefaultIx>>,
+
+    // the cells in between the ends of each edge, by the edge's index, from its source to its
+    // target
+    pub cells: Vec<Vec<(i32, i32)>>,
+
+    // each node's cell, by the node's index
+    places: Vec<(i32, i32)>,
+}
+
+impl<N> Corridors<N> {
+    pub fn node_index(&self, x: i32, y: i32) -> Option<NodeIndex<DefaultIx>> {
+        return self.positions.get(&(x, y)).copied();
+    }
+
+    pub fn position(&self, index: NodeIndex<DefaultIx>) -> (i32, i32) {
+        return self.places[index.index()];
+    }
+
+    // The cells along the shortest corridor between two nodes, in order from one to the other and
+    // not counting either. None if they aren't joined.
+    pub fn corridor(
+        &self,
+        from: NodeIndex<DefaultIx>,
+        to: NodeIndex<DefaultIx>,
+    ) -> Option<Vec<(i32, i32)>> {
+        let edge = self
+            .graph
+            .edges_connecting(from, to)
+            .min_by_key(|edge| *edge.weight())?;
+
+        let mut cells = self.cells[edge.id().index()].clone();
+        if self.graph.edge_endpoints(edge.id()) != Some((from, to)) {
+            cells.reverse();
+        }
+        return Some(cells);
+    }
+
+    // A route through the graph, as every cell along it from its first node to its last. None if
+    // two nodes one after the other in the route aren't joined.
+    pub fn expand(&self, route: &[NodeIndex<DefaultIx>]) -> Option<Vec<(i32, i32)>> {
+        let mut cells = Vec::new();
+        if let Some(first) = route.first() {
+            cells.push(self.position(*first));
+        }
+
+        for leg in route.windows(2) {
+            cells.extend(self.corridor(leg[0], leg[1])?);
+            cells.push(self.position(leg[1]));
+        }
+
+        return Some(cells);
+    }
+}
+
+fn neighbours((x, y): (i32, i32)) -> impl Iterator<Item = (i32, i32)> {
+    return OFFSETS4.iter().map(move |(dx, dy)| (x + dx, y + dy));
+}
+
+// Contracts the cells of a grid that open says can be moved through, keeping those interesting
+// says to. node makes the graph's node for a kept cell.
+pub fn contract<T: Clone, N>(
+    grid: &Grid<T>,
+    open: impl Fn(&T) -> bool,
+    interesting: impl Fn((i32, i32), &T) -> bool,
+    node: impl Fn((i32, i32), &T) -> N,
+) -> Corridors<N> {
+    let mut alive: HashSet<(i32, i32)> = grid
+        .iter()
+        .filter(|(_, cell)| open(cell))
+        .map(|(position, _)| position)
+        .collect();
+    let interesting = |p: (i32, i32)| interesting(p, grid.get(p.0, p.1));
+
+    let mut degree: HashMap<(i32, i32), usize> = HashMap::new();
+    for position in alive.iter() {
+        let open_neighbours = neighbours(*position).filter(|n| alive.contains(n)).count();
+        degree.insert(*position, open_neighbours);
+    }
+
+    // wear dead ends away, back to where they join something
+    let mut dead_ends: VecDeque<(i32, i32)> = alive
+        .iter()
+        .filter(|p| degree[p] <= 1 && !interesting(**p))
+        .copied()
+        .collect();
+    while let Some(position) = dead_ends.pop_front() {
+        if !alive.remove(&position) {
+            continue;
+        }
+        for n in neighbours(position) {
+            if alive.contains(&n) {
+                let d = degree.get_mut(&n).unwrap();
+                *d -= 1;
+                if *d <= 1 && !interesting(n) {
+                    dead_ends.push_back(n);
+                }
+            }
+        }
+    }
+
+    // sorted, so the graph comes out the same every time
+    let mut kept: Vec<(i32, i32)> = alive
+        .iter()
+        .filter(|p| interesting(**p) || degree[p] != 2)
+        .copied()
+        .collect();
+    kept.sort_by_key(|(x, y)| (*y, *x));
+
+    let mut corridors = Corridors {
+        graph: UnGraph::default(),
+        positions: HashMap::new(),
+        cells: Vec::new(),
+        places: kept.clone(),
+    };
+    for position in kept.iter() {
+        let index = corridors
+            .graph
+            .add_node(node(*position, grid.get(position.0, position.1)));
+        corridors.positions.insert(*position, index);
+    }
+
+    for from in kept.iter() {
+        for first in neighbours(*from) {
+            if !alive.contains(&first) {
+                continue;
+            }
+
+            // along the corridor, which only goes one way on from each cell in it
+            let mut previous = *from;
+            let mut current = first;
+            let mut cells = Vec::new();
+            while !corridors.positions.contains_key(&current) {
+                let next = neighbours(current)
+                    .find(|n| *n != previous && alive.contains(n))
+                    .unwrap();
+                cells.push(current);
+                previous = current;
+                current = next;
+            }
+
+            // each corridor is walked from both ends, but only added from one; a loop back to
+            // where it started is never the shortest way anywhere
+            let to = current;
+            if (*from, first) < (to, previous) && *from != to {
+                corridors.graph.add_edge(
+                    corridors.positions[from],
+                    corridors.positions[&to],
+                    cells.len() + 1,
+                );
+                corridors.cells.push(cells);
+            }
+        }
+    }
+
+    return corridors;
+}
+
+#[cfg(test)]
+fn picture(rows: &[&str]) -> Grid<char> {
+    let mut grid = Grid::new('#');
+    for (y, row) in rows.iter().enumerate() {
+        for (x, c) in row.chars().enumerate() {
+            grid.set(x as i32, y as i32, c);
+        }
+    }
+    return grid;
+}
+
+#[cfg(test)]
+fn edges(corridors: &Corridors<char>) -> Vec<(char, char, usize)> {
+    let mut edges: Vec<(char, char, usize)> = corridors
+        .graph
+        .edge_references()
+        .map(|e| {
+            let (a, b) = (corridors.graph[e.source()], corridors.graph[e.target()]);
+            return (a.min(b), a.max(b), *e.weight());
+        })
+        .collect();
+    edges.sort();
+    return edges;
+}
+
+#[test]
+fn test_contract() {
+    // letters are interesting; the corridors off to the right and past c go nowhere
+    let grid = picture(&[
+        "#########",
+        "#a...#..#",
+        "#.##.#.##",
+        "#....b..#",
+        "####.####",
+        "####c...#",
+        "#######.#",
+    ]);
+    let corridors = contract(&grid, |c| *c != '#', |_, c| c.is_alphabetic(), |_, c| *c);
+
+    // the letters, and the junction left of b; the one right of b only led to dead ends
+    assert_eq!(corridors.graph.node_count(), 4);
+    let junction = corridors.node_index(4, 3).unwrap();
+    assert_eq!(corridors.graph[junction], '.');
+    assert_eq!(corridors.node_index(6, 3), None);
+    assert_eq!(corridors.node_index(7, 5), None);
+
+    // a to the junction both ways round the loop, and on from it to b and c
+    assert_eq!(
+        edges(&corridors),
+        vec![('.', 'a', 5), ('.', 'a', 5), ('.', 'b', 1), ('.', 'c', 2)]
+    );
+}
+
+#[test]
+fn test_contract_corridor() {
+    // nothing but a corridor, and a loop hanging off the end of it
+    let grid = picture(&["a....b...", "#####.#.#", "#####...#"]);
+    let corridors = contract(&grid, |c| *c != '#', |_, c| c.is_alphabetic(), |_, c| *c);
+    assert_eq!(edges(&corridors), vec![('a', 'b', 5)]);
+
+    // nothing interesting at all, so nothing's kept
+    let grid = picture(&["#...#", "#.#.#", "#...#"]);
+    let corridors = contract(&grid, |c| *c != '#', |_, c| c.is_alphabetic(), |_, c| *c);
+    assert_eq!(corridors.graph.node_count(), 0);
+
+    // what counts as interesting can depend on where it is
+    let grid = picture(&["......"]);
+    let corridors = contract(
+        &grid,
+        |c| *c != '#',
+        |(x, _), _| x == 1 || x == 4,
+        |_, c| *c,
+    );
+    assert_eq!(corridors.graph.node_count(), 2);
+    assert_eq!(edges(&corridors), vec![('.', '.', 3)]);
+}
+
+#[test]
+fn test_expand() {
+    let grid = picture(&["a.##", "#.b.", "#c##"]);
+    let corridors = contract(&grid, |c| *c != '#', |_, c| c.is_alphabetic(), |_, c| *c);
+
+    let a = corridors.node_index(0, 0).unwrap();
+    let b = corridors.node_index(2, 1).unwrap();
+    let c = corridors.node_index(1, 2).unwrap();
+    let junction = corridors.node_index(1, 1).unwrap();
+    assert_eq!(corridors.position(junction), (1, 1));
+
+    // each edge's cells add up to its length, whichever way round it's gone along
+    for edge in corridors.graph.edge_references() {
+        assert_eq!(corridors.cells[edge.id().index()].len() + 1, *edge.weight());
+    }
+    assert_eq!(corridors.corridor(a, junction), Some(vec![(1, 0)]));
+    assert_eq!(corridors.corridor(junction, a), Some(vec![(1, 0)]));
+    assert_eq!(corridors.corridor(a, b), None);
+
+    assert_eq!(
+        corridors.expand(&[a, junction, c]),
+        Some(vec![(0, 0), (1, 0), (1, 1), (1, 2)])
+    );
+    assert_eq!(
+        corridors.expand(&[b, junction, a]),
+        Some(vec![(2, 1), (1, 1), (1, 0), (0, 0)])
+    );
+    assert_eq!(corridors.expand(&[c]), Some(vec![(1, 2)]));
+    assert_eq!(corridors.expand(&[]), Some(vec![]));
+    assert_eq!(corridors.expand(&[a, c]), None);
+}
@@ -1,5 +1,5 @@
-use std::io::{self, BufRead};
-use std::convert;
+use crate::loader::{self, LoadError, Source};
+use crate::solution::{Answer, Solution};
 
 // Fuel required to launch a given module is based on its mass.
 // Specifically, to find the fuel required for a module, take its mass,
@@ -28,7 +28,7 @@ fn test_get_fuel() {
 
 fn get_fuel_part_2(mass: i32) -> i32 {
     let fuel = get_fuel(mass);
-    if (fuel < 0) {
+    if fuel < 0 {
         return 0;
     }
     return fuel + get_fuel_part_2(fuel);
@@ -41,19 +41,32 @@ fn test_get_fuel_part_2() {
     assert_eq!(get_fuel_part_2(100756), 50346);
 }
 
-fn main() {
-    let reader = io::stdin();
-    let numbers: Vec<i32> =
-        reader.lock()
-              .lines()
-              .map(|s| s.unwrap().parse::<i32>().unwrap())
-              .collect();
+#[derive(Default)]
+pub struct Day01 {
+    numbers: Vec<i32>,
+}
+
+impl Solution for Day01 {
+    fn day(&self) -> u32 {
+        return 1;
+    }
 
-    let mut fuel: i32 = 0;
+    fn title(&self) -> &'static str {
+        return "The Tyranny of the Rocket Equation";
+    }
 
-    for i in 0..numbers.len() {
-        fuel += get_fuel_part_2(numbers[i]);
+    fn parse(&mut self, source: &Source) -> Result<(), LoadError> {
+        self.numbers = loader::load_integers::<i32>(source)?;
+        return Ok(());
     }
 
-    println!("{}", fuel);
+    fn part1(&self) -> Answer {
+        let fuel: i32 = self.numbers.iter().map(|m| get_fuel(*m)).sum();
+        return fuel.into();
+    }
+
+    fn part2(&self) -> Answer {
+        let fuel: i32 = self.numbers.iter().map(|m| get_fuel_part_2(*m)).sum();
+        return fuel.into();
+    }
 }
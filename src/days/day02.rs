@@ -0,0 +1,103 @@
+use crate::intcode;
+use crate::loader::{self, LoadError, Source};
+use crate::solution::{Answer, Solution};
+
+// An Intcode program is a list of integers separated by commas.
+
+// start by looking at the first integer (called position 0).
+
+// you will find an opcode - either 1, 2, or 99.
+
+// 99 means that the program is finished
+
+// Opcode 1 adds together numbers read from two positions and stores
+// the result in a third position. The three integers immediately after
+// the opcode tell you these three positions - the first two indicate
+// the positions from which you should read the input values, and the
+// third indicates the position at which the output should be stored.
+
+// Opcode 2 works exactly like opcode 1, except it multiplies the two
+// inputs instead of adding them.
+
+// Move to the next one by stepping forward 4 positions.
+
+fn intcode_program(input: Vec<i64>) -> Vec<i64> {
+    let len = input.len();
+    let (memory, _) = intcode::run_program(input, &[]).expect("the program asked for input");
+    return memory.to_vec(len);
+}
+
+#[test]
+fn test_intcode_program() {
+    assert_eq!(intcode_program(vec![1, 0, 0, 0, 99]), vec![2, 0, 0, 0, 99]);
+    assert_eq!(intcode_program(vec![2, 3, 0, 3, 99]), vec![2, 3, 0, 6, 99]);
+    assert_eq!(
+        intcode_program(vec![2, 4, 4, 5, 99, 0]),
+        vec![2, 4, 4, 5, 99, 9801]
+    );
+    assert_eq!(
+        intcode_program(vec![1, 1, 1, 4, 99, 5, 6, 0, 99]),
+        vec![30, 1, 1, 4, 2, 5, 6, 0, 99]
+    );
+}
+
+fn run_with(program: &[i64], noun: i64, verb: i64) -> i64 {
+    let mut input: Vec<i64> = program.to_vec();
+
+    input[1] = noun;
+    input[2] = verb;
+
+    return intcode_program(input)[0];
+}
+
+#[derive(Default)]
+pub struct Day02 {
+    numbers: Vec<i64>,
+}
+
+impl Solution for Day02 {
+    fn day(&self) -> u32 {
+        return 2;
+    }
+
+    fn title(&self) -> &'static str {
+        return "1202 Program Alarm";
+    }
+
+    fn parse(&mut self, source: &Source) -> Result<(), LoadError> {
+        self.numbers = loader::load_program(source)?;
+        return Ok(());
+    }
+
+    // Once you have a working computer, the first step is to restore
+    // the gravity assist program (your puzzle input) to the "1202
+    // program alarm" state it had just before the last computer caught
+    // fire. To do this, before running the program, replace position 1
+    // with the value 12 and replace position 2 with the value 2. What
+    // value is left at position 0 after the program halts?
+    fn part1(&self) -> Answer {
+        return run_with(&self.numbers, 12, 2).into();
+    }
+
+    // part 2:
+
+    // determine what pair of inputs produces the output 19690720
+
+    // The inputs should still be provided to the program by replacing
+    // the values at addresses 1 and 2, just like before. In this
+    // program, the value placed in address 1 is called the noun, and
+    // the value placed in address 2 is called the verb.
+
+    // What is 100 * noun + verb?
+    fn part2(&self) -> Answer {
+        for i in 0..99 {
+            for j in 0..99 {
+                if run_with(&self.numbers, i, j) == 19690720 {
+                    return (100 * i + j).into();
+                }
+            }
+        }
+
+        panic!("no noun and verb produce 19690720");
+    }
+}
@@ -1,5 +1,9 @@
+#[cfg(test)]
 use assert::equal;
-use std::io::{self, BufRead};
+
+use super::day03p2;
+use crate::loader::{self, LoadError, Source};
+use crate::solution::{Answer, Solution};
 
 #[derive(Debug)]
 enum Direction {
@@ -36,11 +40,11 @@ fn line_to_points(l: Line) -> Vec<Point> {
 
     // all lines start at Point{0, 0}
     let mut p: Point = Point { x: 0, y: 0 };
-    result.push(p.clone());
+    result.push(p);
 
     for ls in l {
         p.add(ls);
-        result.push(p.clone());
+        result.push(p);
     }
 
     return result;
@@ -92,11 +96,11 @@ struct Point {
 impl Point {
     fn add(&mut self, ls: LineSegment) {
         match ls.d {
-            Direction::Up => self.y = self.y - ls.l,
-            Direction::Down => self.y = self.y + ls.l,
+            Direction::Up => self.y -= ls.l,
+            Direction::Down => self.y += ls.l,
 
-            Direction::Right => self.x = self.x + ls.l,
-            Direction::Left => self.x = self.x - ls.l,
+            Direction::Right => self.x += ls.l,
+            Direction::Left => self.x -= ls.l,
         }
     }
 }
@@ -125,6 +129,7 @@ fn betweenf32(a: f32, b: f32, c: f32) -> bool {
     return a <= b && b <= c;
 }
 
+#[allow(clippy::too_many_arguments)]
 fn cramer_intersection(
     x1: i32,
     y1: i32,
@@ -173,19 +178,18 @@ fn find_intersections(l1: Line, l2: Line) -> Vec<Point> {
     // do not consider an intersection at origin
     // if not considering this, then first two segments can't intersect unless they overlap
     for i1 in 0..(l1points.len() - 1) {
-        let p1a = l1points[i1 + 0];
+        let p1a = l1points[i1];
         let p1b = l1points[i1 + 1];
 
         for i2 in 1..(l2points.len() - 1) {
             // TODO O(n^2), prune some tests?
-            let p2a = l2points[i2 + 0];
+            let p2a = l2points[i2];
             let p2b = l2points[i2 + 1];
 
-            match cramer_intersection(p1a.x, p1a.y, p1b.x, p1b.y, p2a.x, p2a.y, p2b.x, p2b.y) {
-                Some(p) => {
-                    result.push(p);
-                }
-                None => {}
+            if let Some(p) =
+                cramer_intersection(p1a.x, p1a.y, p1b.x, p1b.y, p2a.x, p2a.y, p2b.x, p2b.y)
+            {
+                result.push(p);
             }
         }
     }
@@ -194,24 +198,21 @@ fn find_intersections(l1: Line, l2: Line) -> Vec<Point> {
     let i1 = 1;
     let i2 = 0;
 
-    let p1a = l1points[i1 + 0];
+    let p1a = l1points[i1];
     let p1b = l1points[i1 + 1];
 
-    let p2a = l2points[i2 + 0];
+    let p2a = l2points[i2];
     let p2b = l2points[i2 + 1];
 
-    match cramer_intersection(p1a.x, p1a.y, p1b.x, p1b.y, p2a.x, p2a.y, p2b.x, p2b.y) {
-        Some(p) => {
-            result.push(p);
-        }
-        None => {}
+    if let Some(p) = cramer_intersection(p1a.x, p1a.y, p1b.x, p1b.y, p2a.x, p2a.y, p2b.x, p2b.y) {
+        result.push(p);
     }
 
     return result;
 }
 
 fn manhattan_distance(p: Point) -> usize {
-    return p.x.abs() as usize + p.y.abs() as usize;
+    return p.x.unsigned_abs() as usize + p.y.unsigned_abs() as usize;
 }
 
 fn find_closest_intersection(l1: Line, l2: Line) -> usize {
@@ -234,6 +235,7 @@ fn find_closest_intersection(l1: Line, l2: Line) -> usize {
     return result.unwrap();
 }
 
+#[cfg(test)]
 fn test_harness(sl1: String, sl2: String, expected_length: usize) {
     let input: Vec<String> = vec![sl1, sl2];
     let lines: Vec<Line> = lines_from_input(input);
@@ -267,19 +269,41 @@ fn test3() {
     );
 }
 
-fn main() {
-    let reader = io::stdin();
-    let input: Vec<String> = reader.lock().lines().map(|s| s.unwrap()).collect();
-
-    let lines = lines_from_input(input);
-    for line in &lines {
-        println!("{:?}", line)
-    }
-
-    // find intersections
+fn wires(input: &[String]) -> (Line, Line) {
+    let lines = lines_from_input(input.to_vec());
     let mut lines_iter = lines.into_iter();
     let l1: Line = lines_iter.next().unwrap();
     let l2: Line = lines_iter.next().unwrap();
 
-    println!("{:?}", find_closest_intersection(l1, l2));
+    return (l1, l2);
+}
+
+#[derive(Default)]
+pub struct Day03 {
+    input: Vec<String>,
+}
+
+impl Solution for Day03 {
+    fn day(&self) -> u32 {
+        return 3;
+    }
+
+    fn title(&self) -> &'static str {
+        return "Crossed Wires";
+    }
+
+    fn parse(&mut self, source: &Source) -> Result<(), LoadError> {
+        self.input = loader::load_lines(source)?;
+        return Ok(());
+    }
+
+    fn part1(&self) -> Answer {
+        // find intersections
+        let (l1, l2) = wires(&self.input);
+        return find_closest_intersection(l1, l2).into();
+    }
+
+    fn part2(&self) -> Answer {
+        return day03p2::minimal_signal_delay_from_input(&self.input).into();
+    }
 }
@@ -1,5 +1,7 @@
+#[cfg(test)]
 use assert::equal;
-use std::io::{self, BufRead};
+
+use crate::debug;
 
 #[derive(Debug)]
 enum Direction {
@@ -36,11 +38,11 @@ fn line_to_points(l: Line) -> Vec<Point> {
 
     // all lines start at Point{0, 0}
     let mut p: Point = Point { x: 0, y: 0 };
-    result.push(p.clone());
+    result.push(p);
 
     for ls in l {
         p.add(ls);
-        result.push(p.clone());
+        result.push(p);
     }
 
     return result;
@@ -92,11 +94,11 @@ struct Point {
 impl Point {
     fn add(&mut self, ls: LineSegment) {
         match ls.d {
-            Direction::Up => self.y = self.y - ls.l,
-            Direction::Down => self.y = self.y + ls.l,
+            Direction::Up => self.y -= ls.l,
+            Direction::Down => self.y += ls.l,
 
-            Direction::Right => self.x = self.x + ls.l,
-            Direction::Left => self.x = self.x - ls.l,
+            Direction::Right => self.x += ls.l,
+            Direction::Left => self.x -= ls.l,
         }
     }
 }
@@ -125,6 +127,7 @@ fn betweenf32(a: f32, b: f32, c: f32) -> bool {
     return a <= b && b <= c;
 }
 
+#[allow(clippy::too_many_arguments)]
 fn cramer_intersection(
     x1: i32,
     y1: i32,
@@ -173,16 +176,16 @@ fn point_distance(p1: Point, p2: Point) -> i32 {
     if p1.y == p2.y {
         return (p1.x - p2.x).abs();
     }
-    println!("{:?} {:?}", p1, p2);
+    debug!("{:?} {:?}", p1, p2);
     panic!("bad");
 }
 
-fn sum_steps(points: &Vec<Point>, l: usize) -> i32 {
+fn sum_steps(points: &[Point], l: usize) -> i32 {
     let mut steps: i32 = 0;
 
     for i in 0..(l - 1) {
         // how many steps? not distance
-        steps = steps + point_distance(points[i], points[i + 1]);
+        steps += point_distance(points[i], points[i + 1]);
     }
 
     return steps;
@@ -221,25 +224,24 @@ fn find_steps_to_origin(l1: Line, l2: Line) -> Vec<i32> {
     // do not consider an intersection at origin
     // if not considering this, then first two segments can't intersect unless they overlap
     for i1 in 0..(l1points.len() - 1) {
-        let p1a = l1points[i1 + 0];
+        let p1a = l1points[i1];
         let p1b = l1points[i1 + 1];
 
         for i2 in 1..(l2points.len() - 1) {
-            let p2a = l2points[i2 + 0];
+            let p2a = l2points[i2];
             let p2b = l2points[i2 + 1];
 
-            match cramer_intersection(p1a.x, p1a.y, p1b.x, p1b.y, p2a.x, p2a.y, p2b.x, p2b.y) {
-                Some(p) => {
-                    // sum both wire's steps to origin
-                    // want to include p1a to p, p2a to p
-                    println!("> {:?} {:?} {:?}", p1a, p2a, p);
-                    let steps_to_origin: i32 = sum_steps(&l1points, i1 + 1)
-                        + sum_steps(&l2points, i2 + 1)
-                        + point_distance(p1a, p)
-                        + point_distance(p2a, p);
-                    result.push(steps_to_origin);
-                }
-                None => {}
+            if let Some(p) =
+                cramer_intersection(p1a.x, p1a.y, p1b.x, p1b.y, p2a.x, p2a.y, p2b.x, p2b.y)
+            {
+                // sum both wire's steps to origin
+                // want to include p1a to p, p2a to p
+                debug!("> {:?} {:?} {:?}", p1a, p2a, p);
+                let steps_to_origin: i32 = sum_steps(&l1points, i1 + 1)
+                    + sum_steps(&l2points, i2 + 1)
+                    + point_distance(p1a, p)
+                    + point_distance(p2a, p);
+                result.push(steps_to_origin);
             }
         }
     }
@@ -248,22 +250,19 @@ fn find_steps_to_origin(l1: Line, l2: Line) -> Vec<i32> {
     let i1 = 1;
     let i2 = 0;
 
-    let p1a = l1points[i1 + 0];
+    let p1a = l1points[i1];
     let p1b = l1points[i1 + 1];
 
-    let p2a = l2points[i2 + 0];
+    let p2a = l2points[i2];
     let p2b = l2points[i2 + 1];
 
-    match cramer_intersection(p1a.x, p1a.y, p1b.x, p1b.y, p2a.x, p2a.y, p2b.x, p2b.y) {
-        Some(p) => {
-            // sum both wire's steps to origin
-            let steps_to_origin: i32 = sum_steps(&l1points, i1 + 1)
-                + sum_steps(&l2points, i2 + 1)
-                + point_distance(p1a, p)
-                + point_distance(p2a, p);
-            result.push(steps_to_origin);
-        }
-        None => {}
+    if let Some(p) = cramer_intersection(p1a.x, p1a.y, p1b.x, p1b.y, p2a.x, p2a.y, p2b.x, p2b.y) {
+        // sum both wire's steps to origin
+        let steps_to_origin: i32 = sum_steps(&l1points, i1 + 1)
+            + sum_steps(&l2points, i2 + 1)
+            + point_distance(p1a, p)
+            + point_distance(p2a, p);
+        result.push(steps_to_origin);
     }
 
     return result;
@@ -273,7 +272,7 @@ fn minimal_signal_delay(l1: Line, l2: Line) -> i32 {
     let mut result: Option<i32> = None;
 
     for steps_to_origin in find_steps_to_origin(l1, l2) {
-        println!("steps {}", steps_to_origin);
+        debug!("steps {}", steps_to_origin);
         match result {
             Some(i) => {
                 if steps_to_origin < i {
@@ -289,6 +288,7 @@ fn minimal_signal_delay(l1: Line, l2: Line) -> i32 {
     return result.unwrap();
 }
 
+#[cfg(test)]
 fn test_harness(sl1: String, sl2: String, expected_delay: i32) {
     let input: Vec<String> = vec![sl1, sl2];
     let lines: Vec<Line> = lines_from_input(input);
@@ -322,19 +322,12 @@ fn test3() {
     );
 }
 
-fn main() {
-    let reader = io::stdin();
-    let input: Vec<String> = reader.lock().lines().map(|s| s.unwrap()).collect();
-
-    let lines = lines_from_input(input);
-    for line in &lines {
-        println!("{:?}", line)
-    }
-
+pub(crate) fn minimal_signal_delay_from_input(input: &[String]) -> i32 {
     // find intersections
+    let lines = lines_from_input(input.to_vec());
     let mut lines_iter = lines.into_iter();
     let l1: Line = lines_iter.next().unwrap();
     let l2: Line = lines_iter.next().unwrap();
 
-    println!("{:?}", minimal_signal_delay(l1, l2));
+    return minimal_signal_delay(l1, l2);
 }
@@ -0,0 +1,126 @@
+use crate::loader::{LoadError, ParseError, ParseErrorKind, Source};
+use crate::solution::{Answer, Solution};
+
+use super::day04p2;
+
+fn pass(input: usize) -> bool {
+    let input_s = format!("{}", input);
+
+    // Two adjacent digits are the same
+    let mut adjacent_the_same = false;
+    for i in 0..(input_s.len() - 1) {
+        let a = input_s.chars().nth(i).unwrap();
+        let b = input_s.chars().nth(i + 1).unwrap();
+
+        if a == b {
+            adjacent_the_same = true;
+        }
+
+        // Going from left to right, the digits never decrease
+        let an = a.to_digit(10).unwrap();
+        let bn = b.to_digit(10).unwrap();
+
+        if an > bn {
+            return false;
+        }
+    }
+
+    if !adjacent_the_same {
+        return false;
+    }
+
+    return true;
+}
+
+#[test]
+fn test1() {
+    assert!(pass(111111));
+}
+#[test]
+fn test2() {
+    assert!(!pass(223450));
+}
+#[test]
+fn test3() {
+    assert!(!pass(123789));
+}
+
+// the puzzle input is a range like 235741-706948
+fn parse_range(text: &str) -> Result<(usize, usize), ParseError> {
+    let bounds: Vec<&str> = text.trim().split('-').collect();
+
+    if bounds.len() != 2 {
+        return Err(ParseError {
+            index: 0,
+            kind: ParseErrorKind::InvalidInteger(text.trim().to_string()),
+        });
+    }
+
+    let mut output: [usize; 2] = [0, 0];
+
+    for (index, bound) in bounds.iter().enumerate() {
+        match bound.parse::<usize>() {
+            Ok(v) => output[index] = v,
+            Err(_) => {
+                return Err(ParseError {
+                    index,
+                    kind: ParseErrorKind::InvalidInteger(bound.to_string()),
+                });
+            }
+        }
+    }
+
+    return Ok((output[0], output[1]));
+}
+
+#[test]
+fn test_parse_range() {
+    assert_eq!(parse_range("235741-706948\n"), Ok((235741, 706948)));
+    assert_eq!(
+        parse_range("235741-x"),
+        Err(ParseError {
+            index: 1,
+            kind: ParseErrorKind::InvalidInteger("x".to_string()),
+        })
+    );
+}
+
+#[derive(Default)]
+pub struct Day04 {
+    low: usize,
+    high: usize,
+}
+
+impl Solution for Day04 {
+    fn day(&self) -> u32 {
+        return 4;
+    }
+
+    fn title(&self) -> &'static str {
+        return "Secure Container";
+    }
+
+    fn parse(&mut self, source: &Source) -> Result<(), LoadError> {
+        let text = source.read_string()?;
+        let (low, high) = parse_range(&text).map_err(|e| LoadError::Parse(source.clone(), e))?;
+
+        self.low = low;
+        self.high = high;
+
+        return Ok(());
+    }
+
+    fn part1(&self) -> Answer {
+        return (self.low..(self.high + 1))
+            .filter(|i| pass(*i))
+            .count()
+            .into();
+    }
+
+    fn part2(&self) -> Answer {
+        return (self.low..(self.high + 1))
+            .filter(|i| day04p2::pass(*i))
+            .count()
+            .into();
+    }
+}
@@ -4,7 +4,7 @@ struct Run {
     length: i32,
 }
 
-fn pass(input: usize) -> bool {
+pub(crate) fn pass(input: usize) -> bool {
     let input_s = format!("{:06}", input);
 
     if input_s.len() != 6 {
@@ -14,20 +14,20 @@ fn pass(input: usize) -> bool {
     let mut runs: Vec<Run> = Default::default();
 
     for i in 0..input_s.len() {
-        let a = input_s.chars().nth(i + 0).unwrap();
+        let a = input_s.chars().nth(i).unwrap();
         let an = a.to_digit(10).unwrap();
 
-        if runs.len() == 0 {
+        if runs.is_empty() {
             runs.push(Run {
                 value: an,
                 length: 1,
             });
         } else {
             let last_run_index = runs.len() - 1;
-            let mut last_run = &mut runs[last_run_index];
+            let last_run = &mut runs[last_run_index];
 
             if an == last_run.value {
-                last_run.length = last_run.length + 1;
+                last_run.length += 1;
                 runs[last_run_index] = *last_run;
             } else {
                 runs.push(Run {
@@ -42,7 +42,7 @@ fn pass(input: usize) -> bool {
 
     // going from left to right, the digits never decrease
     for i in 0..(runs.len() - 1) {
-        if runs[i + 0].value > runs[i + 1].value {
+        if runs[i].value > runs[i + 1].value {
             return false;
         }
     }
@@ -55,8 +55,8 @@ fn pass(input: usize) -> bool {
     // 688999 is ok because 88
     let mut at_least_one_run_two_adjacent = false;
 
-    for i in 0..runs.len() {
-        if runs[i].length == 2 {
+    for run in &runs {
+        if run.length == 2 {
             at_least_one_run_two_adjacent = true;
         }
     }
@@ -90,16 +90,3 @@ fn test5() {
     assert!(!pass(235790));
     assert!(pass(688999)); // has two adjacent, doesn't matter there's a 999!
 }
-
-fn main() {
-    let mut count = 0;
-    for i in 235741..(706948 + 1) {
-        if pass(i) {
-            println!("g{:06}", i);
-            count = count + 1;
-        } else {
-            println!("b{:06}", i);
-        }
-    }
-    println!("{}", count);
-}
@@ -0,0 +1,86 @@
+use crate::intcode;
+use crate::loader::{self, LoadError, Source};
+use crate::solution::{Answer, Solution};
+
+#[cfg(test)]
+fn intcode_program(input: Vec<i64>) -> Vec<i64> {
+    let len = input.len();
+    let (memory, _) = intcode::run_program(input, &[]).unwrap();
+    return memory.to_vec(len);
+}
+
+#[test]
+fn test_intcode_program() {
+    assert_eq!(intcode_program(vec![1, 0, 0, 0, 99]), vec![2, 0, 0, 0, 99]);
+    assert_eq!(intcode_program(vec![2, 3, 0, 3, 99]), vec![2, 3, 0, 6, 99]);
+    assert_eq!(
+        intcode_program(vec![2, 4, 4, 5, 99, 0]),
+        vec![2, 4, 4, 5, 99, 9801]
+    );
+    assert_eq!(
+        intcode_program(vec![1, 1, 1, 4, 99, 5, 6, 0, 99]),
+        vec![30, 1, 1, 4, 2, 5, 6, 0, 99]
+    );
+
+    // from day 5
+    assert_eq!(
+        intcode_program(vec![1002, 4, 3, 4, 33]),
+        vec![1002, 4, 3, 4, 99]
+    );
+}
+
+// The TEST diagnostic program asks for the ID of the system to test, runs a series of diagnostic
+// tests and outputs the result of each. Every output but the last should be 0 (test passed); the
+// last is the diagnostic code.
+fn run_diagnostic(program: &[i64], system_id: i64) -> Vec<i64> {
+    let (_, outputs) = intcode::run_program(program.to_vec(), &[system_id])
+        .expect("the diagnostic program asked for more than the system ID");
+    return outputs;
+}
+
+// needs the puzzle input, which isn't checked in
+#[test]
+#[ignore]
+fn test_day_5() {
+    let input = Day05::default().default_input();
+    let program: Vec<i64> =
+        loader::load_program(&Source::from_arg(&input)).unwrap_or_else(|e| panic!("{}", e));
+
+    let outputs = run_diagnostic(&program, 1);
+
+    for output in &outputs[..outputs.len() - 1] {
+        assert_eq!(0, *output);
+    }
+
+    assert_eq!(7692125, outputs[outputs.len() - 1]);
+}
+
+#[derive(Default)]
+pub struct Day05 {
+    program: Vec<i64>,
+}
+
+impl Solution for Day05 {
+    fn day(&self) -> u32 {
+        return 5;
+    }
+
+    fn title(&self) -> &'static str {
+        return "Sunny with a Chance of Asteroids";
+    }
+
+    fn parse(&mut self, source: &Source) -> Result<(), LoadError> {
+        self.program = loader::load_program(source)?;
+        return Ok(());
+    }
+
+    // ID 1 is the ship's air conditioner unit
+    fn part1(&self) -> Answer {
+        return (*run_diagnostic(&self.program, 1).last().unwrap()).into();
+    }
+
+    // ID 5 is the ship's thermal radiator controller
+    fn part2(&self) -> Answer {
+        return (*run_diagnostic(&self.program, 5).last().unwrap()).into();
+    }
+}
@@ -0,0 +1,290 @@
+use crate::info;
+use crate::intcode::run_intcode_computer;
+use crate::loader::{self, LoadError, Source};
+use crate::solution::{Answer, Solution};
+
+fn run_amplifier_chain(program: Vec<i64>, p1: i64, p2: i64, p3: i64, p4: i64, p5: i64) -> i64 {
+    let mut ic0 = run_intcode_computer("ic0".to_string(), program.clone());
+    let mut ic1 = run_intcode_computer("ic1".to_string(), program.clone());
+    let mut ic2 = run_intcode_computer("ic2".to_string(), program.clone());
+    let mut ic3 = run_intcode_computer("ic3".to_string(), program.clone());
+    let mut ic4 = run_intcode_computer("ic4".to_string(), program.clone());
+
+    ic0.send(p1);
+    ic1.send(p2);
+    ic2.send(p3);
+    ic3.send(p4);
+    ic4.send(p5);
+
+    ic0.send(0);
+    ic1.send(ic0.recv());
+    ic2.send(ic1.recv());
+    ic3.send(ic2.recv());
+    ic4.send(ic3.recv());
+
+    return ic4.recv();
+}
+
+#[test]
+fn test_amplifier_programs() {
+    assert_eq!(
+        run_amplifier_chain(
+            vec![3, 15, 3, 16, 1002, 16, 10, 16, 1, 16, 15, 15, 4, 15, 99, 0, 0,],
+            4,
+            3,
+            2,
+            1,
+            0
+        ),
+        43210
+    );
+
+    assert_eq!(
+        run_amplifier_chain(
+            vec![
+                3, 23, 3, 24, 1002, 24, 10, 24, 1002, 23, -1, 23, 101, 5, 23, 23, 1, 24, 23, 23, 4,
+                23, 99, 0, 0
+            ],
+            0,
+            1,
+            2,
+            3,
+            4
+        ),
+        54321
+    );
+
+    assert_eq!(
+        run_amplifier_chain(
+            vec![
+                3, 31, 3, 32, 1002, 32, 10, 32, 1001, 31, -2, 31, 1007, 31, 0, 33, 1002, 33, 7, 33,
+                1, 33, 31, 31, 1, 32, 31, 31, 4, 31, 99, 0, 0, 0
+            ],
+            1,
+            0,
+            4,
+            3,
+            2
+        ),
+        65210
+    );
+}
+
+fn run_amplifier_chain_feedback(
+    program: Vec<i64>,
+    p1: i64,
+    p2: i64,
+    p3: i64,
+    p4: i64,
+    p5: i64,
+) -> i64 {
+    let mut ic0 = run_intcode_computer("ic0".to_string(), program.clone());
+    let mut ic1 = run_intcode_computer("ic1".to_string(), program.clone());
+    let mut ic2 = run_intcode_computer("ic2".to_string(), program.clone());
+    let mut ic3 = run_intcode_computer("ic3".to_string(), program.clone());
+    let mut ic4 = run_intcode_computer("ic4".to_string(), program.clone());
+
+    ic0.send(p1);
+    ic1.send(p2);
+    ic2.send(p3);
+    ic3.send(p4);
+    ic4.send(p5);
+
+    ic0.send(0);
+
+    // connect amplifier E to amplifier A's input, run in feedback loop
+    // computers will produce multiple values before halting
+    // Each one should continue receiving and sending signals until it halts
+    let mut last_output_from_last_amplifier: Option<i64> = None;
+
+    loop {
+        if ic1.halted() {
+            return last_output_from_last_amplifier.unwrap();
+        }
+        ic1.send(ic0.recv());
+
+        if ic2.halted() {
+            return last_output_from_last_amplifier.unwrap();
+        }
+        ic2.send(ic1.recv());
+
+        if ic3.halted() {
+            return last_output_from_last_amplifier.unwrap();
+        }
+        ic3.send(ic2.recv());
+
+        if ic4.halted() {
+            return last_output_from_last_amplifier.unwrap();
+        }
+        ic4.send(ic3.recv());
+
+        last_output_from_last_amplifier = Some(ic4.recv());
+
+        if ic0.halted() {
+            return last_output_from_last_amplifier.unwrap();
+        }
+        ic0.send(last_output_from_last_amplifier.unwrap());
+    }
+}
+
+#[test]
+fn test_amplifier_with_feedback_programs() {
+    assert_eq!(
+        run_amplifier_chain_feedback(
+            vec![
+                3, 26, 1001, 26, -4, 26, 3, 27, 1002, 27, 2, 27, 1, 27, 26, 27, 4, 27, 1001, 28,
+                -1, 28, 1005, 28, 6, 99, 0, 0, 5
+            ],
+            9,
+            8,
+            7,
+            6,
+            5
+        ),
+        139629729
+    );
+
+    assert_eq!(
+        run_amplifier_chain_feedback(
+            vec![
+                3, 52, 1001, 52, -5, 52, 3, 53, 1, 52, 56, 54, 1007, 54, 5, 55, 1005, 55, 26, 1001,
+                54, -5, 54, 1105, 1, 12, 1, 53, 54, 53, 1008, 54, 0, 55, 1001, 55, 1, 55, 2, 53,
+                55, 53, 4, 53, 1001, 56, -1, 56, 1005, 56, 6, 99, 0, 0, 0, 0, 10
+            ],
+            9,
+            7,
+            8,
+            5,
+            6
+        ),
+        18216
+    );
+}
+
+#[derive(Default)]
+pub struct Day07 {
+    numbers: Vec<i64>,
+}
+
+impl Solution for Day07 {
+    fn day(&self) -> u32 {
+        return 7;
+    }
+
+    fn title(&self) -> &'static str {
+        return "Amplification Circuit";
+    }
+
+    fn parse(&mut self, source: &Source) -> Result<(), LoadError> {
+        self.numbers = loader::load_program(source)?;
+        return Ok(());
+    }
+
+    fn part1(&self) -> Answer {
+        let numbers = &self.numbers;
+        let mut max_output = 0;
+
+        for p1 in 0..5 {
+            for p2 in 0..5 {
+                for p3 in 0..5 {
+                    for p4 in 0..5 {
+                        for p5 in 0..5 {
+                            // each phase setting is only used once
+                            let mut bool_array: [bool; 5] = Default::default();
+                            bool_array[p1] = true;
+                            if bool_array[p2] {
+                                continue;
+                            }
+                            bool_array[p2] = true;
+                            if bool_array[p3] {
+                                continue;
+                            }
+                            bool_array[p3] = true;
+                            if bool_array[p4] {
+                                continue;
+                            }
+                            bool_array[p4] = true;
+                            if bool_array[p5] {
+                                continue;
+                            }
+                            bool_array[p5] = true;
+                            assert!(bool_array.iter().all(|b| *b));
+
+                            let output = run_amplifier_chain(
+                                numbers.clone(),
+                                p1 as i64,
+                                p2 as i64,
+                                p3 as i64,
+                                p4 as i64,
+                                p5 as i64,
+                            );
+                            if output > max_output {
+                                info!(
+                                    "update from {} to {} at {} {} {} {} {}",
+                                    max_output, output, p1, p2, p3, p4, p5,
+                                );
+                                max_output = output;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        return max_output.into();
+    }
+
+    fn part2(&self) -> Answer {
+        let numbers = &self.numbers;
+        let mut max_output = 0;
+
+        for p1 in 5..10 {
+            for p2 in 5..10 {
+                for p3 in 5..10 {
+                    for p4 in 5..10 {
+                        for p5 in 5..10 {
+                            // each phase setting is only used once
+                            let mut bool_array: [bool; 5] = Default::default();
+                            bool_array[p1 - 5] = true;
+                            if bool_array[p2 - 5] {
+                                continue;
+                            }
+                            bool_array[p2 - 5] = true;
+                            if bool_array[p3 - 5] {
+                                continue;
+                            }
+                            bool_array[p3 - 5] = true;
+                            if bool_array[p4 - 5] {
+                                continue;
+                            }
+                            bool_array[p4 - 5] = true;
+                            if bool_array[p5 - 5] {
+                                continue;
+                            }
+                            bool_array[p5 - 5] = true;
+                            assert!(bool_array.iter().all(|b| *b));
+
+                            let output = run_amplifier_chain_feedback(
+                                numbers.clone(),
+                                p1 as i64,
+                                p2 as i64,
+                                p3 as i64,
+                                p4 as i64,
+                                p5 as i64,
+                            );
+                            if output > max_output {
+                                info!(
+                                    "update from {} to {} at {} {} {} {} {}",
+                                    max_output, output, p1, p2, p3, p4, p5,
+                                );
+                                max_output = output;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        return max_output.into();
+    }
+}
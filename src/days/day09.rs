@@ -0,0 +1,59 @@
+use crate::intcode;
+use crate::loader::{self, LoadError, Source};
+use crate::solution::{Answer, Solution};
+
+// The BOOST program outputs a single BOOST keycode. If it finds any instructions that aren't
+// working correctly, it outputs those opcodes instead.
+fn run_boost(program: &[i64], input: i64) -> Answer {
+    let (_, outputs) = intcode::run_program(program.to_vec(), &[input])
+        .expect("the BOOST program asked for more than one input");
+
+    if outputs.len() == 1 {
+        return outputs[0].into();
+    }
+
+    let outputs: Vec<String> = outputs.iter().map(|v| v.to_string()).collect();
+    return Answer::Text(outputs.join(","));
+}
+
+#[test]
+fn test_run_boost() {
+    // outputs the input, then halts
+    assert_eq!(run_boost(&[3, 0, 4, 0, 99], 2), Answer::Number(2));
+
+    // outputs two values, which looks like malfunctioning opcodes
+    assert_eq!(
+        run_boost(&[104, 203, 104, 9, 99], 1),
+        Answer::Text("203,9".to_string())
+    );
+}
+
+#[derive(Default)]
+pub struct Day09 {
+    numbers: Vec<i64>,
+}
+
+impl Solution for Day09 {
+    fn day(&self) -> u32 {
+        return 9;
+    }
+
+    fn title(&self) -> &'static str {
+        return "Sensor Boost";
+    }
+
+    fn parse(&mut self, source: &Source) -> Result<(), LoadError> {
+        self.numbers = loader::load_program(source)?;
+        return Ok(());
+    }
+
+    // run in test mode
+    fn part1(&self) -> Answer {
+        return run_boost(&self.numbers, 1);
+    }
+
+    // run in sensor boost mode
+    fn part2(&self) -> Answer {
+        return run_boost(&self.numbers, 2);
+    }
+}
@@ -0,0 +1,102 @@
+use crate::grid::Grid;
+use crate::info;
+use crate::loader::{self, LoadError, Source};
+use crate::ocr;
+use crate::render::{self, Picture, Rgb, Style};
+use crate::robot::{IntcodeBrain, PaintingRobot, BLACK, WHITE};
+use crate::solution::{Answer, Artifact, Solution};
+
+fn paint_hull(program: &[i64], start_color: i32) -> PaintingRobot {
+    // power up the emergency hull painting robot!
+    let mut robot = PaintingRobot::new(start_color);
+    robot.run(&mut IntcodeBrain::new(program));
+    return robot;
+}
+
+struct HullStyle;
+
+impl Style<i32> for HullStyle {
+    fn glyph(&self, cell: &i32) -> char {
+        return if *cell == WHITE { '#' } else { '.' };
+    }
+
+    fn color(&self, cell: &i32) -> Rgb {
+        return if *cell == WHITE {
+            Rgb::WHITE
+        } else {
+            Rgb(40, 40, 40)
+        };
+    }
+}
+
+fn display(panels: &Grid<i32>) -> String {
+    return render::ascii(panels, &HullStyle);
+}
+
+#[derive(Default)]
+pub struct Day11 {
+    program: Vec<i64>,
+}
+
+impl Solution for Day11 {
+    fn day(&self) -> u32 {
+        return 11;
+    }
+
+    fn title(&self) -> &'static str {
+        return "Space Police";
+    }
+
+    fn parse(&mut self, source: &Source) -> Result<(), LoadError> {
+        self.program = loader::load_program(source)?;
+        return Ok(());
+    }
+
+    // Panels painted at least once, starting on a black panel
+    fn part1(&self) -> Answer {
+        return paint_hull(&self.program, BLACK).stats().painted.into();
+    }
+
+    // the registration identifier, painted starting on a white panel
+    fn part2(&self) -> Answer {
+        let panels = paint_hull(&self.program, WHITE).into_hull();
+
+        // if the letters can't be read, the picture is still better than nothing
+        match ocr::read(&panels, |v| *v == WHITE) {
+            Ok(identifier) => return Answer::Text(identifier),
+            Err(e) => {
+                info!("{}", e);
+                return Answer::Text(display(&panels));
+            }
+        }
+    }
+
+    // the hull as painted for part 1, and how the robot got on
+    fn artifacts(&self) -> Vec<Artifact> {
+        let robot = paint_hull(&self.program, BLACK);
+        let stats = robot.stats();
+
+        return vec![
+            Artifact::grid("hull", &display(robot.hull())),
+            Artifact::Text {
+                name: "robot".to_string(),
+                text: format!(
+                    "painted: {}\nrepainted: {}\npaints: {}\npath length: {}\n",
+                    stats.painted, stats.repainted, stats.paints, stats.path_length
+                ),
+            },
+        ];
+    }
+
+    // the hull from both parts, starting on a black panel and on a white one
+    fn pictures(&self) -> Vec<Picture> {
+        return vec![
+            Picture::new("hull", paint_hull(&self.program, BLACK).hull(), &HullStyle),
+            Picture::new(
+                "registration",
+                paint_hull(&self.program, WHITE).hull(),
+                &HullStyle,
+            ),
+        ];
+    }
+}
@@ -0,0 +1,131 @@
+use crate::arcade::{Arcade, ScreenStyle};
+use crate::controller::{self, Greedy};
+use crate::grid::Grid;
+use crate::loader::{self, LoadError, Source};
+use crate::record::Observer;
+use crate::render::{self, Picture};
+use crate::solution::{Answer, Artifact, Solution};
+use crate::{debug, info};
+
+fn display(panels: &Grid<i32>) -> String {
+    return render::ascii(panels, &ScreenStyle);
+}
+
+// Start the game without any quarters and return the screen when it halts, or when it first
+// wants the joystick for a cabinet that plays anyway.
+fn initial_screen(program: &[i64]) -> Grid<i32> {
+    return Arcade::new(program, false).screen;
+}
+
+fn count_initial_blocks(program: &[i64]) -> usize {
+    return Arcade::new(program, false).blocks();
+}
+
+#[test]
+fn test_count_initial_blocks() {
+    // draws a wall, two blocks and a ball, then overwrites the first block with empty space
+    let program = vec![
+        104, 0, 104, 0, 104, 1, // wall
+        104, 1, 104, 0, 104, 2, // block
+        104, 2, 104, 0, 104, 2, // block
+        104, 3, 104, 0, 104, 4, // ball
+        104, 1, 104, 0, 104, 0, // empty
+        99,
+    ];
+    assert_eq!(count_initial_blocks(&program), 1);
+}
+
+// what an observer is shown next to the screen
+fn status(arcade: &Arcade) -> [(&'static str, String); 3] {
+    return [
+        (
+            "score",
+            arcade.score.map(|s| s.to_string()).unwrap_or_default(),
+        ),
+        ("machine", arcade.state().to_string()),
+        ("instructions", arcade.machine.instructions.to_string()),
+    ];
+}
+
+// Plays with two quarters in, chasing the ball with the paddle. Every move goes to the observer, if
+// there is one. The game ends early if the observer says so, with the score so far.
+fn play(program: &[i64], mut observer: Option<&mut dyn Observer>) -> i64 {
+    // let's play a game
+    // how about thermonuclear war
+    let arcade = Arcade::new(program, true);
+
+    let report = controller::run(arcade, &mut Greedy, controller::MAX_TICKS, |arcade| {
+        debug!("{}", display(&arcade.screen));
+
+        match observer.as_deref_mut() {
+            Some(observer) => {
+                let pixels = render::pixels(&arcade.screen, &ScreenStyle);
+                return observer.frame(pixels, &status(arcade));
+            }
+            None => return true,
+        }
+    });
+
+    info!("score: {}", report.score);
+
+    return report.score;
+}
+
+#[test]
+fn test_play() {
+    let program = crate::arcade::breakout();
+
+    // this cabinet wants the joystick even with no quarters in
+    assert_eq!(count_initial_blocks(&program), 6);
+    assert_eq!(play(&program, None), 60);
+}
+
+#[derive(Default)]
+pub struct Day13 {
+    program: Vec<i64>,
+}
+
+impl Solution for Day13 {
+    fn day(&self) -> u32 {
+        return 13;
+    }
+
+    fn title(&self) -> &'static str {
+        return "Care Package";
+    }
+
+    fn parse(&mut self, source: &Source) -> Result<(), LoadError> {
+        self.program = loader::load_program(source)?;
+        return Ok(());
+    }
+
+    // How many block tiles are on the screen when the game exits?
+    fn part1(&self) -> Answer {
+        return count_initial_blocks(&self.program).into();
+    }
+
+    // What is your score after the last block is broken?
+    fn part2(&self) -> Answer {
+        return play(&self.program, None).into();
+    }
+
+    fn artifacts(&self) -> Vec<Artifact> {
+        return vec![Artifact::grid(
+            "screen",
+            &display(&initial_screen(&self.program)),
+        )];
+    }
+
+    fn pictures(&self) -> Vec<Picture> {
+        return vec![Picture::new(
+            "board",
+            &initial_screen(&self.program),
+            &ScreenStyle,
+        )];
+    }
+
+    // the whole game, as played for part 2
+    fn watch(&self, observer: &mut dyn Observer) {
+        play(&self.program, Some(observer));
+    }
+}
@@ -0,0 +1,411 @@
+use crate::droid::{DepthFirst, Explorer};
+use crate::grid::Grid;
+use crate::intcode::{run_intcode_computer, Computer, IntcodeComputer};
+use crate::loader::{self, Format, LoadError, Source};
+use crate::maze::{self, Map, MapStyle, NodeStatus};
+use crate::record::Observer;
+use crate::render::{self, Picture, Pixel, Rgb};
+use crate::solution::{Answer, Artifact, Solution};
+use crate::spread::spread_graph;
+use crate::{debug, info};
+
+// the map as it is recorded, with the droid drawn over it
+fn frame(panels: &Grid<NodeStatus>, dx: i32, dy: i32) -> Grid<Pixel> {
+    let mut pixels = render::pixels(panels, &MapStyle);
+    pixels.set(
+        dx,
+        dy,
+        Pixel {
+            glyph: maze::START,
+            color: Rgb(230, 60, 60),
+        },
+    );
+    return pixels;
+}
+
+// the map, with the droid drawn as a D
+fn display(panels: &Grid<NodeStatus>, dx: i32, dy: i32) -> String {
+    let mut output = String::new();
+
+    let bounds = match panels.bounds() {
+        Some(bounds) => bounds,
+        None => return output,
+    };
+
+    for y in bounds.min_y..=bounds.max_y {
+        for x in bounds.min_x..=bounds.max_x {
+            if x == dx && y == dy {
+                output.push(maze::START);
+            } else {
+                output.push(panels.get(x, y).glyph());
+            }
+        }
+        output.push('\n');
+    }
+
+    return output;
+}
+
+struct Exploration {
+    map: Map,
+    panels: Grid<NodeStatus>,
+
+    // where the droid ended up
+    dx: i32,
+    dy: i32,
+
+    // where the oxygen system is, if the droid came across it
+    oxygen: Option<(i32, i32)>,
+
+    // the observer ended the search before the whole area was explored
+    stopped: bool,
+}
+
+// what an observer is shown next to the map, along with whatever the droid has to say
+fn status<C: Computer>(
+    explorer: &Explorer<C>,
+    describe: fn(&C) -> Vec<(&'static str, String)>,
+) -> Vec<(&'static str, String)> {
+    let (dx, dy) = explorer.position;
+    let mut status = vec![
+        ("droid", format!("{},{}", dx, dy)),
+        ("moves", explorer.moves.to_string()),
+    ];
+    status.extend(describe(explorer.droid()));
+    return status;
+}
+
+fn describe_computer(ic: &IntcodeComputer) -> Vec<(&'static str, String)> {
+    return vec![("instructions", ic.instructions().to_string())];
+}
+
+fn explore(program: &[i64], observer: Option<&mut dyn Observer>) -> Exploration {
+    let ic = run_intcode_computer("ic".to_string(), program.to_vec());
+    return explore_with(ic, observer, describe_computer);
+}
+
+// every step of the search goes to the observer, if there is one
+fn explore_with<C: Computer>(
+    droid: C,
+    mut observer: Option<&mut dyn Observer>,
+    describe: fn(&C) -> Vec<(&'static str, String)>,
+) -> Exploration {
+    let mut explorer = Explorer::new(droid);
+
+    let finished = explorer.explore(&mut DepthFirst::default(), |explorer| {
+        let (dx, dy) = explorer.position;
+        debug!("{}", display(&explorer.chart.grid, dx, dy));
+
+        if let Some(observer) = observer.as_deref_mut() {
+            return observer.frame(
+                frame(&explorer.chart.grid, dx, dy),
+                &status(explorer, describe),
+            );
+        }
+        return true;
+    });
+
+    debug!("explored in {} moves", explorer.moves);
+
+    let (dx, dy) = explorer.position;
+    return Exploration {
+        map: explorer.chart.map,
+        panels: explorer.chart.grid,
+        dx,
+        dy,
+        oxygen: explorer.chart.oxygen,
+        stopped: !finished,
+    };
+}
+
+// a map that was saved once the droid had explored it
+fn explored(map: Map) -> Exploration {
+    let mut panels = Grid::new(NodeStatus::NotSure);
+    let mut oxygen = None;
+
+    for index in map.graph.node_indices() {
+        let node = map.get_node_by_index(index);
+        panels.set(node.x, node.y, node.status);
+        if node.status == NodeStatus::Oxygen {
+            oxygen = Some((node.x, node.y));
+        }
+    }
+
+    return Exploration {
+        map,
+        panels,
+        dx: 0,
+        dy: 0,
+        oxygen,
+        stopped: false,
+    };
+}
+
+// every cell along the shortest way from the start to the oxygen system, the start first
+fn route_to_oxygen(exploration: &Exploration) -> Vec<(i32, i32)> {
+    let mut route = vec![(0, 0)];
+    if let Some((ox, oy)) = exploration.oxygen {
+        let map = exploration.map.contract(&[]);
+        let nodes = map.return_shortest_path(0, 0, ox, oy).unwrap_or_default();
+        route.extend(map.expand((0, 0), &nodes));
+    }
+    return route;
+}
+
+// minutes for the oxygen to reach everywhere, and the map once it has
+fn fill(
+    exploration: &Exploration,
+    mut observer: Option<&mut dyn Observer>,
+) -> (i64, Grid<NodeStatus>) {
+    let Exploration { map, dx, dy, .. } = exploration;
+    let (dx, dy) = (*dx, *dy);
+    let mut panels = exploration.panels.clone();
+
+    debug!("checking fill time");
+
+    // a minute to each open cell next to one with oxygen in it
+    let filled = spread_graph(
+        &map.graph,
+        &[map.find_oxygen_node()],
+        |_| 1,
+        |node| !node.status.is_open(),
+        |minute, nodes| {
+            for index in nodes {
+                let node = map.get_node_by_index(*index);
+                panels.set(node.x, node.y, NodeStatus::Oxygen);
+            }
+
+            debug!("----------------");
+            debug!("{}", display(&panels, dx, dy));
+
+            if let Some(observer) = observer.as_deref_mut() {
+                let pixels = render::pixels(&panels, &MapStyle);
+                return observer.frame(pixels, &[("minute", minute.to_string())]);
+            }
+            return true;
+        },
+    );
+
+    info!("minutes to fill: {}", filled.time);
+
+    return (filled.time as i64, panels);
+}
+
+// The input is either the droid's program, or a map saved after exploring with it. Either way the
+// area is explored once, when the input is parsed.
+#[derive(Default)]
+pub struct Day15 {
+    // empty for a saved map
+    program: Vec<i64>,
+    exploration: Option<Exploration>,
+}
+
+impl Day15 {
+    fn exploration(&self) -> &Exploration {
+        return self
+            .exploration
+            .as_ref()
+            .expect("the input is parsed first");
+    }
+}
+
+impl Solution for Day15 {
+    fn day(&self) -> u32 {
+        return 15;
+    }
+
+    fn title(&self) -> &'static str {
+        return "Oxygen System";
+    }
+
+    fn parse(&mut self, source: &Source) -> Result<(), LoadError> {
+        let bytes = source.read_bytes()?;
+
+        let error = match loader::parse_program(&bytes, Format::Auto) {
+            Ok(program) => {
+                self.exploration = Some(explore(&program, None));
+                self.program = program;
+                return Ok(());
+            }
+            Err(e) => e,
+        };
+
+        // a map is never a program too, since its dots and D aren't numbers
+        if let Ok(text) = std::str::from_utf8(&bytes) {
+            if let Ok(map) = Map::from_text(text) {
+                self.program = Vec::new();
+                self.exploration = Some(explored(map));
+                return Ok(());
+            }
+        }
+
+        return Err(LoadError::Parse(source.clone(), error));
+    }
+
+    // the fewest number of movement commands to move the droid to the oxygen system
+    fn part1(&self) -> Answer {
+        let exploration = self.exploration();
+        let (ox, oy) = match exploration.oxygen {
+            Some(oxygen) => oxygen,
+            None => return "there's no oxygen system".to_string().into(),
+        };
+
+        // only the corridors between the start and the oxygen system matter
+        let map = exploration.map.contract(&[]);
+        match map.return_shortest_path_length(ox, oy, 0, 0) {
+            Some(length) => return length.into(),
+            None => return "the oxygen system can't be reached".to_string().into(),
+        }
+    }
+
+    // minutes for oxygen to fill the whole area
+    fn part2(&self) -> Answer {
+        let exploration = self.exploration();
+        if exploration.oxygen.is_none() {
+            return "there's no oxygen system".to_string().into();
+        }
+        return fill(exploration, None).0.into();
+    }
+
+    // the explored area, as a map that can be given back as the input and as a graph, and the
+    // shortest way from the start to the oxygen system
+    fn artifacts(&self) -> Vec<Artifact> {
+        let exploration = self.exploration();
+        let map = &exploration.map;
+
+        let points: Vec<(i64, i64)> = route_to_oxygen(exploration)
+            .iter()
+            .map(|(x, y)| (*x as i64, *y as i64))
+            .collect();
+
+        return vec![
+            Artifact::grid("map", &map.to_text()),
+            Artifact::Text {
+                name: "graph".to_string(),
+                text: map.to_dot(),
+            },
+            Artifact::Path {
+                name: "path to oxygen".to_string(),
+                points,
+            },
+        ];
+    }
+
+    // the explored area, the droid's way from the start to the oxygen system, and the area once
+    // oxygen has filled it
+    fn pictures(&self) -> Vec<Picture> {
+        let exploration = self.exploration();
+        let explored = Picture::new("map", &exploration.panels, &MapStyle);
+
+        let mut route = Picture {
+            name: "route".to_string(),
+            grid: frame(&exploration.panels, 0, 0),
+        };
+        let line = Pixel {
+            glyph: 'o',
+            color: Rgb(230, 170, 40),
+        };
+        let path = route_to_oxygen(exploration);
+        render::overlay(&mut route.grid, &path[1..], NodeStatus::Empty.glyph(), line);
+
+        let (_, filled) = fill(exploration, None);
+
+        return vec![explored, route, Picture::new("oxygen", &filled, &MapStyle)];
+    }
+
+    // the droid exploring, then the oxygen spreading through what it found; the droid is sent out
+    // again so each of its moves can be shown, unless the input was a saved map
+    fn watch(&self, observer: &mut dyn Observer) {
+        if self.program.is_empty() {
+            fill(self.exploration(), Some(observer));
+            return;
+        }
+
+        let exploration = explore(&self.program, Some(observer));
+        if !exploration.stopped {
+            fill(&exploration, Some(observer));
+        }
+    }
+}
+
+#[test]
+fn test_saved_map() {
+    // the example from part 2, with somewhere for the droid to start
+    let map = " ##\n#D.##\n#.#..#\n#.O.#\n ###\n";
+
+    let path = std::env::temp_dir().join(format!("day15-{}.map", std::process::id()));
+    std::fs::write(&path, map).unwrap();
+    let mut day = Day15::default();
+    let parsed = day.parse(&Source::from_arg(path.to_str().unwrap()));
+    std::fs::remove_file(&path).unwrap();
+    parsed.unwrap();
+
+    assert_eq!(day.part1(), Answer::Number(3));
+    assert_eq!(day.part2(), Answer::Number(4));
+    assert_eq!(day.artifacts()[0].to_string(), map.trim_end());
+    assert_eq!(day.artifacts()[2].to_string(), "0,0 0,1 0,2 1,2");
+
+    // the way there, drawn over the map
+    let route = day.pictures()[1].render(&render::Ascii);
+    let route = String::from_utf8(route).unwrap();
+    assert_eq!(route, " ##   \n#D.## \n#o#..#\n#oO.# \n ###  \n");
+
+    // anything else is still a program, or not; this droid is walled in on every side
+    let mut day = Day15::default();
+    std::fs::write(&path, "3,100,104,0,1105,1,0").unwrap();
+    assert!(day.parse(&Source::from_arg(path.to_str().unwrap())).is_ok());
+    assert_eq!(
+        day.part1(),
+        Answer::Text("there's no oxygen system".to_string())
+    );
+    std::fs::write(&path, "#D.\n#x").unwrap();
+    assert!(day
+        .parse(&Source::from_arg(path.to_str().unwrap()))
+        .is_err());
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_mock_droid() {
+    use crate::droid::MockDroid;
+
+    let no_status = |_: &MockDroid| Vec::new();
+
+    // the example from part 2, explored by a droid
+    let droid = MockDroid::from_text(" ##\n#D.##\n#.#..#\n#.O.#\n ###\n").unwrap();
+    let exploration = explore_with(droid, None, no_status);
+    assert_eq!(exploration.oxygen, Some((1, 2)));
+    assert_eq!(fill(&exploration, None).0, 4);
+
+    // an open room with a pillar, so there are loops, and a corridor off it
+    let room = "\
+#######
+#.....#
+#.#.#.#
+#..D..#####
+#.#.#.....#
+#.....###O#
+#######  #";
+    let exploration = explore_with(MockDroid::from_text(room).unwrap(), None, no_status);
+    assert!(!exploration.stopped);
+    assert_eq!(exploration.oxygen, Some((6, 2)));
+    assert_eq!(
+        exploration.map.return_shortest_path_length(0, 0, 6, 2),
+        Some(8)
+    );
+
+    // all of it is found, other than corners the droid never looks at
+    let explored = [
+        " #####",
+        "#.....#",
+        "#.#.#.#",
+        "#..D..####",
+        "#.#.#.....#",
+        "#.....###O#",
+        " #####   #",
+    ];
+    assert_eq!(exploration.map.to_text(), explored.join("\n") + "\n");
+
+    // the far corner of the room is as far from the oxygen as anywhere
+    assert_eq!(fill(&exploration, None).0, 12);
+}
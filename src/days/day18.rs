@@ -1,41 +1,20 @@
-use std::fs::File;
-use std::io::{self, BufRead, Write};
-
 use std::cmp::Ordering;
 use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
 use std::hash::{Hash, Hasher};
+#[cfg(test)]
 use std::iter::FromIterator;
 
-use petgraph::algo::dijkstra;
-use petgraph::dot::{Config, Dot};
+use petgraph::algo::{astar, dijkstra};
+use petgraph::dot::Dot;
 use petgraph::graph::{DefaultIx, NodeIndex};
 use petgraph::stable_graph::StableGraph;
 
-#[derive(Debug, Clone)]
-struct Node {
-    x: usize,
-    y: usize,
-    c: String,
-    index: NodeIndex<DefaultIx>,
-}
-
-impl Node {
-    fn is_alphabetic(&self) -> bool {
-        return self.c.chars().next().unwrap().is_alphabetic();
-    }
-
-    fn is_key(&self) -> bool {
-        return self.is_alphabetic() && (self.c.to_lowercase() == self.c);
-    }
-
-    fn is_door(&self) -> bool {
-        return self.is_alphabetic() && (self.c.to_uppercase() == self.c);
-    }
-
-    fn key_opens(&self, key: &String) -> bool {
-        return self.c.to_lowercase() == *key;
-    }
-}
+use super::day18p2;
+use super::vault::{self, Node};
+use crate::loader::{self, LoadError, Source};
+use crate::render::{self, Picture, Pixel, Rgb, Style};
+use crate::solution::{Answer, Artifact, Solution};
+use crate::{debug, info};
 
 type DoorNodes = Vec<NodeIndex<DefaultIx>>;
 type KeyNodes = Vec<NodeIndex<DefaultIx>>;
@@ -58,9 +37,9 @@ fn visible_doors_and_keys(
         // terminate if a non-legal character is found
         let node = graph.node_weight(node_index).unwrap();
 
-        if node.c == " ".to_string() || node.c == "@".to_string() || node.c == ".".to_string() {
+        if node.c == " " || node.c == "@" || node.c == "." {
         } else {
-            println!("{:?}", node.c);
+            debug!("{:?}", node.c);
             assert_eq!(true, false);
         }
     }
@@ -124,6 +103,7 @@ impl Maze {
         return None;
     }
 
+    #[cfg(test)]
     fn letters(&self, v: Vec<NodeIndex<DefaultIx>>) -> HashSet<String> {
         let mut output: HashSet<String> = HashSet::new();
         for i in v {
@@ -188,7 +168,7 @@ fn test_letters() {
     assert_eq!(DoorNodes::new(), door_nodes);
 
     let expected_key_nodes: HashSet<String> = HashSet::from_iter(
-        vec!["a", "b", "c", "d", "e", "f", "g", "h"]
+        ["a", "b", "c", "d", "e", "f", "g", "h"]
             .iter()
             .map(|s| s.to_string()),
     );
@@ -197,174 +177,28 @@ fn test_letters() {
 }
 
 fn get_lines_as_maze(raw_map: Vec<Vec<char>>) -> Maze {
-    println!("{:?}", raw_map);
-
-    let rows = raw_map.len();
-    let cols = raw_map[0].len();
-    let mut nodes_map: HashMap<usize, HashMap<usize, NodeIndex<DefaultIx>>> = HashMap::new();
-
-    println!("{} {}", rows, cols);
-
-    let mut maze: Maze = Maze {
-        graph: StableGraph::new(),
+    return Maze {
+        graph: vault::graph(&vault::contract(&raw_map)),
     };
+}
 
-    // add nodes
-    for y in 1..(rows - 1) {
-        for x in 1..(cols - 1) {
-            let point = raw_map[y][x];
-
-            if point != '#' {
-                maze.graph.add_node(Node {
-                    x: x,
-                    y: y,
-                    c: point.to_string(),
-                    index: NodeIndex::new(0),
-                });
-            }
-
-            print!("{}", raw_map[y][x]);
-        }
-        print!("\n");
-    }
-
-    for ix in maze.graph.clone().node_indices() {
-        let node = maze.graph.node_weight_mut(ix).unwrap();
-        node.index = ix;
-
-        if node.c == "." {
-            node.c = " ".to_string();
-        }
-
-        nodes_map.entry(node.y).or_insert(HashMap::new());
-        nodes_map
-            .get_mut(&node.y)
-            .unwrap()
-            .entry(node.x)
-            .or_insert(ix);
-
-        println!("y {} x {} {:?}", node.y, node.x, node);
-    }
-
-    // add edges
-    for y in 1..(rows - 1) {
-        for x in 1..(cols - 1) {
-            let point = raw_map[y][x];
-            let north = raw_map[y - 1][x];
-            let south = raw_map[y + 1][x];
-            let west = raw_map[y][x - 1];
-            let east = raw_map[y][x + 1];
-
-            if point == '#' {
-                continue;
-            }
-
-            println!(" {} ", north);
-            println!("{}{}{}", west, point, east);
-            println!(" {} ", south);
-
-            if north != '#' {
-                println!("y {} x {} N", y, x);
-                maze.graph.add_edge(
-                    *nodes_map.get(&y).unwrap().get(&x).unwrap(),
-                    *nodes_map.get(&(y - 1)).unwrap().get(&x).unwrap(),
-                    1,
-                );
-            }
-            if south != '#' {
-                maze.graph.add_edge(
-                    *nodes_map.get(&y).unwrap().get(&x).unwrap(),
-                    *nodes_map.get(&(y + 1)).unwrap().get(&x).unwrap(),
-                    1,
-                );
-            }
-            if west != '#' {
-                maze.graph.add_edge(
-                    *nodes_map.get(&y).unwrap().get(&x).unwrap(),
-                    *nodes_map.get(&y).unwrap().get(&(x - 1)).unwrap(),
-                    1,
-                );
-            }
-            if east != '#' {
-                maze.graph.add_edge(
-                    *nodes_map.get(&y).unwrap().get(&x).unwrap(),
-                    *nodes_map.get(&y).unwrap().get(&(x + 1)).unwrap(),
-                    1,
-                );
-            }
+struct VaultStyle;
 
-            println!("");
-        }
+impl Style<char> for VaultStyle {
+    fn glyph(&self, cell: &char) -> char {
+        return *cell;
     }
 
-    // simplify maze
-    let mut still_simplifying: bool = true;
-    while still_simplifying {
-        still_simplifying = false;
-
-        // A: "c" <-> B: " " <-> C: "D"
-        //
-        // remove nodes (ex. B) that:
-        // 1. have only two incident edges
-        // 2. are space nodes
-
-        // replace those edges by gluing together both A and C
-        // sum up edge weight
-
-        for ix in maze.graph.clone().node_indices() {
-            let node = maze.graph.node_weight_mut(ix).unwrap();
-
-            // only prune spaces!
-            if node.c != " " {
-                continue;
-            }
-
-            let mut num_edges: usize = 0;
-            let mut edge_weight: usize = 0;
-            let mut other_nodes: Vec<NodeIndex<DefaultIx>> = Vec::new();
-
-            // because there's no diagonal and this is a maze, there
-            // won't be multiple edges to a neighbor so this works
-            for jx in maze.graph.neighbors(ix) {
-                num_edges += 1;
-                other_nodes.push(jx);
-            }
-
-            for eix in maze.graph.edges(ix) {
-                // edge weight will be the same in either direction
-                edge_weight += *eix.weight();
-            }
-
-            if num_edges == 2 && (other_nodes[0] != other_nodes[1]) {
-                maze.graph.remove_node(ix);
-
-                maze.graph
-                    .add_edge(other_nodes[0], other_nodes[1], edge_weight);
-                maze.graph
-                    .add_edge(other_nodes[1], other_nodes[0], edge_weight);
-
-                println!(
-                    "removed {:?}, connected from {:?} to {:?} weight {}",
-                    ix, other_nodes[0], other_nodes[1], edge_weight
-                );
-
-                still_simplifying = true;
-                break;
-            }
-
-            if num_edges == 1 {
-                // extra: prune leaf nodes that are not special
-                maze.graph.remove_node(ix);
-
-                println!("removed {:?}, was non-special leaf", ix);
-
-                still_simplifying = true;
-                break;
-            }
+    fn color(&self, cell: &char) -> Rgb {
+        match cell {
+            '#' => return Rgb(110, 110, 110),
+            '.' => return Rgb(220, 220, 220),
+            '@' => return Rgb(230, 60, 60),
+            c if c.is_ascii_lowercase() => return Rgb(240, 200, 40),
+            c if c.is_ascii_uppercase() => return Rgb(150, 90, 40),
+            _ => return Rgb::BLACK,
         }
     }
-
-    return maze;
 }
 
 struct Search {
@@ -374,6 +208,9 @@ struct Search {
     cost: i32,
     depth: usize,
     keys: HashSet<String>,
+
+    // the keys collected so far, in order
+    route: Vec<NodeIndex<DefaultIx>>,
 }
 
 impl Ord for Search {
@@ -411,7 +248,6 @@ impl Hash for SearchState {
         for key in self.keys.clone() {
             key.hash(state);
         }
-        state.finish();
     }
 }
 impl PartialEq for SearchState {
@@ -422,7 +258,7 @@ impl PartialEq for SearchState {
 impl Eq for SearchState {}
 
 fn collect_all(maze: &Maze) -> usize {
-    return collect_all_given(maze).unwrap();
+    return collect_all_given(maze).unwrap().0;
 }
 
 fn steps_to_farthest_key(node_index: NodeIndex<DefaultIx>, maze: &Maze) -> Option<usize> {
@@ -449,7 +285,8 @@ fn steps_to_farthest_key(node_index: NodeIndex<DefaultIx>, maze: &Maze) -> Optio
     return steps;
 }
 
-fn collect_all_given(amaze: &Maze) -> Option<usize> {
+// the fewest steps to collect every key, and the keys in the order they're collected in
+fn collect_all_given(amaze: &Maze) -> Option<(usize, Vec<NodeIndex<DefaultIx>>)> {
     // make sure cost is negative - this makes this a min heap
     let mut search_space: BinaryHeap<Search> = BinaryHeap::new();
 
@@ -464,11 +301,13 @@ fn collect_all_given(amaze: &Maze) -> Option<usize> {
             cost: 0,
             depth: 0,
             keys: ["".to_string()].iter().cloned().collect(),
+            route: Vec::new(),
         });
     }
 
     // best total path
     let mut best_path: Option<usize> = None;
+    let mut best_route: Vec<NodeIndex<DefaultIx>> = Vec::new();
 
     // closed set: if a node has already been examined, then don't re-examine, unless its
     // cost can be lowered
@@ -519,27 +358,26 @@ fn collect_all_given(amaze: &Maze) -> Option<usize> {
             match best_path {
                 Some(i) => {
                     if current_search.path_length < i {
-                        println!("update {}", current_search.path_length);
+                        info!("update {}", current_search.path_length);
                         best_path = Some(current_search.path_length);
+                        best_route = current_search.route.clone();
                     }
                 }
                 None => {
-                    println!("new {}", current_search.path_length);
+                    info!("new {}", current_search.path_length);
                     best_path = Some(current_search.path_length);
+                    best_route = current_search.route.clone();
                 }
             }
 
             continue;
         }
 
-        match best_path {
-            Some(i) => {
-                // if the best path is known, then ignore items that are not better
-                if current_search.path_length >= i {
-                    continue;
-                }
+        // if the best path is known, then ignore items that are not better
+        if let Some(i) = best_path {
+            if current_search.path_length >= i {
+                continue;
             }
-            None => {}
         }
 
         /*
@@ -581,9 +419,7 @@ fn collect_all_given(amaze: &Maze) -> Option<usize> {
             match farthest_key {
                 // there is some key still to get
                 Some(_) => {
-                    let cost: i32;
-
-                    match best_path {
+                    let cost: i32 = match best_path {
                         Some(i) => {
                             // the best possible path must be better to count (hence >=)
                             // note there might be a key along the way
@@ -595,17 +431,20 @@ fn collect_all_given(amaze: &Maze) -> Option<usize> {
                             //cost = farthest_key.unwrap() as i32;
 
                             // dfs
-                            cost = keys_left;
+                            keys_left
                         }
                         None => {
                             // if no best path exists, dfs to find one
-                            cost = keys_left;
+                            keys_left
                         }
-                    }
+                    };
 
                     let mut new_keys: HashSet<String> = current_search.keys.clone();
                     new_keys.insert(key_node.c.clone());
 
+                    let mut new_route = current_search.route.clone();
+                    new_route.push(key);
+
                     search_space.push(Search {
                         maze: new_maze,
                         index: key,
@@ -613,6 +452,7 @@ fn collect_all_given(amaze: &Maze) -> Option<usize> {
                         cost: -(cost),
                         depth: current_search.depth + 1,
                         keys: new_keys,
+                        route: new_route,
                     });
                 }
                 // there is no other key, so the cost after this is zero
@@ -620,6 +460,9 @@ fn collect_all_given(amaze: &Maze) -> Option<usize> {
                     let mut new_keys: HashSet<String> = current_search.keys.clone();
                     new_keys.insert(key_node.c.clone());
 
+                    let mut new_route = current_search.route.clone();
+                    new_route.push(key);
+
                     search_space.push(Search {
                         maze: new_maze,
                         index: key,
@@ -627,13 +470,14 @@ fn collect_all_given(amaze: &Maze) -> Option<usize> {
                         cost: 0,
                         depth: current_search.depth + 1,
                         keys: new_keys,
+                        route: new_route,
                     });
                 }
             }
         }
     }
 
-    return best_path;
+    return best_path.map(|steps| (steps, best_route));
 }
 
 #[test]
@@ -695,22 +539,154 @@ fn test_example3() {
     assert_eq!(collect_all(&maze), 81);
 }
 
-fn main() {
-    let reader = io::stdin();
-    let raw_map: Vec<Vec<char>> = reader
-        .lock()
-        .lines()
-        .map(|s| s.unwrap().chars().collect())
-        .collect();
+// Every cell along the shortest way to collect all of the keys, from the entrance. The search only
+// says which key it went to next; the way there is the shortest through the corridors, which is
+// how it counted the steps, and each corridor is then put back a cell at a time.
+fn key_route(raw_map: &[Vec<char>]) -> Vec<(i32, i32)> {
+    let corridors = vault::contract(raw_map);
+    let maze = Maze {
+        graph: vault::graph(&corridors),
+    };
+    let keys = match collect_all_given(&maze) {
+        Some((_, keys)) => keys,
+        None => return Vec::new(),
+    };
+
+    // from the maze's nodes back to the corridors'
+    let corridor_node = |ix: NodeIndex<DefaultIx>| {
+        let node = &maze.graph[ix];
+        return corridors.node_index(node.x as i32, node.y as i32).unwrap();
+    };
+
+    let mut stops = vec![corridor_node(maze.find_start_index())];
+    stops.extend(keys.into_iter().map(corridor_node));
+
+    let mut route = vec![stops[0]];
+    for leg in stops.windows(2) {
+        let (_, nodes) = astar(
+            &corridors.graph,
+            leg[0],
+            |n| n == leg[1],
+            |e| *e.weight(),
+            |_| 0,
+        )
+        .unwrap();
+        route.extend(&nodes[1..]);
+    }
+
+    return corridors.expand(&route).unwrap();
+}
+
+#[test]
+fn test_key_route() {
+    let raw_map: Vec<Vec<char>> = vec![
+        "#########".chars().collect(),
+        "#b.A.@.a#".chars().collect(),
+        "#########".chars().collect(),
+    ];
+
+    // over to a, then back past the entrance and through the door to b
+    let route = key_route(&raw_map);
+    assert_eq!(route.len(), 8 + 1);
+    assert_eq!(route.first(), Some(&(5, 1)));
+    assert_eq!(route[2], (7, 1));
+    assert_eq!(route.last(), Some(&(1, 1)));
+
+    let mut picture = Picture::new("route", &vault::grid(&raw_map), &VaultStyle);
+    let line = Pixel {
+        glyph: 'o',
+        color: Rgb::WHITE,
+    };
+    render::overlay(&mut picture.grid, &route, '.', line);
+    assert_eq!(
+        String::from_utf8(picture.render(&render::Ascii)).unwrap(),
+        "#########\n#boAo@oa#\n#########\n"
+    );
+
+    // a step at a time, and as many as the search counted
+    let raw_map: Vec<Vec<char>> = vec![
+        "########################".chars().collect(),
+        "#...............b.C.D.f#".chars().collect(),
+        "#.######################".chars().collect(),
+        "#.....@.a.B.c.d.A.e.F.g#".chars().collect(),
+        "########################".chars().collect(),
+    ];
+    let route = key_route(&raw_map);
+    assert_eq!(route.len(), 132 + 1);
+    for step in route.windows(2) {
+        let ((x1, y1), (x2, y2)) = (step[0], step[1]);
+        assert_eq!((x1 - x2).abs() + (y1 - y2).abs(), 1);
+        assert_ne!(raw_map[y2 as usize][x2 as usize], '#');
+    }
+    assert_eq!(route.last(), Some(&(22, 3)));
+}
 
+fn solve(raw_map: Vec<Vec<char>>) -> usize {
     let maze = get_lines_as_maze(raw_map);
 
     let text = format!("{:?}", Dot::with_config(&maze.graph, &[]));
-    println!("{}", text);
+    debug!("{}", text);
 
-    let mut file = File::create("graph.dot").expect("failed to create graph.dot");
-    file.write(&text.into_bytes())
-        .expect("could not write into graph.dot");
+    let steps = collect_all(&maze);
+    info!("{} steps", steps);
+
+    return steps;
+}
+
+#[derive(Default)]
+pub struct Day18 {
+    raw_map: Vec<Vec<char>>,
+}
+
+impl Solution for Day18 {
+    fn day(&self) -> u32 {
+        return 18;
+    }
+
+    fn title(&self) -> &'static str {
+        return "Many-Worlds Interpretation";
+    }
 
-    println!("{} steps", collect_all(&maze));
+    fn parse(&mut self, source: &Source) -> Result<(), LoadError> {
+        self.raw_map = loader::load_lines(source)?
+            .iter()
+            .map(|s| s.chars().collect())
+            .collect();
+        return Ok(());
+    }
+
+    // How many steps is the shortest path that collects all of the keys?
+    fn part1(&self) -> Answer {
+        return solve(self.raw_map.clone()).into();
+    }
+
+    // the same, after splitting the vault into four sections with one robot each
+    fn part2(&self) -> Answer {
+        return day18p2::solve(self.raw_map.clone()).into();
+    }
+
+    // the maze as a graph, in DOT format
+    fn artifacts(&self) -> Vec<Artifact> {
+        let maze = get_lines_as_maze(self.raw_map.clone());
+
+        return vec![Artifact::Text {
+            name: "maze".to_string(),
+            text: format!("{:?}", Dot::with_config(&maze.graph, &[])),
+        }];
+    }
+
+    // the vault, and the shortest way through it that collects every key
+    fn pictures(&self) -> Vec<Picture> {
+        let vault = Picture::new("vault", &vault::grid(&self.raw_map), &VaultStyle);
+
+        let mut route = vault.clone();
+        route.name = "route".to_string();
+        let line = Pixel {
+            glyph: 'o',
+            color: Rgb(80, 200, 120),
+        };
+        render::overlay(&mut route.grid, &key_route(&self.raw_map), '.', line);
+
+        return vec![vault, route];
+    }
 }
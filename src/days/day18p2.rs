@@ -1,9 +1,7 @@
-use std::fs::File;
-use std::io::{self, BufRead, Write};
-
 use std::cmp::Ordering;
 use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
 use std::hash::{Hash, Hasher};
+#[cfg(test)]
 use std::iter::FromIterator;
 
 use petgraph::algo::dijkstra;
@@ -11,31 +9,8 @@ use petgraph::dot::Dot;
 use petgraph::graph::{DefaultIx, NodeIndex};
 use petgraph::stable_graph::StableGraph;
 
-#[derive(Debug, Clone)]
-struct Node {
-    x: usize,
-    y: usize,
-    c: String,
-    index: NodeIndex<DefaultIx>,
-}
-
-impl Node {
-    fn is_alphabetic(&self) -> bool {
-        return self.c.chars().next().unwrap().is_alphabetic();
-    }
-
-    fn is_key(&self) -> bool {
-        return self.is_alphabetic() && (self.c.to_lowercase() == self.c);
-    }
-
-    fn is_door(&self) -> bool {
-        return self.is_alphabetic() && (self.c.to_uppercase() == self.c);
-    }
-
-    fn key_opens(&self, key: &String) -> bool {
-        return self.c.to_lowercase() == *key;
-    }
-}
+use super::vault::{self, Node};
+use crate::{debug, info};
 
 type DoorNodes = Vec<NodeIndex<DefaultIx>>;
 type KeyNodes = Vec<NodeIndex<DefaultIx>>;
@@ -58,9 +33,9 @@ fn visible_doors_and_keys(
         // terminate if a non-legal character is found
         let node = graph.node_weight(node_index).unwrap();
 
-        if node.c == " ".to_string() || node.c == "@".to_string() || node.c == ".".to_string() {
+        if node.c == " " || node.c == "@" || node.c == "." {
         } else {
-            println!("{:?}", node.c);
+            debug!("{:?}", node.c);
             assert_eq!(true, false);
         }
     }
@@ -114,6 +89,7 @@ struct Maze {
 }
 
 impl Maze {
+    #[cfg(test)]
     fn node_index(&self, c: String) -> Option<NodeIndex<DefaultIx>> {
         for node_index in self.graph.node_indices() {
             let node = self.graph.node_weight(node_index).unwrap();
@@ -124,6 +100,7 @@ impl Maze {
         return None;
     }
 
+    #[cfg(test)]
     fn letters(&self, v: Vec<NodeIndex<DefaultIx>>) -> HashSet<String> {
         let mut output: HashSet<String> = HashSet::new();
         for i in v {
@@ -143,12 +120,12 @@ impl Maze {
             let node = self.graph.node_weight(node_index).unwrap();
             if node.c == "@" {
                 indexes[i] = node.index;
-                i = i + 1;
+                i += 1;
             }
         }
 
-        for k in 0..4 {
-            println!("found index at {:?}", indexes[k])
+        for index in &indexes {
+            debug!("found index at {:?}", index)
         }
 
         return indexes;
@@ -159,7 +136,7 @@ impl Maze {
             dijkstra(&self.graph, i, Some(j), |e| *e.weight());
 
         match result.get(&j) {
-            Some(i) => return Some(i.clone()),
+            Some(i) => return Some(*i),
             None => return None,
         }
     }
@@ -207,7 +184,7 @@ fn test_letters() {
     assert_eq!(DoorNodes::new(), door_nodes);
 
     let expected_key_nodes: HashSet<String> = HashSet::from_iter(
-        vec!["a", "b", "c", "d", "e", "f", "g", "h"]
+        ["a", "b", "c", "d", "e", "f", "g", "h"]
             .iter()
             .map(|s| s.to_string()),
     );
@@ -216,174 +193,9 @@ fn test_letters() {
 }
 
 fn get_lines_as_maze(raw_map: Vec<Vec<char>>) -> Maze {
-    println!("{:?}", raw_map);
-
-    let rows = raw_map.len();
-    let cols = raw_map[0].len();
-    let mut nodes_map: HashMap<usize, HashMap<usize, NodeIndex<DefaultIx>>> = HashMap::new();
-
-    println!("{} {}", rows, cols);
-
-    let mut maze: Maze = Maze {
-        graph: StableGraph::new(),
+    return Maze {
+        graph: vault::graph(&vault::contract(&raw_map)),
     };
-
-    // add nodes
-    for y in 1..(rows - 1) {
-        for x in 1..(cols - 1) {
-            let point = raw_map[y][x];
-
-            if point != '#' {
-                maze.graph.add_node(Node {
-                    x: x,
-                    y: y,
-                    c: point.to_string(),
-                    index: NodeIndex::new(0),
-                });
-            }
-
-            print!("{}", raw_map[y][x]);
-        }
-        print!("\n");
-    }
-
-    for ix in maze.graph.clone().node_indices() {
-        let node = maze.graph.node_weight_mut(ix).unwrap();
-        node.index = ix;
-
-        if node.c == "." {
-            node.c = " ".to_string();
-        }
-
-        nodes_map.entry(node.y).or_insert(HashMap::new());
-        nodes_map
-            .get_mut(&node.y)
-            .unwrap()
-            .entry(node.x)
-            .or_insert(ix);
-
-        println!("y {} x {} {:?}", node.y, node.x, node);
-    }
-
-    // add edges
-    for y in 1..(rows - 1) {
-        for x in 1..(cols - 1) {
-            let point = raw_map[y][x];
-            let north = raw_map[y - 1][x];
-            let south = raw_map[y + 1][x];
-            let west = raw_map[y][x - 1];
-            let east = raw_map[y][x + 1];
-
-            if point == '#' {
-                continue;
-            }
-
-            println!(" {} ", north);
-            println!("{}{}{}", west, point, east);
-            println!(" {} ", south);
-
-            if north != '#' {
-                println!("y {} x {} N", y, x);
-                maze.graph.add_edge(
-                    *nodes_map.get(&y).unwrap().get(&x).unwrap(),
-                    *nodes_map.get(&(y - 1)).unwrap().get(&x).unwrap(),
-                    1,
-                );
-            }
-            if south != '#' {
-                maze.graph.add_edge(
-                    *nodes_map.get(&y).unwrap().get(&x).unwrap(),
-                    *nodes_map.get(&(y + 1)).unwrap().get(&x).unwrap(),
-                    1,
-                );
-            }
-            if west != '#' {
-                maze.graph.add_edge(
-                    *nodes_map.get(&y).unwrap().get(&x).unwrap(),
-                    *nodes_map.get(&y).unwrap().get(&(x - 1)).unwrap(),
-                    1,
-                );
-            }
-            if east != '#' {
-                maze.graph.add_edge(
-                    *nodes_map.get(&y).unwrap().get(&x).unwrap(),
-                    *nodes_map.get(&y).unwrap().get(&(x + 1)).unwrap(),
-                    1,
-                );
-            }
-
-            println!("");
-        }
-    }
-
-    // simplify maze
-    let mut still_simplifying: bool = true;
-    while still_simplifying {
-        still_simplifying = false;
-
-        // A: "c" <-> B: " " <-> C: "D"
-        //
-        // remove nodes (ex. B) that:
-        // 1. have only two incident edges
-        // 2. are space nodes
-
-        // replace those edges by gluing together both A and C
-        // sum up edge weight
-
-        for ix in maze.graph.clone().node_indices() {
-            let node = maze.graph.node_weight_mut(ix).unwrap();
-
-            // only prune spaces!
-            if node.c != " " {
-                continue;
-            }
-
-            let mut num_edges: usize = 0;
-            let mut edge_weight: usize = 0;
-            let mut other_nodes: Vec<NodeIndex<DefaultIx>> = Vec::new();
-
-            // because there's no diagonal and this is a maze, there
-            // won't be multiple edges to a neighbor so this works
-            for jx in maze.graph.neighbors(ix) {
-                num_edges += 1;
-                other_nodes.push(jx);
-            }
-
-            for eix in maze.graph.edges(ix) {
-                // edge weight will be the same in either direction
-                edge_weight += *eix.weight();
-            }
-
-            if num_edges == 2 && (other_nodes[0] != other_nodes[1]) {
-                maze.graph.remove_node(ix);
-
-                maze.graph
-                    .add_edge(other_nodes[0], other_nodes[1], edge_weight);
-                maze.graph
-                    .add_edge(other_nodes[1], other_nodes[0], edge_weight);
-
-                println!(
-                    "removed {:?}, connected from {:?} to {:?} weight {}",
-                    ix, other_nodes[0], other_nodes[1], edge_weight
-                );
-
-                still_simplifying = true;
-                break;
-            }
-
-            if num_edges == 1 {
-                // extra: prune leaf nodes that are not special
-                maze.graph.remove_node(ix);
-
-                println!("removed {:?}, was non-special leaf", ix);
-
-                still_simplifying = true;
-                break;
-            }
-        }
-    }
-
-    return maze;
 }
 
 struct Search {
@@ -434,8 +246,6 @@ impl Hash for SearchState {
         for key in self.keys.clone() {
             key.hash(state);
         }
-
-        state.finish();
     }
 }
 impl PartialEq for SearchState {
@@ -507,7 +317,7 @@ fn collect_all_given(amaze: &Maze) -> Option<usize> {
         // pop off best search so far
         let current_search = search_space.pop().unwrap();
 
-        println!("searching {}, not done yet", current_search.path_length);
+        debug!("searching {}, not done yet", current_search.path_length);
 
         let reached: SearchState = SearchState {
             indexes: current_search.indexes,
@@ -547,12 +357,12 @@ fn collect_all_given(amaze: &Maze) -> Option<usize> {
             match best_path {
                 Some(i) => {
                     if current_search.path_length < i {
-                        println!("update {}", current_search.path_length);
+                        info!("update {}", current_search.path_length);
                         best_path = Some(current_search.path_length);
                     }
                 }
                 None => {
-                    println!("new {}", current_search.path_length);
+                    info!("new {}", current_search.path_length);
                     best_path = Some(current_search.path_length);
                 }
             }
@@ -560,28 +370,23 @@ fn collect_all_given(amaze: &Maze) -> Option<usize> {
             continue;
         }
 
-        match best_path {
-            Some(i) => {
-                // if the best path is known, then ignore items that are not better
-                if current_search.path_length >= i {
-                    continue;
-                }
+        // if the best path is known, then ignore items that are not better
+        if let Some(i) = best_path {
+            if current_search.path_length >= i {
+                continue;
             }
-            None => {}
         }
 
         // what can I collect?
         let mut key_nodes: [KeyNodes; 4] = Default::default();
-        for i in 0..4 {
+        for (i, nodes) in key_nodes.iter_mut().enumerate() {
             let (_, _key_nodes) =
                 visible_doors_and_keys(current_search.indexes[i], &current_search.maze.graph);
-            key_nodes[i] = _key_nodes;
+            *nodes = _key_nodes;
         }
 
         // create a search branch for each index's grabbable keys
-        for i in 0..4 {
-            let key_node = &key_nodes[i];
-
+        for (i, key_node) in key_nodes.iter().enumerate() {
             for key in key_node {
                 let key_node = current_search.maze.graph.node_weight(*key).unwrap();
 
@@ -610,24 +415,22 @@ fn collect_all_given(amaze: &Maze) -> Option<usize> {
                 let mut cumulative_farthest_key: usize = 0;
                 let mut new_search_node_indexes: [NodeIndex<DefaultIx>; 4] = Default::default();
 
-                for j in 0..4 {
+                for (j, new_index) in new_search_node_indexes.iter_mut().enumerate() {
                     if i == j {
-                        new_search_node_indexes[j] = *key;
+                        *new_index = *key;
                     } else {
-                        new_search_node_indexes[j] = current_search.indexes[j];
+                        *new_index = current_search.indexes[j];
                     }
 
                     // a heuristic function that estimates the cost of the cheapest path from n to the goal.
                     // - it never overestimates the actual cost to get to the goal
                     //
                     // A* must examine all equally meritorious paths to find the optimal path.
-                    let farthest_key: Option<usize> =
-                        steps_to_farthest_key(new_search_node_indexes[j], &new_maze);
+                    let farthest_key: Option<usize> = steps_to_farthest_key(*new_index, &new_maze);
 
                     match farthest_key {
                         Some(_) => {
-                            cumulative_farthest_key =
-                                cumulative_farthest_key + farthest_key.unwrap();
+                            cumulative_farthest_key += farthest_key.unwrap();
                         }
                         None => {
                             // no other key for this @
@@ -646,9 +449,7 @@ fn collect_all_given(amaze: &Maze) -> Option<usize> {
 
                 if cumulative_farthest_key > 0 {
                     // there is some key still to get
-                    let cost: i32;
-
-                    match best_path {
+                    let cost: i32 = match best_path {
                         Some(i) => {
                             // the best possible path must be better to count (hence >=)
                             // note there might be a key along the way
@@ -658,13 +459,13 @@ fn collect_all_given(amaze: &Maze) -> Option<usize> {
                             }
 
                             // dfs
-                            cost = keys_left;
+                            keys_left
                         }
                         None => {
                             // if no best path exists, dfs to find one
-                            cost = keys_left;
+                            keys_left
                         }
-                    }
+                    };
 
                     new_search_node.cost = -(cost);
 
@@ -813,22 +614,14 @@ fn test_example4() {
     assert_eq!(collect_all(&maze), 72);
 }
 
-fn main() {
-    let reader = io::stdin();
-    let raw_map: Vec<Vec<char>> = reader
-        .lock()
-        .lines()
-        .map(|s| s.unwrap().chars().collect())
-        .collect();
-
+pub(crate) fn solve(raw_map: Vec<Vec<char>>) -> usize {
     let maze = get_lines_as_maze(split_into_four_sections(raw_map));
 
     let text = format!("{:?}", Dot::with_config(&maze.graph, &[]));
-    println!("{}", text);
+    debug!("{}", text);
 
-    let mut file = File::create("graph.dot").expect("failed to create graph.dot");
-    file.write(&text.into_bytes())
-        .expect("could not write into graph.dot");
+    let steps = collect_all(&maze);
+    info!("{} steps", steps);
 
-    println!("{} steps", collect_all(&maze));
+    return steps;
 }
@@ -0,0 +1,63 @@
+use crate::solution::Solution;
+
+mod day01;
+mod day02;
+mod day03;
+mod day03p2;
+mod day04;
+mod day04p2;
+mod day05;
+mod day07;
+mod day09;
+mod day11;
+mod day13;
+mod day15;
+mod day16;
+mod day18;
+mod day18p2;
+mod vault;
+
+pub use day01::Day01;
+pub use day02::Day02;
+pub use day03::Day03;
+pub use day04::Day04;
+pub use day05::Day05;
+pub use day07::Day07;
+pub use day09::Day09;
+pub use day11::Day11;
+pub use day13::Day13;
+pub use day15::Day15;
+pub use day16::Day16;
+pub use day18::Day18;
+
+// every solved day, in order
+pub fn all() -> Vec<Box<dyn Solution>> {
+    return vec![
+        Box::new(Day01::default()),
+        Box::new(Day02::default()),
+        Box::new(Day03::default()),
+        Box::new(Day04::default()),
+        Box::new(Day05::default()),
+        Box::new(Day07::default()),
+        Box::new(Day09::default()),
+        Box::new(Day11::default()),
+        Box::new(Day13::default()),
+        Box::new(Day15::default()),
+        Box::new(Day16::default()),
+        Box::new(Day18::default()),
+    ];
+}
+
+pub fn get(day: u32) -> Option<Box<dyn Solution>> {
+    return all().into_iter().find(|s| s.day() == day);
+}
+
+#[test]
+fn test_registry() {
+    let days: Vec<u32> = all().iter().map(|s| s.day()).collect();
+    assert_eq!(days, vec![1, 2, 3, 4, 5, 7, 9, 11, 13, 15, 16, 18]);
+
+    assert_eq!(get(11).unwrap().title(), "Space Police");
+    assert_eq!(get(11).unwrap().default_input(), "day11.input");
+    assert!(get(6).is_none());
+}
@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+
+use petgraph::graph::{DefaultIx, NodeIndex};
+use petgraph::stable_graph::StableGraph;
+use petgraph::visit::EdgeRef;
+
+use crate::corridors::{self, Corridors};
+use crate::debug;
+use crate::grid::Grid;
+
+// Day 18's vault, which both parts search: the map as it's drawn, and as a graph of the places
+// that matter in it, with the corridors between them as edges.
+
+#[derive(Debug, Clone)]
+pub struct Node {
+    pub x: usize,
+    pub y: usize,
+    pub c: String,
+    pub index: NodeIndex<DefaultIx>,
+}
+
+impl Node {
+    pub fn is_alphabetic(&self) -> bool {
+        return self.c.chars().next().unwrap().is_alphabetic();
+    }
+
+    pub fn is_key(&self) -> bool {
+        return self.is_alphabetic() && (self.c.to_lowercase() == self.c);
+    }
+
+    pub fn is_door(&self) -> bool {
+        return self.is_alphabetic() && (self.c.to_uppercase() == self.c);
+    }
+
+    pub fn key_opens(&self, key: &String) -> bool {
+        return self.c.to_lowercase() == *key;
+    }
+}
+
+pub fn grid(raw_map: &[Vec<char>]) -> Grid<char> {
+    let mut grid = Grid::new('#');
+    for (y, row) in raw_map.iter().enumerate() {
+        for (x, point) in row.iter().enumerate() {
+            grid.set(x as i32, y as i32, *point);
+        }
+    }
+    return grid;
+}
+
+// the vault's corridors, each joined up into one edge that remembers the cells it stands for
+pub fn contract(raw_map: &[Vec<char>]) -> Corridors<Node> {
+    debug!("{:?}", raw_map);
+
+    // keys, doors and entrances are kept, and so are the junctions between them; corridors become
+    // single edges as long as they are, and dead ends with nothing in them go
+    return corridors::contract(
+        &grid(raw_map),
+        |point| *point != '#',
+        |_, point| *point != '.',
+        |(x, y), point| Node {
+            x: x as usize,
+            y: y as usize,
+            c: if *point == '.' { ' ' } else { *point }.to_string(),
+            index: NodeIndex::new(0),
+        },
+    );
+}
+
+// The contracted vault as the directed graph the searches use, with an edge each way along each
+// corridor and the nodes at the same indexes.
+pub fn graph(corridors: &Corridors<Node>) -> StableGraph<Node, usize> {
+    let mut graph: StableGraph<Node, usize> = StableGraph::new();
+
+    let mut indexes: HashMap<NodeIndex<DefaultIx>, NodeIndex<DefaultIx>> = HashMap::new();
+    for ix in corridors.graph.node_indices() {
+        let jx = graph.add_node(corridors.graph[ix].clone());
+        graph[jx].index = jx;
+        indexes.insert(ix, jx);
+
+        let node = &graph[jx];
+        debug!("y {} x {} {:?}", node.y, node.x, node);
+    }
+
+    // A node's neighbours come out newest edge first, and the keys and doors a search can see are
+    // listed in the order its breadth first walk meets them, which is the order it then tries them
+    // in. The longest edges go in first so the nearest are met first.
+    let mut edges: Vec<_> = corridors.graph.edge_references().collect();
+    edges.sort_by_key(|edge| std::cmp::Reverse(*edge.weight()));
+    for edge in edges {
+        let (a, b) = (indexes[&edge.source()], indexes[&edge.target()]);
+        graph.add_edge(a, b, *edge.weight());
+        graph.add_edge(b, a, *edge.weight());
+    }
+
+    return graph;
+}
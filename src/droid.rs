@@ -0,0 +1,957 @@
+use std::collections::{HashSet, VecDeque};
+
+use serde::Serialize;
+
+use crate::grid::Grid;
+use crate::intcode::Computer;
+use crate::maze::{Heuristic, Map, MapError, NodeStatus};
+
+// Driving the day 15 repair droid. The remote control program executes the following steps in a
+// loop forever:
+//
+// - Accept a movement command via an input instruction.
+// - Send the movement command to the repair droid.
+// - Wait for the repair droid to finish the movement operation.
+// - Report on the status of the repair droid via an output instruction.
+//
+// An Explorer sends the moves a Strategy chooses, and records what the droid finds in a Map (for
+// finding paths) and a Grid (for drawing).
+
+// Only four movement commands are understood: north (1), south (2), west (3), and east (4).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    South,
+    West,
+    East,
+}
+
+impl Direction {
+    // in the order the depth first search pushes them
+    pub const ALL: [Direction; 4] = [
+        Direction::West,
+        Direction::East,
+        Direction::North,
+        Direction::South,
+    ];
+
+    pub fn command(self) -> i64 {
+        match self {
+            Direction::North => return 1,
+            Direction::South => return 2,
+            Direction::West => return 3,
+            Direction::East => return 4,
+        }
+    }
+
+    pub fn offset(self) -> (i32, i32) {
+        match self {
+            Direction::North => return (0, -1),
+            Direction::South => return (0, 1),
+            Direction::West => return (-1, 0),
+            Direction::East => return (1, 0),
+        }
+    }
+
+    pub fn from_command(command: i64) -> Option<Direction> {
+        match command {
+            1 => return Some(Direction::North),
+            2 => return Some(Direction::South),
+            3 => return Some(Direction::West),
+            4 => return Some(Direction::East),
+            _ => return None,
+        }
+    }
+
+    pub fn from_offset(offset: (i32, i32)) -> Option<Direction> {
+        return Direction::ALL
+            .iter()
+            .copied()
+            .find(|d| d.offset() == offset);
+    }
+
+    pub fn turn_left(self) -> Direction {
+        match self {
+            Direction::North => return Direction::West,
+            Direction::West => return Direction::South,
+            Direction::South => return Direction::East,
+            Direction::East => return Direction::North,
+        }
+    }
+
+    pub fn turn_right(self) -> Direction {
+        match self {
+            Direction::North => return Direction::East,
+            Direction::East => return Direction::South,
+            Direction::South => return Direction::West,
+            Direction::West => return Direction::North,
+        }
+    }
+}
+
+pub fn step(position: (i32, i32), direction: Direction) -> (i32, i32) {
+    let (ox, oy) = direction.offset();
+    return (position.0 + ox, position.1 + oy);
+}
+
+// The repair droid can reply with any of the following status codes:
+//
+// 0: The repair droid hit a wall. Its position has not changed.
+// 1: The repair droid has moved one step in the requested direction.
+// 2: The repair droid has moved one step in the requested direction; its new position is the
+//    location of the oxygen system.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reply {
+    Wall,
+    Moved,
+    Oxygen,
+}
+
+impl Reply {
+    pub fn from_status(status: i64) -> Option<Reply> {
+        match status {
+            0 => return Some(Reply::Wall),
+            1 => return Some(Reply::Moved),
+            2 => return Some(Reply::Oxygen),
+            _ => return None,
+        }
+    }
+}
+
+// Chooses where the droid goes next.
+pub trait Strategy {
+    fn name(&self) -> &'static str;
+
+    // the next move to send, or None once there's nowhere left worth going
+    fn next(&mut self, map: &Map, position: (i32, i32)) -> Option<Direction>;
+
+    // what came of the last move
+    fn reply(&mut self, _direction: Direction, _reply: Reply) {}
+}
+
+// the strategies that can be chosen by name
+pub const STRATEGIES: [&str; 3] = ["dfs", "bfs", "wall"];
+
+pub fn strategy(name: &str) -> Option<Box<dyn Strategy>> {
+    match name {
+        "dfs" => return Some(Box::new(DepthFirst::default())),
+        "bfs" => return Some(Box::new(NearestFrontier::default())),
+        "wall" => return Some(Box::new(WallFollower::default())),
+        _ => return None,
+    }
+}
+
+// the moves along a path found in the map
+fn route(map: &Map, position: (i32, i32), targets: &[(i32, i32)]) -> VecDeque<Direction> {
+    let heuristic = if targets.len() == 1 {
+        Heuristic::Manhattan
+    } else {
+        // the estimate looks at every target, which costs more than it saves with lots of them
+        Heuristic::None
+    };
+
+    let mut directions = VecDeque::new();
+    let path = match map.find_path(position, targets, heuristic) {
+        Some(path) => path,
+        None => return directions,
+    };
+
+    let mut from = position;
+    for index in path.nodes {
+        let node = map.get_node_by_index(index);
+        let to = (node.x, node.y);
+        directions.push_back(Direction::from_offset((to.0 - from.0, to.1 - from.1)).unwrap());
+        from = to;
+    }
+
+    return directions;
+}
+
+// It's a backtracking search: cells next to each one the droid reaches go on a stack, and the droid
+// goes to whichever is on top.
+#[derive(Default)]
+pub struct DepthFirst {
+    stack: Vec<(i32, i32)>,
+    expanded: HashSet<(i32, i32)>,
+    route: VecDeque<Direction>,
+}
+
+impl Strategy for DepthFirst {
+    fn name(&self) -> &'static str {
+        return "dfs";
+    }
+
+    fn next(&mut self, map: &Map, position: (i32, i32)) -> Option<Direction> {
+        // search in 4 cardinal directions, skipping what we've searched before
+        if self.expanded.insert(position) {
+            for direction in Direction::ALL.iter() {
+                let cell = step(position, *direction);
+                if map.status(cell.0, cell.1) == Some(NodeStatus::NotSure) {
+                    self.stack.push(cell);
+                }
+            }
+        }
+
+        while self.route.is_empty() {
+            let target = self.stack.pop()?;
+
+            // it may have been found out about on the way somewhere else
+            if map.status(target.0, target.1) != Some(NodeStatus::NotSure) {
+                continue;
+            }
+            self.route = route(map, position, &[target]);
+        }
+
+        return self.route.pop_front();
+    }
+
+    fn reply(&mut self, _direction: Direction, reply: Reply) {
+        // a way through a cell nobody had been to yet turned out to be blocked
+        if reply == Reply::Wall {
+            self.route.clear();
+        }
+    }
+}
+
+// Always heads for whichever unexplored cell is the fewest moves away.
+#[derive(Default)]
+pub struct NearestFrontier {
+    route: VecDeque<Direction>,
+}
+
+impl Strategy for NearestFrontier {
+    fn name(&self) -> &'static str {
+        return "bfs";
+    }
+
+    fn next(&mut self, map: &Map, position: (i32, i32)) -> Option<Direction> {
+        if self.route.is_empty() {
+            let frontier = frontier(map);
+            if frontier.is_empty() {
+                return None;
+            }
+            self.route = route(map, position, &frontier);
+        }
+
+        return self.route.pop_front();
+    }
+
+    fn reply(&mut self, _direction: Direction, reply: Reply) {
+        if reply == Reply::Wall {
+            self.route.clear();
+        }
+    }
+}
+
+// Keeps its left hand on the wall: after a move it tries turning left, and after hitting a wall it
+// tries turning right. That goes everywhere in a maze without loops and comes back to the start;
+// it stops once it's about to try a move it has tried before, from the same place.
+#[derive(Default)]
+pub struct WallFollower {
+    heading: Option<Direction>,
+    tried: HashSet<((i32, i32), Direction)>,
+}
+
+impl Strategy for WallFollower {
+    fn name(&self) -> &'static str {
+        return "wall";
+    }
+
+    fn next(&mut self, _map: &Map, position: (i32, i32)) -> Option<Direction> {
+        let heading = *self.heading.get_or_insert(Direction::North);
+
+        if !self.tried.insert((position, heading)) {
+            return None;
+        }
+
+        return Some(heading);
+    }
+
+    fn reply(&mut self, direction: Direction, reply: Reply) {
+        match reply {
+            Reply::Wall => self.heading = Some(direction.turn_right()),
+            Reply::Moved | Reply::Oxygen => self.heading = Some(direction.turn_left()),
+        }
+    }
+}
+
+// cells next to explored ones that nobody has been to yet
+pub fn frontier(map: &Map) -> Vec<(i32, i32)> {
+    let mut cells: Vec<(i32, i32)> = map
+        .graph
+        .node_indices()
+        .map(|i| map.get_node_by_index(i))
+        .filter(|n| n.status == NodeStatus::NotSure)
+        .map(|n| (n.x, n.y))
+        .collect();
+    cells.sort();
+    return cells;
+}
+
+// How one exploration went.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Report {
+    pub strategy: String,
+
+    // movement commands sent
+    pub moves: usize,
+
+    // cells found that the droid can move through
+    pub open: usize,
+
+    pub complete: bool,
+    pub oxygen: Option<(i32, i32)>,
+
+    // fewest moves from the start to the oxygen system, through what was explored
+    pub distance: Option<usize>,
+}
+
+// What the droids have found out about the area: a Map for finding paths and a Grid for drawing.
+// Droids start at (0, 0), which is taken to be empty.
+pub struct Chart {
+    pub map: Map,
+    pub grid: Grid<NodeStatus>,
+    pub oxygen: Option<(i32, i32)>,
+}
+
+impl Chart {
+    pub fn new() -> Chart {
+        let mut chart = Chart {
+            map: Map::new(),
+            grid: Grid::new(NodeStatus::NotSure),
+            oxygen: None,
+        };
+
+        chart.map.add_node(0, 0, NodeStatus::Empty);
+        chart.grid.set(0, 0, NodeStatus::Empty);
+        chart.look_around((0, 0));
+
+        return chart;
+    }
+
+    fn mark(&mut self, cell: (i32, i32), status: NodeStatus) {
+        self.map.update_node(cell.0, cell.1, status);
+        self.grid.set(cell.0, cell.1, status);
+    }
+
+    // Joins where a droid is to every neighbour it could go to, adding the ones nobody has been to
+    // yet.
+    fn look_around(&mut self, position: (i32, i32)) {
+        let (x, y) = position;
+
+        for direction in Direction::ALL.iter() {
+            let (nx, ny) = step(position, *direction);
+
+            match self.map.status(nx, ny) {
+                None => {
+                    self.map.add_node(nx, ny, NodeStatus::NotSure);
+                }
+                Some(NodeStatus::Wall) => continue,
+                Some(_) => {}
+            }
+
+            if !self.map.has_edge(x, y, nx, ny) {
+                self.map.add_edge(x, y, nx, ny);
+            }
+        }
+    }
+
+    // Records what came of a move, and returns where the droid is now.
+    pub fn record(&mut self, from: (i32, i32), direction: Direction, reply: Reply) -> (i32, i32) {
+        let target = step(from, direction);
+
+        match reply {
+            Reply::Wall => {
+                self.mark(target, NodeStatus::Wall);
+
+                // it may have been next to more than one cell a droid has been to
+                for direction in Direction::ALL.iter() {
+                    let (nx, ny) = step(target, *direction);
+                    if self.map.has_edge(target.0, target.1, nx, ny) {
+                        self.map.remove_edge(target.0, target.1, nx, ny);
+                    }
+                }
+
+                return from;
+            }
+            Reply::Moved | Reply::Oxygen => {
+                let status = if reply == Reply::Oxygen {
+                    self.oxygen = Some(target);
+                    NodeStatus::Oxygen
+                } else {
+                    NodeStatus::Empty
+                };
+
+                // a cell a droid has been to can't have changed
+                if self.map.status(target.0, target.1) == Some(NodeStatus::NotSure) {
+                    self.mark(target, status);
+                }
+                self.look_around(target);
+
+                return target;
+            }
+        }
+    }
+
+    // explored everywhere that can be reached
+    pub fn complete(&self) -> bool {
+        return frontier(&self.map).is_empty();
+    }
+
+    pub fn report(&self, strategy: &str, moves: usize) -> Report {
+        let distance = self
+            .oxygen
+            .and_then(|(x, y)| self.map.return_shortest_path_length(0, 0, x, y));
+
+        return Report {
+            strategy: strategy.to_string(),
+            moves,
+            open: self.grid.count_where(|s| s.is_open()),
+            complete: self.complete(),
+            oxygen: self.oxygen,
+            distance,
+        };
+    }
+}
+
+impl Default for Chart {
+    fn default() -> Chart {
+        return Chart::new();
+    }
+}
+
+pub struct Explorer<C: Computer> {
+    droid: C,
+
+    pub chart: Chart,
+    pub position: (i32, i32),
+
+    // movement commands sent so far
+    pub moves: usize,
+}
+
+impl<C: Computer> Explorer<C> {
+    pub fn new(droid: C) -> Explorer<C> {
+        return Explorer {
+            droid,
+            chart: Chart::new(),
+            position: (0, 0),
+            moves: 0,
+        };
+    }
+
+    pub fn droid(&self) -> &C {
+        return &self.droid;
+    }
+
+    // Sends one move and records the reply. None if the droid stopped answering.
+    pub fn send(&mut self, direction: Direction) -> Option<Reply> {
+        self.droid.send(direction.command());
+        self.moves += 1;
+        let reply = Reply::from_status(self.droid.recv()?)?;
+
+        self.position = self.chart.record(self.position, direction, reply);
+
+        return Some(reply);
+    }
+
+    // Explores until the strategy runs out of places to go. observe is shown the explorer before
+    // every move and can stop it by returning false; then this returns false too.
+    pub fn explore(
+        &mut self,
+        strategy: &mut dyn Strategy,
+        mut observe: impl FnMut(&Explorer<C>) -> bool,
+    ) -> bool {
+        loop {
+            if !observe(self) {
+                return false;
+            }
+
+            let direction = match strategy.next(&self.chart.map, self.position) {
+                Some(direction) => direction,
+                None => return true,
+            };
+
+            match self.send(direction) {
+                Some(reply) => strategy.reply(direction, reply),
+                None => return true,
+            }
+        }
+    }
+
+    pub fn complete(&self) -> bool {
+        return self.chart.complete();
+    }
+
+    pub fn report(&self, strategy: &str) -> Report {
+        return self.chart.report(strategy, self.moves);
+    }
+}
+
+// Several droids exploring together from the same start, each running its own copy of the
+// program, and all adding to one chart. Every round each droid makes one move. A droid with
+// nowhere to go is sent towards the nearest cell nobody has been to that no other droid is
+// already heading for, so they spread out instead of going over the same ground.
+pub struct Fleet<C: Computer> {
+    droids: Vec<Member<C>>,
+
+    pub chart: Chart,
+    pub rounds: usize,
+
+    // each droid that has got to the oxygen system, and the round it got there
+    pub found: Vec<(usize, usize)>,
+}
+
+struct Member<C: Computer> {
+    droid: C,
+    position: (i32, i32),
+    moves: usize,
+
+    // the cell it was sent to, and the way there
+    target: Option<(i32, i32)>,
+    route: VecDeque<Direction>,
+
+    // it stopped answering
+    lost: bool,
+}
+
+impl<C: Computer> Fleet<C> {
+    pub fn new(droids: Vec<C>) -> Fleet<C> {
+        let droids = droids
+            .into_iter()
+            .map(|droid| Member {
+                droid,
+                position: (0, 0),
+                moves: 0,
+                target: None,
+                route: VecDeque::new(),
+                lost: false,
+            })
+            .collect();
+
+        return Fleet {
+            droids,
+            chart: Chart::new(),
+            rounds: 0,
+            found: Vec::new(),
+        };
+    }
+
+    pub fn positions(&self) -> Vec<(i32, i32)> {
+        return self.droids.iter().map(|d| d.position).collect();
+    }
+
+    // movement commands sent to each droid
+    pub fn moves(&self) -> Vec<usize> {
+        return self.droids.iter().map(|d| d.moves).collect();
+    }
+
+    pub fn total_moves(&self) -> usize {
+        return self.droids.iter().map(|d| d.moves).sum();
+    }
+
+    // Sends a droid towards the nearest unexplored cell that's nobody else's target.
+    fn assign(&mut self, n: usize) {
+        let claimed: HashSet<(i32, i32)> = self
+            .droids
+            .iter()
+            .enumerate()
+            .filter(|(m, _)| *m != n)
+            .filter_map(|(_, d)| d.target)
+            .collect();
+        let unclaimed: Vec<(i32, i32)> = frontier(&self.chart.map)
+            .into_iter()
+            .filter(|cell| !claimed.contains(cell))
+            .collect();
+
+        let member = &mut self.droids[n];
+        member.route = route(&self.chart.map, member.position, &unclaimed);
+        member.target = if member.route.is_empty() {
+            None
+        } else {
+            Some(
+                member
+                    .route
+                    .iter()
+                    .fold(member.position, |cell, direction| step(cell, *direction)),
+            )
+        };
+    }
+
+    // Moves every droid that has somewhere to go once. Returns whether any did.
+    pub fn round(&mut self) -> bool {
+        let mut moved = false;
+
+        for n in 0..self.droids.len() {
+            if self.droids[n].lost {
+                continue;
+            }
+
+            // another droid may have found out about it already
+            if let Some((x, y)) = self.droids[n].target {
+                if self.chart.map.status(x, y) != Some(NodeStatus::NotSure) {
+                    self.droids[n].target = None;
+                    self.droids[n].route.clear();
+                }
+            }
+            if self.droids[n].route.is_empty() {
+                self.assign(n);
+            }
+
+            let member = &mut self.droids[n];
+            let direction = match member.route.pop_front() {
+                Some(direction) => direction,
+                None => continue,
+            };
+
+            member.droid.send(direction.command());
+            member.moves += 1;
+            moved = true;
+
+            let reply = match member.droid.recv().and_then(Reply::from_status) {
+                Some(reply) => reply,
+                None => {
+                    member.lost = true;
+                    member.target = None;
+                    continue;
+                }
+            };
+
+            member.position = self.chart.record(member.position, direction, reply);
+
+            if reply == Reply::Oxygen && !self.found.iter().any(|(m, _)| *m == n) {
+                self.found.push((n, self.rounds));
+            }
+            if reply == Reply::Wall || member.route.is_empty() {
+                member.target = None;
+                member.route.clear();
+            }
+        }
+
+        self.rounds += 1;
+        return moved;
+    }
+
+    // Explores until no droid has anywhere left to go. observe is shown the fleet before every
+    // round and can stop it by returning false; then this returns false too.
+    pub fn explore(&mut self, mut observe: impl FnMut(&Fleet<C>) -> bool) -> bool {
+        loop {
+            if !observe(self) {
+                return false;
+            }
+            if !self.round() {
+                return true;
+            }
+        }
+    }
+
+    pub fn complete(&self) -> bool {
+        return self.chart.complete();
+    }
+
+    pub fn report(&self) -> Report {
+        let name = format!("fleet of {}", self.droids.len());
+        return self.chart.report(&name, self.total_moves());
+    }
+}
+
+// A droid in a maze drawn the way maps are saved, answering movement commands the way the remote
+// control program does, for trying out explorers without it. Anywhere that isn't drawn as open
+// floor is a wall.
+pub struct MockDroid {
+    open: HashSet<(i32, i32)>,
+    oxygen: HashSet<(i32, i32)>,
+    replies: VecDeque<i64>,
+
+    // a command it doesn't understand stops it, as it would the real program
+    halted: bool,
+
+    pub position: (i32, i32),
+
+    // movement commands it has been sent
+    pub commands: usize,
+}
+
+impl MockDroid {
+    pub fn new(maze: &Map) -> MockDroid {
+        let mut open = HashSet::new();
+        let mut oxygen = HashSet::new();
+
+        for index in maze.graph.node_indices() {
+            let node = maze.get_node_by_index(index);
+            if node.status.is_open() {
+                open.insert((node.x, node.y));
+            }
+            if node.status == NodeStatus::Oxygen {
+                oxygen.insert((node.x, node.y));
+            }
+        }
+
+        return MockDroid {
+            open,
+            oxygen,
+            replies: VecDeque::new(),
+            halted: false,
+            position: (0, 0),
+            commands: 0,
+        };
+    }
+
+    pub fn from_text(text: &str) -> Result<MockDroid, MapError> {
+        return Ok(MockDroid::new(&Map::from_text(text)?));
+    }
+
+    pub fn load(path: &str) -> Result<MockDroid, MapError> {
+        return Ok(MockDroid::new(&Map::load(path)?));
+    }
+}
+
+impl Computer for MockDroid {
+    fn send(&mut self, v: i64) {
+        if self.halted {
+            return;
+        }
+
+        let direction = match Direction::from_command(v) {
+            Some(direction) => direction,
+            None => {
+                self.halted = true;
+                return;
+            }
+        };
+        self.commands += 1;
+
+        let target = step(self.position, direction);
+        if !self.open.contains(&target) {
+            self.replies.push_back(0);
+        } else if self.oxygen.contains(&target) {
+            self.position = target;
+            self.replies.push_back(2);
+        } else {
+            self.position = target;
+            self.replies.push_back(1);
+        }
+    }
+
+    fn recv(&mut self) -> Option<i64> {
+        return self.replies.pop_front();
+    }
+}
+
+#[cfg(test)]
+const TREE: &str = "\
+#########
+#D..#...#
+#.#.#.#.#
+#.#...#.#
+#.#####.#
+#...#..O#
+#########";
+
+#[cfg(test)]
+const LOOPS: &str = "\
+#########
+#.......#
+#.#.#.#.#
+#...D...#
+#.#.#.#.#
+#......O#
+#########";
+
+#[test]
+fn test_directions() {
+    for direction in Direction::ALL.iter() {
+        assert_eq!(Direction::from_offset(direction.offset()), Some(*direction));
+        assert_eq!(
+            Direction::from_command(direction.command()),
+            Some(*direction)
+        );
+        assert_eq!(direction.turn_left().turn_right(), *direction);
+        assert_eq!(
+            direction.turn_left().turn_left(),
+            direction.turn_right().turn_right()
+        );
+    }
+    assert_eq!(Direction::North.command(), 1);
+    assert_eq!(Direction::East.command(), 4);
+    assert_eq!(Reply::from_status(2), Some(Reply::Oxygen));
+    assert_eq!(Reply::from_status(3), None);
+    assert_eq!(Direction::from_command(5), None);
+}
+
+#[test]
+fn test_mock_droid() {
+    let mut droid = MockDroid::from_text(TREE).unwrap();
+
+    // into the wall to the north, then east twice and south into the dead end
+    for command in [1, 4, 4, 2, 2, 2].iter() {
+        droid.send(*command);
+    }
+    let replies: Vec<i64> = std::iter::from_fn(|| droid.recv()).collect();
+    assert_eq!(replies, vec![0, 1, 1, 1, 1, 0]);
+    assert_eq!(droid.position, (2, 2));
+    assert_eq!(droid.commands, 6);
+
+    // the oxygen system says so, and spaces are walls too
+    let mut droid = MockDroid::from_text("D.O \n").unwrap();
+    droid.send(4);
+    droid.send(4);
+    droid.send(4);
+    assert_eq!(droid.recv(), Some(1));
+    assert_eq!(droid.recv(), Some(2));
+    assert_eq!(droid.recv(), Some(0));
+
+    // and it stops answering once it's sent nonsense
+    droid.send(7);
+    droid.send(3);
+    assert_eq!(droid.recv(), None);
+    assert_eq!(droid.commands, 3);
+
+    assert!(MockDroid::from_text("...").is_err());
+}
+
+#[test]
+fn test_strategies() {
+    let mut moves = Vec::new();
+    for name in STRATEGIES.iter() {
+        let mut strategy = strategy(name).unwrap();
+        assert_eq!(strategy.name(), *name);
+
+        let mut explorer = Explorer::new(MockDroid::from_text(TREE).unwrap());
+        assert!(explorer.explore(strategy.as_mut(), |_| true));
+
+        // everything is found, whichever way it's done
+        assert!(explorer.complete(), "{} left some out", name);
+        assert_eq!(explorer.chart.oxygen, Some((6, 4)));
+        assert_eq!(
+            explorer.chart.grid.count_where(|s| *s != NodeStatus::Wall),
+            23,
+            "{}",
+            name
+        );
+        assert_eq!(
+            explorer.chart.map.return_shortest_path_length(0, 0, 6, 4),
+            Some(14)
+        );
+        moves.push(explorer.moves);
+
+        // saving the map and loading it again gives the same graph
+        let saved = Map::from_text(&explorer.chart.map.to_text()).unwrap();
+        assert_eq!(saved.to_text(), explorer.chart.map.to_text());
+        assert_eq!(
+            saved.graph.node_count(),
+            explorer.chart.map.graph.node_count()
+        );
+        assert_eq!(
+            saved.graph.edge_count(),
+            explorer.chart.map.graph.edge_count()
+        );
+    }
+
+    // heading for the nearest unexplored cell wastes the fewest moves here
+    assert_eq!(moves, vec![78, 74, 92]);
+
+    let mut explorer = Explorer::new(MockDroid::from_text(TREE).unwrap());
+    explorer.explore(&mut NearestFrontier::default(), |_| true);
+    let report = explorer.report("bfs");
+    assert_eq!(report.moves, 74);
+    assert_eq!(report.open, 23);
+    assert_eq!(report.distance, Some(14));
+
+    assert!(strategy("random").is_none());
+}
+
+#[test]
+fn test_loops() {
+    // with loops, paths go the short way round
+    for name in ["dfs", "bfs"].iter() {
+        let mut explorer = Explorer::new(MockDroid::from_text(LOOPS).unwrap());
+        explorer.explore(strategy(name).unwrap().as_mut(), |_| true);
+
+        assert!(explorer.complete());
+        assert_eq!(explorer.chart.oxygen, Some((3, 2)));
+        assert_eq!(
+            explorer.chart.map.return_shortest_path_length(0, 0, 3, 2),
+            Some(5)
+        );
+    }
+
+    // but keeping a hand on the wall just goes round the pillar the droid starts next to
+    let mut explorer = Explorer::new(MockDroid::from_text(LOOPS).unwrap());
+    explorer.explore(&mut WallFollower::default(), |_| true);
+    assert!(!explorer.complete());
+    assert_eq!(explorer.chart.oxygen, None);
+    assert_eq!(explorer.moves, 12);
+
+    // and can be stopped part way
+    let mut explorer = Explorer::new(MockDroid::from_text(LOOPS).unwrap());
+    let mut looks = 0;
+    let finished = explorer.explore(&mut NearestFrontier::default(), |_| {
+        looks += 1;
+        return looks <= 3;
+    });
+    assert!(!finished);
+    assert_eq!(explorer.moves, 3);
+}
+
+#[test]
+fn test_fleet() {
+    // one droid goes where the nearest frontier strategy would
+    let mut fleet = Fleet::new(vec![MockDroid::from_text(LOOPS).unwrap()]);
+    assert!(fleet.explore(|_| true));
+    assert!(fleet.complete());
+    let mut explorer = Explorer::new(MockDroid::from_text(LOOPS).unwrap());
+    explorer.explore(&mut NearestFrontier::default(), |_| true);
+    assert_eq!(fleet.total_moves(), explorer.moves);
+    let alone = fleet.total_moves();
+
+    // more share the work and finish sooner, though between them they go back and forth more
+    let mut totals = Vec::new();
+    let mut rounds = Vec::new();
+    for droids in 2..=4 {
+        let mut fleet = Fleet::new(
+            (0..droids)
+                .map(|_| MockDroid::from_text(LOOPS).unwrap())
+                .collect(),
+        );
+        assert!(fleet.explore(|_| true));
+
+        assert!(fleet.complete());
+        assert_eq!(fleet.chart.map.to_text(), explorer.chart.map.to_text());
+        assert_eq!(fleet.chart.oxygen, Some((3, 2)));
+        assert!(fleet.moves().iter().all(|m| *m > 0));
+        totals.push(fleet.total_moves());
+        rounds.push(fleet.rounds);
+
+        let report = fleet.report();
+        assert_eq!(report.strategy, format!("fleet of {}", droids));
+        assert_eq!(report.distance, Some(5));
+    }
+    assert_eq!(alone, 81);
+    assert_eq!(totals, vec![87, 90, 84]);
+    assert_eq!(rounds, vec![45, 32, 23]);
+
+    // one droid finds the oxygen system, and the other goes through it later on the way to the
+    // end of the corridor; it's still the one oxygen system
+    let mut fleet = Fleet::new(vec![
+        MockDroid::from_text("#...O.D#").unwrap(),
+        MockDroid::from_text("#...O.D#").unwrap(),
+    ]);
+    fleet.explore(|_| true);
+    assert_eq!(fleet.found, vec![(0, 1), (1, 6)]);
+    assert_eq!(fleet.chart.oxygen, Some((-2, 0)));
+    assert_eq!(
+        fleet.chart.grid.count_where(|s| *s == NodeStatus::Oxygen),
+        1
+    );
+
+    // a droid that stops answering is left behind, and the rest carry on
+    let mut fleet = Fleet::new(vec![
+        MockDroid::from_text("D").unwrap(),
+        MockDroid::from_text(TREE).unwrap(),
+    ]);
+    fleet.droids[0].droid.send(9);
+    fleet.explore(|_| true);
+    assert!(fleet.complete());
+    assert_eq!(fleet.moves()[0], 1);
+}
@@ -0,0 +1,504 @@
+use std::collections::HashMap;
+
+// A sparse two dimensional grid, for hulls, screens and maps that grow in any direction from where
+// they start. Cells that were never set read as the grid's default value. x grows to the right and
+// y grows downwards, the same as the puzzles draw them.
+
+// The smallest rectangle holding every cell that was ever set, inclusive on all sides.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bounds {
+    pub min_x: i32,
+    pub min_y: i32,
+    pub max_x: i32,
+    pub max_y: i32,
+}
+
+impl Bounds {
+    pub fn point(x: i32, y: i32) -> Bounds {
+        return Bounds {
+            min_x: x,
+            min_y: y,
+            max_x: x,
+            max_y: y,
+        };
+    }
+
+    pub fn include(&mut self, x: i32, y: i32) {
+        self.min_x = self.min_x.min(x);
+        self.min_y = self.min_y.min(y);
+        self.max_x = self.max_x.max(x);
+        self.max_y = self.max_y.max(y);
+    }
+
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        return self.min_x <= x && x <= self.max_x && self.min_y <= y && y <= self.max_y;
+    }
+
+    pub fn width(&self) -> usize {
+        return (self.max_x - self.min_x + 1) as usize;
+    }
+
+    pub fn height(&self) -> usize {
+        return (self.max_y - self.min_y + 1) as usize;
+    }
+}
+
+// up, right, down, left
+pub(crate) const OFFSETS4: [(i32, i32); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+// clockwise from up
+const OFFSETS8: [(i32, i32); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+// Hex grids use axial coordinates (q, r) for flat-topped hexes, clockwise from north: north,
+// north east, south east, south, south west, north west.
+pub const HEX_OFFSETS: [(i32, i32); 6] = [(0, -1), (1, -1), (1, 0), (0, 1), (-1, 1), (-1, 0)];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: HashMap<(i32, i32), T>,
+    default: T,
+    bounds: Option<Bounds>,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(default: T) -> Grid<T> {
+        return Grid {
+            cells: HashMap::new(),
+            default,
+            bounds: None,
+        };
+    }
+
+    // reading never creates a cell
+    pub fn get(&self, x: i32, y: i32) -> &T {
+        return self.cells.get(&(x, y)).unwrap_or(&self.default);
+    }
+
+    pub fn set(&mut self, x: i32, y: i32, v: T) {
+        match &mut self.bounds {
+            Some(bounds) => bounds.include(x, y),
+            None => self.bounds = Some(Bounds::point(x, y)),
+        }
+
+        self.cells.insert((x, y), v);
+    }
+
+    pub fn default_value(&self) -> &T {
+        return &self.default;
+    }
+
+    // how many cells were set, including ones set back to the default value
+    pub fn len(&self) -> usize {
+        return self.cells.len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.cells.is_empty();
+    }
+
+    // None until the first cell is set
+    pub fn bounds(&self) -> Option<Bounds> {
+        return self.bounds;
+    }
+
+    // every cell that was set, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = ((i32, i32), &T)> {
+        return self.cells.iter().map(|(p, v)| (*p, v));
+    }
+
+    pub fn neighbors4(&self, x: i32, y: i32) -> impl Iterator<Item = ((i32, i32), &T)> {
+        return OFFSETS4.iter().map(move |(dx, dy)| {
+            let p = (x + dx, y + dy);
+            (p, self.get(p.0, p.1))
+        });
+    }
+
+    pub fn neighbors8(&self, x: i32, y: i32) -> impl Iterator<Item = ((i32, i32), &T)> {
+        return OFFSETS8.iter().map(move |(dx, dy)| {
+            let p = (x + dx, y + dy);
+            (p, self.get(p.0, p.1))
+        });
+    }
+
+    // the six neighbours of a hex, for grids holding axial coordinates
+    pub fn neighbors_hex(&self, q: i32, r: i32) -> impl Iterator<Item = ((i32, i32), &T)> {
+        return HEX_OFFSETS.iter().map(move |(dq, dr)| {
+            let p = (q + dq, r + dr);
+            (p, self.get(p.0, p.1))
+        });
+    }
+
+    // A hex grid laid out on a square one so that it can be drawn. Each column is pushed down by
+    // half a hex more than the one to its left, so every hex takes two rows and every other row of
+    // each column is left at the default.
+    pub fn hex_layout(&self) -> Grid<T> {
+        let mut output: Grid<T> = Grid::new(self.default.clone());
+
+        for ((q, r), v) in self.iter() {
+            output.set(q, 2 * r + q, v.clone());
+        }
+
+        return output;
+    }
+
+    // counts cells that were set, cells left at the default are not looked at
+    pub fn count_where<F: Fn(&T) -> bool>(&self, f: F) -> usize {
+        return self.cells.values().filter(|v| f(v)).count();
+    }
+
+    // the position of a set cell matching f, if there is one (any one, if there are several)
+    pub fn find<F: Fn(&T) -> bool>(&self, f: F) -> Option<(i32, i32)> {
+        return self.cells.iter().find(|(_, v)| f(v)).map(|(p, _)| *p);
+    }
+
+    // the same cells (and so the same bounds) with f applied to every value
+    pub fn map<U: Clone, F: Fn(&T) -> U>(&self, f: F) -> Grid<U> {
+        return Grid {
+            cells: self.cells.iter().map(|(p, v)| (*p, f(v))).collect(),
+            default: f(&self.default),
+            bounds: self.bounds,
+        };
+    }
+
+    pub fn to_dense(&self) -> Dense<T> {
+        let bounds = match self.bounds {
+            Some(bounds) => bounds,
+            None => {
+                return Dense {
+                    origin: (0, 0),
+                    width: 0,
+                    height: 0,
+                    cells: Vec::new(),
+                }
+            }
+        };
+
+        let mut cells: Vec<T> = Vec::with_capacity(bounds.width() * bounds.height());
+        for y in bounds.min_y..=bounds.max_y {
+            for x in bounds.min_x..=bounds.max_x {
+                cells.push(self.get(x, y).clone());
+            }
+        }
+
+        return Dense {
+            origin: (bounds.min_x, bounds.min_y),
+            width: bounds.width(),
+            height: bounds.height(),
+            cells,
+        };
+    }
+
+    // every cell of the dense grid is set, so the bounds cover all of it
+    pub fn from_dense(dense: &Dense<T>, default: T) -> Grid<T> {
+        let mut grid = Grid::new(default);
+
+        for (i, v) in dense.cells.iter().enumerate() {
+            let x = dense.origin.0 + (i % dense.width) as i32;
+            let y = dense.origin.1 + (i / dense.width) as i32;
+            grid.set(x, y, v.clone());
+        }
+
+        return grid;
+    }
+}
+
+// A rectangle of cells stored row by row, with origin being the position of the first cell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dense<T> {
+    pub origin: (i32, i32),
+    pub width: usize,
+    pub height: usize,
+    pub cells: Vec<T>,
+}
+
+impl<T: Clone> Dense<T> {
+    // rows of equal length, with the top left cell at (0, 0)
+    pub fn from_rows(rows: &[Vec<T>]) -> Dense<T> {
+        let width = rows.first().map(|row| row.len()).unwrap_or(0);
+        assert!(
+            rows.iter().all(|row| row.len() == width),
+            "rows differ in length"
+        );
+
+        return Dense {
+            origin: (0, 0),
+            width,
+            height: rows.len(),
+            cells: rows.concat(),
+        };
+    }
+
+    pub fn rows(&self) -> Vec<Vec<T>> {
+        if self.width == 0 {
+            return Vec::new();
+        }
+        return self
+            .cells
+            .chunks(self.width)
+            .map(|row| row.to_vec())
+            .collect();
+    }
+
+    pub fn get(&self, x: i32, y: i32) -> Option<&T> {
+        let column = x - self.origin.0;
+        let row = y - self.origin.1;
+
+        if column < 0 || row < 0 || column as usize >= self.width || row as usize >= self.height {
+            return None;
+        }
+
+        return self.cells.get(row as usize * self.width + column as usize);
+    }
+}
+
+// The three dimensional version of Grid, for voxels. z grows upwards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Voxels<T> {
+    cells: HashMap<(i32, i32, i32), T>,
+    default: T,
+}
+
+// left, right, up, down (on the page), then below and above
+const OFFSETS6: [(i32, i32, i32); 6] = [
+    (-1, 0, 0),
+    (1, 0, 0),
+    (0, -1, 0),
+    (0, 1, 0),
+    (0, 0, -1),
+    (0, 0, 1),
+];
+
+impl<T: Clone> Voxels<T> {
+    pub fn new(default: T) -> Voxels<T> {
+        return Voxels {
+            cells: HashMap::new(),
+            default,
+        };
+    }
+
+    pub fn get(&self, x: i32, y: i32, z: i32) -> &T {
+        return self.cells.get(&(x, y, z)).unwrap_or(&self.default);
+    }
+
+    pub fn set(&mut self, x: i32, y: i32, z: i32, v: T) {
+        self.cells.insert((x, y, z), v);
+    }
+
+    pub fn len(&self) -> usize {
+        return self.cells.len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.cells.is_empty();
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = ((i32, i32, i32), &T)> {
+        return self.cells.iter().map(|(p, v)| (*p, v));
+    }
+
+    pub fn neighbors6(
+        &self,
+        x: i32,
+        y: i32,
+        z: i32,
+    ) -> impl Iterator<Item = ((i32, i32, i32), &T)> {
+        return OFFSETS6.iter().map(move |(dx, dy, dz)| {
+            let p = (x + dx, y + dy, z + dz);
+            (p, self.get(p.0, p.1, p.2))
+        });
+    }
+
+    pub fn count_where<F: Fn(&T) -> bool>(&self, f: F) -> usize {
+        return self.cells.values().filter(|v| f(v)).count();
+    }
+
+    // lowest and highest z of any set cell
+    pub fn z_range(&self) -> Option<(i32, i32)> {
+        let min = self.cells.keys().map(|p| p.2).min()?;
+        let max = self.cells.keys().map(|p| p.2).max()?;
+        return Some((min, max));
+    }
+
+    // one horizontal slice, as a grid that can be drawn
+    pub fn layer(&self, z: i32) -> Grid<T> {
+        let mut grid: Grid<T> = Grid::new(self.default.clone());
+
+        for ((x, y, cz), v) in self.iter() {
+            if cz == z {
+                grid.set(x, y, v.clone());
+            }
+        }
+
+        return grid;
+    }
+}
+
+#[test]
+fn test_grid() {
+    let mut grid: Grid<i32> = Grid::new(0);
+
+    assert_eq!(grid.len(), 0);
+    assert_eq!(grid.bounds(), None);
+
+    grid.set(0, 0, 1);
+    assert_eq!(grid.len(), 1);
+
+    grid.set(-1, 0, 0);
+    assert_eq!(grid.len(), 2);
+
+    grid.set(-1, -1, 1);
+    assert_eq!(grid.len(), 3);
+
+    grid.set(0, -1, 1);
+    assert_eq!(grid.len(), 4);
+
+    // setting a cell again doesn't add one
+    grid.set(0, 0, 0);
+    assert_eq!(grid.len(), 4);
+
+    grid.set(1, 0, 1);
+    assert_eq!(grid.len(), 5);
+
+    grid.set(1, 1, 1);
+    assert_eq!(grid.len(), 6);
+
+    assert_eq!(*grid.get(1, 1), 1);
+    assert_eq!(*grid.get(5, 5), 0);
+    assert_eq!(grid.len(), 6);
+
+    assert_eq!(
+        grid.bounds(),
+        Some(Bounds {
+            min_x: -1,
+            min_y: -1,
+            max_x: 1,
+            max_y: 1,
+        })
+    );
+    assert_eq!(grid.count_where(|v| *v == 1), 4);
+    assert_eq!(grid.count_where(|v| *v == 0), 2);
+}
+
+#[test]
+fn test_grid_find_and_neighbors() {
+    // a tiny breakout board
+    let mut grid: Grid<char> = Grid::new(' ');
+    grid.set(0, 0, '#');
+    grid.set(2, 0, '#');
+    grid.set(1, 1, '*');
+    grid.set(1, 2, '-');
+
+    assert_eq!(grid.find(|c| *c == '*'), Some((1, 1)));
+    assert_eq!(grid.find(|c| *c == '-'), Some((1, 2)));
+    assert_eq!(grid.find(|c| *c == 'W'), None);
+
+    let around: Vec<((i32, i32), char)> = grid.neighbors4(1, 1).map(|(p, c)| (p, *c)).collect();
+    assert_eq!(
+        around,
+        vec![((1, 0), ' '), ((2, 1), ' '), ((1, 2), '-'), ((0, 1), ' ')]
+    );
+
+    let walls = grid.neighbors8(1, 1).filter(|(_, c)| **c == '#').count();
+    assert_eq!(walls, 2);
+
+    let mut set: Vec<(i32, i32)> = grid.iter().map(|(p, _)| p).collect();
+    set.sort();
+    assert_eq!(set, vec![(0, 0), (1, 1), (1, 2), (2, 0)]);
+}
+
+#[test]
+fn test_dense() {
+    let mut grid: Grid<char> = Grid::new('.');
+    grid.set(-1, 0, '#');
+    grid.set(1, 1, '#');
+
+    let dense = grid.to_dense();
+    assert_eq!(dense.origin, (-1, 0));
+    assert_eq!((dense.width, dense.height), (3, 2));
+    assert_eq!(dense.rows(), vec![vec!['#', '.', '.'], vec!['.', '.', '#']]);
+    assert_eq!(dense.get(1, 1), Some(&'#'));
+    assert_eq!(dense.get(2, 1), None);
+
+    let back = Grid::from_dense(&dense, '.');
+    assert_eq!(back.bounds(), grid.bounds());
+    assert_eq!(back.to_dense(), dense);
+    assert_eq!(back.len(), 6);
+
+    let maze = Dense::from_rows(&[vec!['#', '#'], vec!['@', 'a']]);
+    let grid = Grid::from_dense(&maze, '#');
+    assert_eq!(grid.find(|c| *c == '@'), Some((0, 1)));
+    assert_eq!(*grid.get(1, 1), 'a');
+
+    assert_eq!(Grid::new(0).to_dense().rows(), Vec::<Vec<i32>>::new());
+}
+
+#[test]
+fn test_hex() {
+    let mut grid: Grid<char> = Grid::new('.');
+    grid.set(0, 0, 'o');
+    for (p, _) in grid.clone().neighbors_hex(0, 0) {
+        grid.set(p.0, p.1, '#');
+    }
+
+    // the hex north of the centre touches the centre and two of its other neighbours
+    let north: Vec<(i32, i32)> = grid.neighbors_hex(0, -1).map(|(p, _)| p).collect();
+    assert!(north.contains(&(0, 0)));
+    assert_eq!(
+        grid.neighbors_hex(0, -1)
+            .filter(|(_, c)| **c == '#')
+            .count(),
+        2
+    );
+
+    //  .#.
+    //  #.#
+    //  .o.
+    //  #.#
+    //  .#.
+    let laid_out = grid.hex_layout();
+    assert_eq!(
+        laid_out.to_dense().rows(),
+        vec![
+            vec!['.', '#', '.'],
+            vec!['#', '.', '#'],
+            vec!['.', 'o', '.'],
+            vec!['#', '.', '#'],
+            vec!['.', '#', '.'],
+        ]
+    );
+}
+
+#[test]
+fn test_voxels() {
+    let mut voxels: Voxels<i32> = Voxels::new(0);
+    assert_eq!(voxels.z_range(), None);
+
+    voxels.set(0, 0, 0, 1);
+    voxels.set(1, 0, 0, 1);
+    voxels.set(0, 0, 2, 1);
+    voxels.set(0, 0, 2, 1);
+
+    assert_eq!(voxels.len(), 3);
+    assert_eq!(*voxels.get(0, 0, 2), 1);
+    assert_eq!(*voxels.get(0, 0, 1), 0);
+    assert_eq!(voxels.z_range(), Some((0, 2)));
+    assert_eq!(voxels.count_where(|v| *v == 1), 3);
+    assert_eq!(
+        voxels.neighbors6(0, 0, 1).filter(|(_, v)| **v == 1).count(),
+        2
+    );
+
+    let ground = voxels.layer(0);
+    assert_eq!(ground.len(), 2);
+    assert_eq!(ground.to_dense().rows(), vec![vec![1, 1]]);
+    assert!(voxels.layer(1).is_empty());
+}
@@ -0,0 +1,891 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::ops::{Index, IndexMut};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(PartialEq, Copy, Clone, Debug, Default)]
+enum ParameterMode {
+    // which causes the parameter to be interpreted as a position - if the parameter is 50, its
+    // value is the value stored at address 50 in memory.
+    #[default]
+    Position = 0,
+
+    // a parameter is interpreted as a value - if the parameter is 50, its value is simply 50.
+    Immediate,
+
+    // the parameter is interpreted as a position like Position mode
+    // except relative mode parameters don't count from address 0. Instead, they count from a value called the relative base.
+    // The address a relative mode parameter refers to is itself plus the current relative base.
+    Relative,
+}
+
+fn get_parameter_modes_from_opcode(opcode: i64) -> [ParameterMode; 4] {
+    // Parameter modes are stored in the same value as the instruction's opcode.
+    //
+    // Parameter modes are single digits, one per parameter, read right-to-left from the opcode:
+    //
+    // - the first parameter's mode is in the hundreds digit,
+    // - the second parameter's mode is in the thousands digit,
+    // - the third parameter's mode is in the ten-thousands digit,
+    // - and so on.
+    //
+    // Any missing modes are 0 (== Position)
+
+    let mut parameter_mode: [ParameterMode; 4] = Default::default();
+
+    let mut t = opcode;
+    let mut i = 0;
+
+    while t > 0 {
+        if (t % 10) == 0 {
+            parameter_mode[i] = ParameterMode::Position;
+        } else if (t % 10) == 1 {
+            parameter_mode[i] = ParameterMode::Immediate;
+        } else if (t % 10) == 2 {
+            parameter_mode[i] = ParameterMode::Relative;
+        }
+
+        i += 1;
+        t /= 10;
+    }
+
+    return parameter_mode;
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Memory {
+    pub memory: HashMap<i64, i64>,
+}
+
+impl Memory {
+    pub fn from_program(program: &[i64]) -> Memory {
+        let mut memory: Memory = Default::default();
+
+        for (i, v) in program.iter().enumerate() {
+            memory[i as i64] = *v;
+        }
+
+        return memory;
+    }
+
+    // the first len addresses, for comparing against a program listing
+    pub fn to_vec(&self, len: usize) -> Vec<i64> {
+        return (0..len).map(|i| self[i as i64]).collect();
+    }
+}
+
+impl Index<i64> for Memory {
+    type Output = i64;
+
+    fn index(&self, index: i64) -> &Self::Output {
+        if index < 0 {
+            panic!("index {} < 0!", index);
+        }
+        return self.memory.get(&index).unwrap_or(&0);
+    }
+}
+
+impl IndexMut<i64> for Memory {
+    fn index_mut(&mut self, index: i64) -> &mut Self::Output {
+        if index < 0 {
+            panic!("index < 0!");
+        }
+        return self.memory.entry(index).or_insert(0);
+    }
+}
+
+#[test]
+fn test_memory() {
+    let mut memory = Memory {
+        memory: Default::default(),
+    };
+
+    assert_eq!(memory[0], 0);
+    assert_eq!(memory[1000], 0);
+
+    memory[1000] = 123;
+
+    assert_eq!(memory[0], 0);
+    assert_eq!(memory[1000], 123);
+
+    // only 1000 written in
+    assert_eq!(memory.memory.keys().len(), 1);
+
+    memory[0] = 23874612876341;
+
+    assert_eq!(memory[0], 23874612876341);
+    assert_eq!(memory[1000], 123);
+
+    assert_eq!(memory.memory.keys().len(), 2);
+
+    memory[985237621] = 72346571;
+
+    assert_eq!(memory[0], 23874612876341);
+    assert_eq!(memory[1000], 123);
+    assert_eq!(memory[985237621], 72346571);
+
+    assert_eq!(memory.memory.keys().len(), 3);
+}
+
+fn get_value(output: &Memory, iptr: i64, param_mode: ParameterMode, rbase: i64) -> i64 {
+    let param = output[iptr];
+
+    match param_mode {
+        ParameterMode::Position => output[param],
+        ParameterMode::Immediate => param,
+        ParameterMode::Relative => output[param + rbase],
+    }
+}
+
+fn set_value(output: &mut Memory, iptr: i64, param_mode: ParameterMode, rbase: i64, v: i64) {
+    let param = output[iptr];
+
+    match param_mode {
+        ParameterMode::Position => {
+            output[param] = v;
+        }
+        ParameterMode::Relative => {
+            output[param + rbase] = v;
+        }
+        ParameterMode::Immediate => {
+            // Parameters that an instruction writes to will never be in immediate mode.
+            panic!("write to immediate mode parameter at {}", iptr);
+        }
+    }
+}
+
+#[test]
+fn test_relative_mode() {
+    // Parameters in mode 2, relative mode, behave very similarly to parameters in position mode:
+    // the parameter is interpreted as a position. Like position mode, parameters in relative mode
+    // can be read from or written to.
+    //
+    // The address a relative mode parameter refers to is itself plus the current relative base.
+    //
+    // When the relative base is 0, relative mode parameters and position mode parameters with the
+    // same value refer to the same address. For example, given a relative base of 50, a relative
+    // mode parameter of -7 refers to memory address 50 + -7 = 43.
+
+    let mut memory = Memory {
+        memory: Default::default(),
+    };
+
+    memory[43] = 873645927183645;
+
+    let rbase = 50;
+    let parameter = -7;
+
+    memory[0] = parameter;
+
+    assert_eq!(
+        873645927183645,
+        get_value(&memory, 0, ParameterMode::Relative, rbase)
+    );
+}
+
+// What the computer is doing, as far as the host can tell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MachineState {
+    Running,
+    WaitingOnInput,
+    Halted,
+}
+
+impl fmt::Display for MachineState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MachineState::Running => write!(f, "running"),
+            MachineState::WaitingOnInput => write!(f, "waiting on input"),
+            MachineState::Halted => write!(f, "halted"),
+        }
+    }
+}
+
+pub struct IntcodeComputer {
+    input_sender: mpsc::Sender<i64>,
+    output_receiver: mpsc::Receiver<i64>,
+    halt_receiver: mpsc::Receiver<i64>,
+    wait_receiver: mpsc::Receiver<i64>,
+    waiting_on_input: bool,
+    halted: bool,
+    instructions: Arc<AtomicU64>,
+    thread_handle: thread::JoinHandle<Memory>,
+}
+
+pub fn run_intcode_computer(name: String, program: Vec<i64>) -> IntcodeComputer {
+    let (isend, irecv) = mpsc::channel();
+    let (osend, orecv) = mpsc::channel();
+    let (hsend, hrecv) = mpsc::channel();
+    let (wsend, wrecv) = mpsc::channel();
+    let instructions = Arc::new(AtomicU64::new(0));
+    let counter = Arc::clone(&instructions);
+    return IntcodeComputer {
+        input_sender: isend,
+        output_receiver: orecv,
+        halt_receiver: hrecv,
+        wait_receiver: wrecv,
+        waiting_on_input: false,
+        halted: false,
+        instructions,
+        thread_handle: thread::Builder::new()
+            .name(name)
+            .spawn(move || {
+                return intcode_program(program, 0, irecv, osend, hsend, wsend, &counter);
+            })
+            .unwrap(),
+    };
+}
+
+// How a host talks to a computer: values in, values out. IntcodeComputer is one, and anything else
+// that answers the same way can stand in for it.
+pub trait Computer {
+    fn send(&mut self, v: i64);
+
+    // the next output, or None once there won't be any more
+    fn recv(&mut self) -> Option<i64>;
+}
+
+impl Computer for IntcodeComputer {
+    fn send(&mut self, v: i64) {
+        IntcodeComputer::send(self, v);
+    }
+
+    fn recv(&mut self) -> Option<i64> {
+        return self.recv2().ok();
+    }
+}
+
+impl IntcodeComputer {
+    pub fn send(&mut self, v: i64) {
+        self.waiting_on_input = false;
+        self.input_sender.send(v).expect("unable to send input!");
+    }
+
+    pub fn recv(&self) -> i64 {
+        return self.output_receiver.recv().unwrap();
+    }
+
+    pub fn recv2(&self) -> Result<i64, mpsc::RecvError> {
+        return self.output_receiver.recv();
+    }
+
+    pub fn try_recv(&self) -> Option<i64> {
+        return self.output_receiver.try_recv().ok();
+    }
+
+    pub fn waiting_on_input(&mut self) -> bool {
+        if self.waiting_on_input {
+            return true;
+        }
+
+        if self.wait_receiver.try_recv().is_ok() {
+            self.waiting_on_input = true;
+        }
+        return self.waiting_on_input;
+    }
+
+    pub fn halted(&mut self) -> bool {
+        // the computer has halted if there's a value here
+        if self.halt_receiver.try_recv().is_ok() {
+            self.halted = true;
+        }
+        return self.halted;
+    }
+
+    pub fn state(&mut self) -> MachineState {
+        if self.halted() {
+            return MachineState::Halted;
+        }
+        if self.waiting_on_input() {
+            return MachineState::WaitingOnInput;
+        }
+        return MachineState::Running;
+    }
+
+    // how many instructions the program has run so far
+    pub fn instructions(&self) -> u64 {
+        return self.instructions.load(Ordering::Relaxed);
+    }
+
+    // wait for the program to halt and return its final memory
+    pub fn join(self) -> Memory {
+        return self.thread_handle.join().unwrap();
+    }
+}
+
+// How far a Machine got with one step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    Ran,
+    Output(i64),
+    // the next instruction reads input and none has been sent; nothing was run
+    NeedsInput,
+    Halted,
+}
+
+// A computer that runs on the caller's thread, one instruction at a time. Everything about it is
+// in here, so a clone is a snapshot that can be gone back to, and it can be saved as JSON and
+// picked up again later.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Machine {
+    pub memory: Memory,
+    pub iptr: i64,
+    pub rbase: i64,
+
+    // sent but not read yet
+    pub inputs: VecDeque<i64>,
+
+    // how many instructions have run so far
+    pub instructions: u64,
+    pub halted: bool,
+}
+
+impl Machine {
+    pub fn new(program: &[i64]) -> Machine {
+        return Machine {
+            memory: Memory::from_program(program),
+            ..Default::default()
+        };
+    }
+
+    pub fn send(&mut self, v: i64) {
+        self.inputs.push_back(v);
+    }
+
+    pub fn state(&self) -> MachineState {
+        if self.halted {
+            return MachineState::Halted;
+        }
+        if self.inputs.is_empty() && self.memory[self.iptr] % 100 == 3 {
+            return MachineState::WaitingOnInput;
+        }
+        return MachineState::Running;
+    }
+
+    // Runs the next instruction, and says whether it output anything. Once halted, or while the
+    // next instruction is waiting on input that hasn't been sent, nothing is run.
+    pub fn step(&mut self) -> Step {
+        if self.halted {
+            return Step::Halted;
+        }
+
+        let mut output = None;
+
+        // The opcode is a two-digit number based only on the ones and tens digit of the value
+        let opcode = self.memory[self.iptr] % 100;
+        let param_modes = get_parameter_modes_from_opcode(self.memory[self.iptr] / 100);
+
+        // It is important to remember that the instruction pointer should increase by the number
+        // of values in the instruction after the instruction finishes.
+        let step;
+
+        // Parameters that an instruction writes to will never be in immediate mode.
+
+        match opcode {
+            // Opcode 1 adds together numbers read from two positions and stores the result in a
+            // third position. The three integers immediately after the opcode tell you these three
+            // positions - the first two indicate the positions from which you should read the
+            // input values, and the third indicates the position at which the output should be
+            // stored.
+            1 => {
+                let i1 = get_value(&self.memory, self.iptr + 1, param_modes[0], self.rbase);
+                let i2 = get_value(&self.memory, self.iptr + 2, param_modes[1], self.rbase);
+                set_value(
+                    &mut self.memory,
+                    self.iptr + 3,
+                    param_modes[2],
+                    self.rbase,
+                    i1 + i2,
+                );
+
+                step = 4;
+            }
+
+            // Opcode 2 works exactly like opcode 1, except it multiplies the two inputs instead of
+            // adding them.
+            2 => {
+                let i1 = get_value(&self.memory, self.iptr + 1, param_modes[0], self.rbase);
+                let i2 = get_value(&self.memory, self.iptr + 2, param_modes[1], self.rbase);
+                set_value(
+                    &mut self.memory,
+                    self.iptr + 3,
+                    param_modes[2],
+                    self.rbase,
+                    i1 * i2,
+                );
+
+                step = 4;
+            }
+
+            // Opcode 3 takes a single integer as input and saves it to the position given by its
+            // only parameter. For example, the instruction 3,50 would take an input value and
+            // store it at address 50.
+            3 => {
+                let i = match self.inputs.pop_front() {
+                    Some(i) => i,
+                    // nothing to read yet, so stay on this instruction until something is sent
+                    None => return Step::NeedsInput,
+                };
+
+                set_value(
+                    &mut self.memory,
+                    self.iptr + 1,
+                    param_modes[0],
+                    self.rbase,
+                    i,
+                );
+
+                step = 2;
+            }
+
+            // Opcode 4 outputs the value of its only parameter. For example, the instruction 4,50
+            // would output the value at address 50.
+            4 => {
+                let i1 = get_value(&self.memory, self.iptr + 1, param_modes[0], self.rbase);
+
+                output = Some(i1);
+
+                step = 2;
+            }
+
+            // Opcode 5 is jump-if-true: if the first parameter is non-zero, it sets the
+            // instruction pointer to the value from the second parameter. Otherwise, it does
+            // nothing.
+            5 => {
+                let i1 = get_value(&self.memory, self.iptr + 1, param_modes[0], self.rbase);
+                let i2 = get_value(&self.memory, self.iptr + 2, param_modes[1], self.rbase);
+
+                if i1 != 0 {
+                    self.iptr = i2;
+                    step = 0;
+                } else {
+                    step = 3;
+                }
+            }
+
+            // Opcode 6 is jump-if-false: if the first parameter is zero, it sets the instruction
+            // pointer to the value from the second parameter. Otherwise, it does nothing.
+            6 => {
+                let i1 = get_value(&self.memory, self.iptr + 1, param_modes[0], self.rbase);
+                let i2 = get_value(&self.memory, self.iptr + 2, param_modes[1], self.rbase);
+
+                if i1 == 0 {
+                    self.iptr = i2;
+                    step = 0;
+                } else {
+                    step = 3;
+                }
+            }
+
+            // Opcode 7 is less than: if the first parameter is less than the second parameter, it
+            // stores 1 in the position given by the third parameter. Otherwise, it stores 0.
+            7 => {
+                let i1 = get_value(&self.memory, self.iptr + 1, param_modes[0], self.rbase);
+                let i2 = get_value(&self.memory, self.iptr + 2, param_modes[1], self.rbase);
+
+                if i1 < i2 {
+                    set_value(
+                        &mut self.memory,
+                        self.iptr + 3,
+                        param_modes[2],
+                        self.rbase,
+                        1,
+                    );
+                } else {
+                    set_value(
+                        &mut self.memory,
+                        self.iptr + 3,
+                        param_modes[2],
+                        self.rbase,
+                        0,
+                    );
+                }
+
+                step = 4;
+            }
+
+            // Opcode 8 is equals: if the first parameter is equal to the second parameter, it
+            // stores 1 in the position given by the third parameter. Otherwise, it stores 0.
+            8 => {
+                let i1 = get_value(&self.memory, self.iptr + 1, param_modes[0], self.rbase);
+                let i2 = get_value(&self.memory, self.iptr + 2, param_modes[1], self.rbase);
+
+                if i1 == i2 {
+                    set_value(
+                        &mut self.memory,
+                        self.iptr + 3,
+                        param_modes[2],
+                        self.rbase,
+                        1,
+                    );
+                } else {
+                    set_value(
+                        &mut self.memory,
+                        self.iptr + 3,
+                        param_modes[2],
+                        self.rbase,
+                        0,
+                    );
+                }
+
+                step = 4;
+            }
+
+            // Opcode 9 adjusts the relative base by the value of its only parameter. The relative
+            // base increases (or decreases, if the value is negative) by the value of the
+            // parameter.
+            9 => {
+                let i1 = get_value(&self.memory, self.iptr + 1, param_modes[0], self.rbase);
+                self.rbase += i1;
+
+                step = 2;
+            }
+
+            // 99 means that the program is finished
+            99 => {
+                // halt!
+                self.halted = true;
+                self.instructions += 1;
+                return Step::Halted;
+            }
+
+            x => {
+                panic!("unrecognized opcode {}", x);
+            }
+        }
+
+        self.iptr += step;
+        self.instructions += 1;
+
+        match output {
+            Some(v) => return Step::Output(v),
+            None => return Step::Ran,
+        }
+    }
+
+    // Runs until the program halts or wants input that hasn't been sent, returning everything it
+    // output on the way.
+    pub fn run(&mut self) -> Vec<i64> {
+        let mut outputs = Vec::new();
+
+        loop {
+            match self.step() {
+                Step::Ran => {}
+                Step::Output(v) => outputs.push(v),
+                Step::NeedsInput | Step::Halted => return outputs,
+            }
+        }
+    }
+}
+
+pub fn intcode_program(
+    input: Vec<i64>,
+    ip: i64,
+    computer_input: mpsc::Receiver<i64>,
+    computer_output: mpsc::Sender<i64>,
+    computer_halted: mpsc::Sender<i64>,
+    wait_output: mpsc::Sender<i64>,
+    instructions: &AtomicU64,
+) -> Memory {
+    let mut machine = Machine::new(&input);
+    machine.iptr = ip;
+
+    // The host may have hung up on any of these channels already (a finished game, an amplifier
+    // whose output nobody reads any more) so send results are deliberately ignored.
+
+    loop {
+        let step = machine.step();
+        instructions.store(machine.instructions, Ordering::Relaxed);
+
+        match step {
+            Step::Ran => {}
+            Step::Output(v) => {
+                let _ = computer_output.send(v);
+            }
+            Step::NeedsInput => {
+                let _ = wait_output.send(0);
+
+                // nobody left to send any, so the program can never go on
+                match computer_input.recv() {
+                    Ok(i) => machine.send(i),
+                    Err(_) => return machine.memory,
+                }
+            }
+            Step::Halted => {
+                let _ = computer_halted.send(0);
+                return machine.memory;
+            }
+        }
+    }
+}
+
+// Run a program to completion on the current thread with all of its input supplied up front,
+// returning the final memory and everything it output. None if it wants more input than it was
+// given.
+pub fn run_program(program: Vec<i64>, inputs: &[i64]) -> Option<(Memory, Vec<i64>)> {
+    let mut machine = Machine::new(&program);
+    for i in inputs {
+        machine.send(*i);
+    }
+
+    let outputs = machine.run();
+    if !machine.halted {
+        return None;
+    }
+
+    return Some((machine.memory, outputs));
+}
+
+#[test]
+fn test_run_program() {
+    // echo program
+    let (memory, outputs) = run_program(vec![3, 0, 4, 0, 99], &[1234]).unwrap();
+
+    assert_eq!(outputs, vec![1234]);
+    assert_eq!(memory.to_vec(5), vec![1234, 0, 4, 0, 99]);
+
+    // echoes twice, but only gets one thing to echo
+    let program = vec![3, 0, 4, 0, 3, 0, 4, 0, 99];
+    assert_eq!(run_program(program, &[1234]), None);
+}
+
+#[test]
+fn test_quine() {
+    let program = vec![
+        109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
+    ];
+    let ic = run_intcode_computer("ic".to_string(), program.clone());
+    let memory: Memory = ic.join();
+
+    assert_eq!(memory.to_vec(program.len()), program);
+}
+
+#[test]
+fn test_16_digit() {
+    let ic = run_intcode_computer(
+        "ic".to_string(),
+        vec![1102, 34915192, 34915192, 7, 4, 7, 99, 0],
+    );
+    assert_eq!(1219070632396864, ic.recv());
+}
+
+#[test]
+fn test_output_large_middle() {
+    let ic = run_intcode_computer("ic".to_string(), vec![104, 1125899906842624, 99]);
+    assert_eq!(1125899906842624, ic.recv());
+}
+
+#[test]
+fn test_halted_is_sticky() {
+    let mut ic = run_intcode_computer("ic".to_string(), vec![104, 7, 99]);
+    assert_eq!(7, ic.recv());
+
+    // the halt notice follows the last output
+    while !ic.halted() {
+        thread::yield_now();
+    }
+    assert!(ic.halted());
+    assert_eq!(ic.state(), MachineState::Halted);
+
+    // output, then halt
+    assert_eq!(ic.instructions(), 2);
+}
+
+#[test]
+fn test_machine() {
+    // add one to each input and output it, until a 0 comes in
+    let program = vec![3, 20, 1006, 20, 14, 1001, 20, 1, 21, 4, 21, 1105, 1, 0, 99];
+    let mut machine = Machine::new(&program);
+
+    assert_eq!(machine.step(), Step::NeedsInput);
+    assert_eq!(machine.state(), MachineState::WaitingOnInput);
+    assert_eq!(machine.instructions, 0);
+
+    machine.send(5);
+    machine.send(8);
+    assert_eq!(machine.run(), vec![6, 9]);
+    assert_eq!(machine.state(), MachineState::WaitingOnInput);
+
+    // a clone carries on from the same place, separately
+    let mut snapshot = machine.clone();
+    machine.send(0);
+    assert_eq!(machine.run(), Vec::<i64>::new());
+    assert_eq!(machine.state(), MachineState::Halted);
+    assert_eq!(machine.step(), Step::Halted);
+
+    snapshot.send(41);
+    assert_eq!(snapshot.run(), vec![42]);
+
+    // and so does one that went through JSON
+    let json = serde_json::to_string(&snapshot).unwrap();
+    let mut loaded: Machine = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded, snapshot);
+    loaded.send(1);
+    assert_eq!(loaded.run(), vec![2]);
+}
+
+// An operand for the Assembler: a value written into the program, a named variable, or a fixed
+// address. Labels are only ever jumped to.
+#[cfg(test)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Arg {
+    Value(i64),
+    Var(String),
+    Address(i64),
+    Label(String),
+}
+
+#[cfg(test)]
+impl From<i64> for Arg {
+    fn from(v: i64) -> Arg {
+        return Arg::Value(v);
+    }
+}
+
+#[cfg(test)]
+impl From<i32> for Arg {
+    fn from(v: i32) -> Arg {
+        return Arg::Value(v as i64);
+    }
+}
+
+#[cfg(test)]
+impl From<&str> for Arg {
+    fn from(name: &str) -> Arg {
+        return Arg::Var(name.to_string());
+    }
+}
+
+// Writes small Intcode programs for tests one instruction at a time. Jumps go to named labels and
+// variables are named too; both are only given addresses when the program is built, with the
+// variables after the code, starting at 0.
+#[cfg(test)]
+#[derive(Debug, Default)]
+pub struct Assembler {
+    code: Vec<i64>,
+    labels: HashMap<String, i64>,
+
+    // where each label and variable is used, to fill in once they all have addresses
+    uses: Vec<(usize, Arg)>,
+}
+
+#[cfg(test)]
+impl Assembler {
+    fn instruction(&mut self, opcode: i64, args: Vec<Arg>) {
+        let mut modes = 0;
+        for (i, arg) in args.iter().enumerate() {
+            if let Arg::Value(_) | Arg::Label(_) = arg {
+                modes += 10_i64.pow(i as u32);
+            }
+        }
+        self.code.push(modes * 100 + opcode);
+
+        for arg in args {
+            match arg {
+                Arg::Value(v) | Arg::Address(v) => self.code.push(v),
+                named => {
+                    self.uses.push((self.code.len(), named));
+                    self.code.push(0);
+                }
+            }
+        }
+    }
+
+    pub fn add(&mut self, a: impl Into<Arg>, b: impl Into<Arg>, to: impl Into<Arg>) {
+        self.instruction(1, vec![a.into(), b.into(), to.into()]);
+    }
+
+    pub fn mul(&mut self, a: impl Into<Arg>, b: impl Into<Arg>, to: impl Into<Arg>) {
+        self.instruction(2, vec![a.into(), b.into(), to.into()]);
+    }
+
+    pub fn input(&mut self, to: impl Into<Arg>) {
+        self.instruction(3, vec![to.into()]);
+    }
+
+    pub fn output(&mut self, a: impl Into<Arg>) {
+        self.instruction(4, vec![a.into()]);
+    }
+
+    pub fn jump_if_true(&mut self, a: impl Into<Arg>, label: &str) {
+        self.instruction(5, vec![a.into(), Arg::Label(label.to_string())]);
+    }
+
+    pub fn jump_if_false(&mut self, a: impl Into<Arg>, label: &str) {
+        self.instruction(6, vec![a.into(), Arg::Label(label.to_string())]);
+    }
+
+    pub fn less_than(&mut self, a: impl Into<Arg>, b: impl Into<Arg>, to: impl Into<Arg>) {
+        self.instruction(7, vec![a.into(), b.into(), to.into()]);
+    }
+
+    pub fn equals(&mut self, a: impl Into<Arg>, b: impl Into<Arg>, to: impl Into<Arg>) {
+        self.instruction(8, vec![a.into(), b.into(), to.into()]);
+    }
+
+    pub fn halt(&mut self) {
+        self.instruction(99, vec![]);
+    }
+
+    // there's no instruction for these, so they're made out of others
+    pub fn copy(&mut self, from: impl Into<Arg>, to: impl Into<Arg>) {
+        self.add(from, 0, to);
+    }
+
+    pub fn jump(&mut self, label: &str) {
+        self.jump_if_true(1, label);
+    }
+
+    // the next instruction
+    pub fn label(&mut self, label: &str) {
+        let previous = self
+            .labels
+            .insert(label.to_string(), self.code.len() as i64);
+        assert!(previous.is_none(), "label {} is used twice", label);
+    }
+
+    pub fn build(mut self) -> Vec<i64> {
+        // the variables go after the code, in the order they're first used
+        let mut variables: HashMap<String, i64> = HashMap::new();
+        let end = self.code.len() as i64;
+
+        for (at, arg) in self.uses {
+            self.code[at] = match arg {
+                Arg::Label(label) => match self.labels.get(&label) {
+                    Some(address) => *address,
+                    None => panic!("no label {}", label),
+                },
+                Arg::Var(name) => {
+                    let next = end + variables.len() as i64;
+                    *variables.entry(name).or_insert(next)
+                }
+                _ => unreachable!(),
+            };
+        }
+
+        return self.code;
+    }
+}
+
+#[test]
+fn test_assembler() {
+    // count down from the input, outputting each number on the way
+    let mut a = Assembler::default();
+    a.input("n");
+    a.label("loop");
+    a.output("n");
+    a.add("n", -1, "n");
+    a.jump_if_true("n", "loop");
+    a.halt();
+
+    let program = a.build();
+    assert_eq!(
+        program,
+        vec![3, 12, 4, 12, 1001, 12, -1, 12, 1005, 12, 2, 99]
+    );
+    assert_eq!(run_program(program, &[3]).unwrap().1, vec![3, 2, 1]);
+}
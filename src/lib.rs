@@ -0,0 +1,24 @@
+// explicit returns are the house style
+#![allow(clippy::needless_return)]
+
+pub mod answers;
+pub mod arcade;
+pub mod bench;
+pub mod controller;
+pub mod corridors;
+pub mod days;
+pub mod droid;
+pub mod grid;
+pub mod intcode;
+pub mod loader;
+pub mod maze;
+pub mod ocr;
+pub mod play;
+pub mod record;
+pub mod render;
+pub mod report;
+pub mod robot;
+pub mod solution;
+pub mod spread;
+pub mod verbosity;
+pub mod watch;
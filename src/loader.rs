@@ -0,0 +1,608 @@
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::str::FromStr;
+
+// Every puzzle input is either a list of integers (Intcode programs, module masses), a line of
+// digits (day 16) or a block of text lines (wires, mazes). This module reads all of them from a
+// file path or, if the path is "-", from stdin.
+
+// Compact binary images start with this magic, followed by a format version byte, a varint value
+// count and then one zigzag-encoded varint per value.
+const BINARY_MAGIC: &[u8; 4] = b"INTC";
+const BINARY_VERSION: u8 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Stdin,
+    File(String),
+}
+
+impl Source {
+    pub fn from_arg(arg: &str) -> Source {
+        if arg == "-" {
+            return Source::Stdin;
+        }
+        return Source::File(arg.to_string());
+    }
+
+    pub fn read_bytes(&self) -> Result<Vec<u8>, LoadError> {
+        let mut bytes: Vec<u8> = Vec::new();
+
+        match self {
+            Source::Stdin => {
+                io::stdin()
+                    .lock()
+                    .read_to_end(&mut bytes)
+                    .map_err(|e| LoadError::Io(self.clone(), e))?;
+            }
+            Source::File(path) => {
+                bytes = fs::read(path).map_err(|e| LoadError::Io(self.clone(), e))?;
+            }
+        }
+
+        return Ok(bytes);
+    }
+
+    pub fn read_string(&self) -> Result<String, LoadError> {
+        let bytes = self.read_bytes()?;
+        return String::from_utf8(bytes).map_err(|_| LoadError::NotUtf8(self.clone()));
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Source::Stdin => write!(f, "<stdin>"),
+            Source::File(path) => write!(f, "{}", path),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    // binary if the image magic is present, text otherwise
+    Auto,
+
+    // integers separated by commas and/or whitespace (so one-per-line works too)
+    Text,
+
+    // the compact binary image written by encode_binary
+    Binary,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    // the token is not a valid integer of the requested type
+    InvalidInteger(String),
+
+    // two separating commas with nothing between them
+    EmptyToken,
+
+    // a character that should have been a digit
+    InvalidDigit(char),
+
+    // binary image problems
+    BadMagic,
+    UnsupportedVersion(u8),
+    Truncated,
+    Overflow,
+    TrailingBytes,
+
+    // there was nothing to parse at all
+    Empty,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    // index of the offending token (value) in the input
+    pub index: usize,
+    pub kind: ParseErrorKind,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.kind {
+            ParseErrorKind::InvalidInteger(token) => {
+                write!(
+                    f,
+                    "token {}: {:?} is not a valid integer",
+                    self.index, token
+                )
+            }
+            ParseErrorKind::EmptyToken => write!(f, "token {}: empty token", self.index),
+            ParseErrorKind::InvalidDigit(c) => {
+                write!(f, "token {}: {:?} is not a digit", self.index, c)
+            }
+            ParseErrorKind::BadMagic => write!(f, "not a binary program image"),
+            ParseErrorKind::UnsupportedVersion(v) => {
+                write!(f, "unsupported binary image version {}", v)
+            }
+            ParseErrorKind::Truncated => write!(f, "token {}: image is truncated", self.index),
+            ParseErrorKind::Overflow => write!(f, "token {}: value overflows i64", self.index),
+            ParseErrorKind::TrailingBytes => {
+                write!(f, "token {}: trailing bytes after last value", self.index)
+            }
+            ParseErrorKind::Empty => write!(f, "input is empty"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug)]
+pub enum LoadError {
+    Io(Source, io::Error),
+    NotUtf8(Source),
+    Parse(Source, ParseError),
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LoadError::Io(source, e) => write!(f, "could not read {}: {}", source, e),
+            LoadError::NotUtf8(source) => write!(f, "{} is not valid UTF-8 text", source),
+            LoadError::Parse(source, e) => write!(f, "could not parse {}: {}", source, e),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+// Drop everything from a '#' to the end of the line.
+fn strip_comments(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+
+    for line in text.lines() {
+        match line.find('#') {
+            Some(i) => output.push_str(&line[..i]),
+            None => output.push_str(line),
+        }
+        output.push('\n');
+    }
+
+    return output;
+}
+
+// Split text into integer tokens. Commas and whitespace both separate tokens, so "1,2,3",
+// "1, 2, 3\n" and one value per line all parse the same. A trailing comma is tolerated, but an
+// empty token between two commas is reported because it would shift every following address.
+fn tokenize(text: &str) -> Result<Vec<String>, ParseError> {
+    let stripped = strip_comments(text);
+    let pieces: Vec<&str> = stripped.split(',').collect();
+    let mut tokens: Vec<String> = Vec::new();
+
+    for (i, piece) in pieces.iter().enumerate() {
+        let before = tokens.len();
+
+        for token in piece.split_whitespace() {
+            tokens.push(token.to_string());
+        }
+
+        let is_last = i == pieces.len() - 1;
+        if tokens.len() == before && !is_last {
+            return Err(ParseError {
+                index: tokens.len(),
+                kind: ParseErrorKind::EmptyToken,
+            });
+        }
+    }
+
+    return Ok(tokens);
+}
+
+pub fn parse_integers<T: FromStr>(text: &str) -> Result<Vec<T>, ParseError> {
+    let tokens = tokenize(text)?;
+
+    if tokens.is_empty() {
+        return Err(ParseError {
+            index: 0,
+            kind: ParseErrorKind::Empty,
+        });
+    }
+
+    let mut output: Vec<T> = Vec::with_capacity(tokens.len());
+
+    for (index, token) in tokens.into_iter().enumerate() {
+        match token.parse::<T>() {
+            Ok(v) => output.push(v),
+            Err(_) => {
+                return Err(ParseError {
+                    index,
+                    kind: ParseErrorKind::InvalidInteger(token),
+                });
+            }
+        }
+    }
+
+    return Ok(output);
+}
+
+#[test]
+fn test_parse_integers() {
+    let expected: Vec<i64> = vec![1, 0, 0, 3, 99];
+
+    assert_eq!(parse_integers::<i64>("1,0,0,3,99"), Ok(expected.clone()));
+    assert_eq!(parse_integers::<i64>("1,0,0,3,99\n"), Ok(expected.clone()));
+    assert_eq!(parse_integers::<i64>("1,0,0,3,99,\n"), Ok(expected.clone()));
+    assert_eq!(
+        parse_integers::<i64>(" 1, 0,\n0 ,3,\t99 "),
+        Ok(expected.clone())
+    );
+    assert_eq!(
+        parse_integers::<i64>("1\n0\n0\n3\n99\n"),
+        Ok(expected.clone())
+    );
+    assert_eq!(
+        parse_integers::<i64>("# gravity assist\n1,0,0,3 # add\n99\n"),
+        Ok(expected)
+    );
+
+    assert_eq!(
+        parse_integers::<i64>("104,1125899906842624,99"),
+        Ok(vec![104, 1125899906842624, 99])
+    );
+    assert_eq!(parse_integers::<i32>("-7,+3"), Ok(vec![-7, 3]));
+}
+
+#[test]
+fn test_parse_integers_errors() {
+    assert_eq!(
+        parse_integers::<i64>("1,2,x3,4"),
+        Err(ParseError {
+            index: 2,
+            kind: ParseErrorKind::InvalidInteger("x3".to_string()),
+        })
+    );
+    assert_eq!(
+        parse_integers::<i64>("1,2,,4"),
+        Err(ParseError {
+            index: 2,
+            kind: ParseErrorKind::EmptyToken,
+        })
+    );
+    assert_eq!(
+        parse_integers::<i64>("  \n# nothing here\n"),
+        Err(ParseError {
+            index: 0,
+            kind: ParseErrorKind::Empty,
+        })
+    );
+
+    // out of range for the requested type
+    assert_eq!(
+        parse_integers::<i32>("1,99999999999"),
+        Err(ParseError {
+            index: 1,
+            kind: ParseErrorKind::InvalidInteger("99999999999".to_string()),
+        })
+    );
+}
+
+// day 16 style input: a single run of digits, where each digit is one value
+pub fn parse_digits(text: &str) -> Result<Vec<i32>, ParseError> {
+    let mut output: Vec<i32> = Vec::new();
+
+    for c in text.trim().chars() {
+        match c.to_digit(10) {
+            Some(d) => output.push(d as i32),
+            None => {
+                return Err(ParseError {
+                    index: output.len(),
+                    kind: ParseErrorKind::InvalidDigit(c),
+                });
+            }
+        }
+    }
+
+    if output.is_empty() {
+        return Err(ParseError {
+            index: 0,
+            kind: ParseErrorKind::Empty,
+        });
+    }
+
+    return Ok(output);
+}
+
+#[test]
+fn test_parse_digits() {
+    assert_eq!(parse_digits("15243\n"), Ok(vec![1, 5, 2, 4, 3]));
+    assert_eq!(
+        parse_digits("152a43"),
+        Err(ParseError {
+            index: 3,
+            kind: ParseErrorKind::InvalidDigit('a'),
+        })
+    );
+    assert_eq!(
+        parse_digits("\n"),
+        Err(ParseError {
+            index: 0,
+            kind: ParseErrorKind::Empty,
+        })
+    );
+}
+
+fn push_varint(output: &mut Vec<u8>, mut v: u64) {
+    loop {
+        let byte = (v & 0x7f) as u8;
+        v >>= 7;
+
+        if v == 0 {
+            output.push(byte);
+            return;
+        }
+
+        output.push(byte | 0x80);
+    }
+}
+
+fn read_varint(bytes: &[u8], pos: &mut usize, index: usize) -> Result<u64, ParseError> {
+    let mut v: u64 = 0;
+    let mut shift = 0;
+
+    loop {
+        if *pos >= bytes.len() {
+            return Err(ParseError {
+                index,
+                kind: ParseErrorKind::Truncated,
+            });
+        }
+        if shift >= 64 {
+            return Err(ParseError {
+                index,
+                kind: ParseErrorKind::Overflow,
+            });
+        }
+
+        let byte = bytes[*pos];
+        *pos += 1;
+        // the tenth byte only has room for the top bit
+        if shift == 63 && byte & 0x7e != 0 {
+            return Err(ParseError {
+                index,
+                kind: ParseErrorKind::Overflow,
+            });
+        }
+
+        v |= ((byte & 0x7f) as u64) << shift;
+        shift += 7;
+
+        if byte & 0x80 == 0 {
+            return Ok(v);
+        }
+    }
+}
+
+// Write a program as a compact binary image. Zigzag encoding keeps small negative values (relative
+// base offsets, jump deltas) down to a single byte.
+pub fn encode_binary(program: &[i64]) -> Vec<u8> {
+    let mut output: Vec<u8> = Vec::with_capacity(program.len() * 2 + 8);
+
+    output.extend_from_slice(BINARY_MAGIC);
+    output.push(BINARY_VERSION);
+    push_varint(&mut output, program.len() as u64);
+
+    for v in program {
+        push_varint(&mut output, ((v << 1) ^ (v >> 63)) as u64);
+    }
+
+    return output;
+}
+
+pub fn is_binary(bytes: &[u8]) -> bool {
+    return bytes.starts_with(BINARY_MAGIC);
+}
+
+pub fn decode_binary(bytes: &[u8]) -> Result<Vec<i64>, ParseError> {
+    if !is_binary(bytes) {
+        return Err(ParseError {
+            index: 0,
+            kind: ParseErrorKind::BadMagic,
+        });
+    }
+
+    let mut pos = BINARY_MAGIC.len();
+
+    match bytes.get(pos) {
+        Some(&BINARY_VERSION) => {}
+        Some(v) => {
+            return Err(ParseError {
+                index: 0,
+                kind: ParseErrorKind::UnsupportedVersion(*v),
+            });
+        }
+        None => {
+            return Err(ParseError {
+                index: 0,
+                kind: ParseErrorKind::Truncated,
+            });
+        }
+    }
+    pos += 1;
+
+    let count = read_varint(bytes, &mut pos, 0)? as usize;
+
+    // don't trust the count for the allocation, each value takes at least one byte
+    let mut output: Vec<i64> = Vec::with_capacity(count.min(bytes.len()));
+
+    for index in 0..count {
+        let v = read_varint(bytes, &mut pos, index)?;
+        output.push(((v >> 1) as i64) ^ -((v & 1) as i64));
+    }
+
+    if pos != bytes.len() {
+        return Err(ParseError {
+            index: count,
+            kind: ParseErrorKind::TrailingBytes,
+        });
+    }
+
+    if output.is_empty() {
+        return Err(ParseError {
+            index: 0,
+            kind: ParseErrorKind::Empty,
+        });
+    }
+
+    return Ok(output);
+}
+
+#[test]
+fn test_binary_round_trip() {
+    let program: Vec<i64> = vec![
+        109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
+    ];
+
+    let image = encode_binary(&program);
+    assert!(is_binary(&image));
+    assert_eq!(decode_binary(&image), Ok(program.clone()));
+
+    // most values in a program are small, so the image beats the text form
+    let text: Vec<String> = program.iter().map(|v| v.to_string()).collect();
+    assert!(image.len() < text.join(",").len());
+
+    let extremes: Vec<i64> = vec![i64::MIN, -1, 0, 1, i64::MAX, 1125899906842624];
+    assert_eq!(decode_binary(&encode_binary(&extremes)), Ok(extremes));
+}
+
+#[test]
+fn test_binary_errors() {
+    let image = encode_binary(&[1, 2, 300, 4]);
+
+    assert_eq!(
+        decode_binary(b"1,2,3"),
+        Err(ParseError {
+            index: 0,
+            kind: ParseErrorKind::BadMagic,
+        })
+    );
+
+    // chop the last value in half: 300 takes two bytes
+    let mut truncated = image.clone();
+    truncated.truncate(image.len() - 2);
+    assert_eq!(
+        decode_binary(&truncated),
+        Err(ParseError {
+            index: 2,
+            kind: ParseErrorKind::Truncated,
+        })
+    );
+
+    let mut trailing = image.clone();
+    trailing.push(0);
+    assert_eq!(
+        decode_binary(&trailing),
+        Err(ParseError {
+            index: 4,
+            kind: ParseErrorKind::TrailingBytes,
+        })
+    );
+
+    // ten bytes of varint hold 70 bits, and anything past the 64th doesn't fit
+    let mut overflow = encode_binary(&[i64::MIN]);
+    assert_eq!(overflow.len(), 16);
+    overflow[15] = 0x7f;
+    assert_eq!(
+        decode_binary(&overflow),
+        Err(ParseError {
+            index: 0,
+            kind: ParseErrorKind::Overflow,
+        })
+    );
+
+    let mut version = image;
+    version[4] = 9;
+    assert_eq!(
+        decode_binary(&version),
+        Err(ParseError {
+            index: 0,
+            kind: ParseErrorKind::UnsupportedVersion(9),
+        })
+    );
+}
+
+pub fn parse_program(bytes: &[u8], format: Format) -> Result<Vec<i64>, ParseError> {
+    let binary = match format {
+        Format::Auto => is_binary(bytes),
+        Format::Text => false,
+        Format::Binary => true,
+    };
+
+    if binary {
+        return decode_binary(bytes);
+    }
+
+    // non-UTF-8 bytes can't be part of a valid token anyway
+    return parse_integers::<i64>(&String::from_utf8_lossy(bytes));
+}
+
+pub fn load_program_with_format(source: &Source, format: Format) -> Result<Vec<i64>, LoadError> {
+    let bytes = source.read_bytes()?;
+    return parse_program(&bytes, format).map_err(|e| LoadError::Parse(source.clone(), e));
+}
+
+// Load an Intcode program in any supported format.
+pub fn load_program(source: &Source) -> Result<Vec<i64>, LoadError> {
+    return load_program_with_format(source, Format::Auto);
+}
+
+pub fn load_integers<T: FromStr>(source: &Source) -> Result<Vec<T>, LoadError> {
+    let text = source.read_string()?;
+    return parse_integers::<T>(&text).map_err(|e| LoadError::Parse(source.clone(), e));
+}
+
+pub fn load_digits(source: &Source) -> Result<Vec<i32>, LoadError> {
+    let text = source.read_string()?;
+    return parse_digits(&text).map_err(|e| LoadError::Parse(source.clone(), e));
+}
+
+// Line-oriented inputs (wires, mazes) keep their text, minus any trailing blank lines.
+pub fn load_lines(source: &Source) -> Result<Vec<String>, LoadError> {
+    let text = source.read_string()?;
+    let mut lines: Vec<String> = text.lines().map(|s| s.trim_end().to_string()).collect();
+
+    while let Some(true) = lines.last().map(|s| s.is_empty()) {
+        lines.pop();
+    }
+
+    return Ok(lines);
+}
+
+#[test]
+fn test_load_from_file() {
+    let dir = std::env::temp_dir();
+
+    let text_path = dir.join(format!("loader-text-{}.input", std::process::id()));
+    fs::write(&text_path, "3,0,4,0,99\n").unwrap();
+
+    let binary_path = dir.join(format!("loader-binary-{}.input", std::process::id()));
+    fs::write(&binary_path, encode_binary(&[3, 0, 4, 0, 99])).unwrap();
+
+    let text_source = Source::from_arg(text_path.to_str().unwrap());
+    let binary_source = Source::from_arg(binary_path.to_str().unwrap());
+
+    assert_eq!(load_program(&text_source).unwrap(), vec![3, 0, 4, 0, 99]);
+    assert_eq!(load_program(&binary_source).unwrap(), vec![3, 0, 4, 0, 99]);
+    assert_eq!(
+        load_lines(&text_source).unwrap(),
+        vec!["3,0,4,0,99".to_string()]
+    );
+
+    // forcing text on a binary image reports the first bad token
+    match load_program_with_format(&binary_source, Format::Text) {
+        Err(LoadError::Parse(_, e)) => assert_eq!(e.index, 0),
+        x => panic!("expected a parse error, got {:?}", x),
+    }
+
+    fs::remove_file(text_path).unwrap();
+    fs::remove_file(binary_path).unwrap();
+
+    match load_program(&Source::from_arg("does-not-exist.input")) {
+        Err(LoadError::Io(Source::File(path), _)) => assert_eq!(path, "does-not-exist.input"),
+        x => panic!("expected an io error, got {:?}", x),
+    }
+
+    assert_eq!(Source::from_arg("-"), Source::Stdin);
+}
@@ -1,3 +1,1234 @@
+// explicit returns are the house style
+#![allow(clippy::needless_return)]
+
+use std::env;
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::process::exit;
+use std::time::Duration;
+
+use advent_of_code_2019::answers::{self, Answers, Verdict};
+use advent_of_code_2019::arcade::{self, Arcade, HighScores};
+use advent_of_code_2019::bench::{self, BenchRun, History};
+use advent_of_code_2019::controller;
+use advent_of_code_2019::days;
+use advent_of_code_2019::droid::{self, Explorer, Fleet, MockDroid};
+use advent_of_code_2019::info;
+use advent_of_code_2019::intcode::{run_intcode_computer, Computer};
+use advent_of_code_2019::loader::{self, Source};
+use advent_of_code_2019::maze::{GraphFormat, Map};
+use advent_of_code_2019::play::{self, Game};
+use advent_of_code_2019::record::{self, Recorder};
+use advent_of_code_2019::render::{self, Pixel};
+use advent_of_code_2019::report::{self, DayReport};
+use advent_of_code_2019::solution::{Answer, Solution};
+use advent_of_code_2019::verbosity;
+use advent_of_code_2019::watch::Viewer;
+use serde::Serialize;
+
+const USAGE: &str = "usage:
+    aoc [options] run <day> [--part 1|2] [--input <path>|-]
+    aoc [options] list
+    aoc [options] all
+    aoc [options] verify [<day>...] [--answers <path>] [--record]
+    aoc [options] bench [<day>...] [--runs <n>] [--history <path>] [--threshold <percent>] [--no-save]
+    aoc [options] render <day> [--as ascii|ansi|ppm|png|svg] [--scale <n>] [--output <dir>] [--input <path>|-]
+    aoc [options] watch <day> [--delay <ms>] [--input <path>|-]
+    aoc [options] record <day> [--as gif|apng|cast|ppm] [--every <n>] [--delay <ms>] [--scale <n>] [--output <path>] [--input <path>|-]
+    aoc [options] arcade [greedy|predictive|replay...] [--ticks <n>] [--timeline <path>] [--input <path>|-]
+    aoc [options] explore [dfs|bfs|wall...] [--droids <n>] [--save <path>] [--maze <path>] [--input <path>|-]
+    aoc [options] play [--tick <ms>] [--load <path>] [--save <path>] [--scores <path>] [--input <path>|-]
+
+options:
+    --format text|json  how run, all, arcade and explore print their results (default text)
+    --artifacts         also produce extras such as rendered grids and paths
+    -v, -vv             progress, or every step, on stderr";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OutputFormat {
+    Text,
+    Json,
+}
+
+struct Options {
+    format: OutputFormat,
+    artifacts: bool,
+}
+
+fn usage_error(message: &str) -> ! {
+    eprintln!("{}\n\n{}", message, USAGE);
+    exit(2);
+}
+
+// Options can go anywhere on the command line. They are taken out, the rest is returned.
+fn take_options(args: Vec<String>) -> (Options, Vec<String>) {
+    let mut options = Options {
+        format: OutputFormat::Text,
+        artifacts: false,
+    };
+    let mut rest: Vec<String> = Vec::new();
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => match args.next().as_deref() {
+                Some("text") => options.format = OutputFormat::Text,
+                Some("json") => options.format = OutputFormat::Json,
+                _ => usage_error("--format must be text or json"),
+            },
+            "--artifacts" => options.artifacts = true,
+            "-v" | "--verbose" => verbosity::set_level(verbosity::level() + 1),
+            "-vv" => verbosity::set_level(verbosity::level() + 2),
+            _ => rest.push(arg),
+        }
+    }
+
+    return (options, rest);
+}
+
+fn print_answer(part: u32, answer: &Answer) {
+    let text = answer.to_string();
+
+    // pictures go underneath the label so they line up
+    if text.contains('\n') {
+        println!("  part {}:\n{}", part, text.trim_end());
+    } else {
+        println!("  part {}: {}", part, text);
+    }
+}
+
+fn print_report(report: &DayReport) {
+    println!("Day {}: {}", report.day, report.title);
+
+    if let Some(e) = &report.error {
+        eprintln!("  {}", e);
+        return;
+    }
+
+    for part in 1..=2 {
+        if let Some(answer) = report.answers.get(&format!("part{}", part)) {
+            print_answer(part, answer);
+        }
+    }
+
+    for artifact in &report.artifacts {
+        println!("  {}:\n{}", artifact.name(), artifact);
+    }
+
+    for (phase, ns) in &report.timings_ns {
+        info!("  {} took {}", phase, bench::format_duration(*ns));
+    }
+}
+
+fn print_json<T: Serialize>(value: &T) {
+    match serde_json::to_string_pretty(value) {
+        Ok(text) => println!("{}", text),
+        Err(e) => {
+            eprintln!("could not write JSON: {}", e);
+            exit(1);
+        }
+    }
+}
+
+fn run_solution(
+    solution: &mut dyn Solution,
+    parts: &[u32],
+    input: Option<&str>,
+    options: &Options,
+) -> DayReport {
+    let source = day_source(solution, input);
+    let report = report::run(solution, &source, parts, options.artifacts);
+
+    if options.format == OutputFormat::Text {
+        print_report(&report);
+    }
+
+    return report;
+}
+
+fn parse_day(arg: &str) -> u32 {
+    match arg.parse::<u32>() {
+        Ok(day) => day,
+        Err(_) => usage_error(&format!("{:?} is not a day", arg)),
+    }
+}
+
+// the day, given to a command that works on one
+fn day_arg(day: &mut Option<u32>, arg: &str) {
+    if day.is_some() {
+        usage_error(&format!("unexpected argument {:?}", arg));
+    }
+    *day = Some(parse_day(arg));
+}
+
+fn which_day(day: Option<u32>) -> u32 {
+    match day {
+        Some(day) => return day,
+        None => usage_error("which day?"),
+    }
+}
+
+// the path after --input, leaving i on it
+fn input_arg(args: &[String], i: &mut usize) -> String {
+    *i += 1;
+    match args.get(*i) {
+        Some(path) => return path.clone(),
+        None => usage_error("--input needs a path, or - for stdin"),
+    }
+}
+
+fn solution_for(day: u32) -> Box<dyn Solution> {
+    match days::get(day) {
+        Some(solution) => return solution,
+        None => {
+            eprintln!("day {} is not solved yet", day);
+            exit(1);
+        }
+    }
+}
+
+// where a day's input comes from: --input if it was given, and the day's usual place if not
+fn day_source(solution: &dyn Solution, input: Option<&str>) -> Source {
+    match input {
+        Some(arg) => return Source::from_arg(arg),
+        None => return Source::from_arg(&solution.default_input()),
+    }
+}
+
+// a day's solution with its input parsed, for the commands that do something other than run it
+fn parsed_solution(day: u32, input: Option<&str>) -> Box<dyn Solution> {
+    let mut solution = solution_for(day);
+
+    if let Err(e) = solution.parse(&day_source(solution.as_ref(), input)) {
+        eprintln!("{}", e);
+        exit(1);
+    }
+
+    return solution;
+}
+
+fn run(args: &[String], options: &Options) {
+    let mut day: Option<u32> = None;
+    let mut parts: Vec<u32> = vec![1, 2];
+    let mut input: Option<String> = None;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--part" => {
+                i += 1;
+                match args.get(i).map(|s| s.as_str()) {
+                    Some("1") => parts = vec![1],
+                    Some("2") => parts = vec![2],
+                    _ => usage_error("--part must be 1 or 2"),
+                }
+            }
+            "--input" => input = Some(input_arg(args, &mut i)),
+            arg => day_arg(&mut day, arg),
+        }
+        i += 1;
+    }
+
+    let day = which_day(day);
+
+    let mut solution = solution_for(day);
+
+    let report = run_solution(solution.as_mut(), &parts, input.as_deref(), options);
+
+    if options.format == OutputFormat::Json {
+        print_json(&report);
+    }
+
+    if report.error.is_some() {
+        exit(1);
+    }
+}
+
+fn list() {
+    for solution in days::all() {
+        println!(
+            "{:>2}  {:<40} {}",
+            solution.day(),
+            solution.title(),
+            solution.default_input()
+        );
+    }
+}
+
+#[derive(Serialize)]
+struct AllReports<'a> {
+    days: &'a [DayReport],
+}
+
+fn all(options: &Options) {
+    let mut reports: Vec<DayReport> = Vec::new();
+
+    for mut solution in days::all() {
+        reports.push(run_solution(solution.as_mut(), &[1, 2], None, options));
+    }
+
+    if options.format == OutputFormat::Json {
+        print_json(&AllReports { days: &reports });
+    }
+
+    let failed = reports.iter().filter(|r| r.error.is_some()).count();
+    if failed > 0 {
+        eprintln!("{} day(s) could not be run", failed);
+        exit(1);
+    }
+}
+
+#[derive(Default)]
+struct Tally {
+    passed: usize,
+    changed: usize,
+    failed: usize,
+    new: usize,
+}
+
+// Run every part against its default input and compare with the recorded answers. With record
+// set, answers for inputs that have none yet are added to the file.
+fn verify_solution(
+    solution: &mut dyn Solution,
+    answers: &mut Answers,
+    record: bool,
+    tally: &mut Tally,
+) {
+    let day = solution.day();
+    let source = Source::from_arg(&solution.default_input());
+
+    let hash = match source.read_bytes() {
+        Ok(bytes) => answers::input_hash(&bytes),
+        Err(e) => {
+            println!("day {:>2}:        FAIL {}", day, e);
+            tally.failed += 2;
+            return;
+        }
+    };
+
+    if let Err(e) = solution.parse(&source) {
+        println!("day {:>2}:        FAIL {}", day, e);
+        tally.failed += 2;
+        return;
+    }
+
+    for part in 1..=2 {
+        let label = format!("day {:>2} part {}:", day, part);
+
+        // a panicking solution is a failure to report, not a reason to stop checking the others
+        let answer = match panic::catch_unwind(AssertUnwindSafe(|| solution.part(part))) {
+            Ok(answer) => answer,
+            Err(_) => {
+                println!("{} FAIL panicked", label);
+                tally.failed += 1;
+                continue;
+            }
+        };
+
+        match answers.check(day, part, hash, &answer) {
+            Verdict::Pass => {
+                println!("{} pass", label);
+                tally.passed += 1;
+            }
+            Verdict::Changed { expected } => {
+                println!("{} CHANGED", label);
+                println!(
+                    "    expected: {}",
+                    expected.replace('\n', "\n              ")
+                );
+                println!(
+                    "    got:      {}",
+                    answer.to_string().replace('\n', "\n              ")
+                );
+                tally.changed += 1;
+            }
+            Verdict::New => {
+                if record {
+                    answers.record(day, part, hash, &answer);
+                    println!("{} recorded", label);
+                } else {
+                    println!("{} new (no recorded answer for this input)", label);
+                }
+                tally.new += 1;
+            }
+        }
+    }
+}
+
+fn verify(args: &[String]) {
+    let mut path = answers::DEFAULT_PATH.to_string();
+    let mut record = false;
+    let mut solutions: Vec<Box<dyn Solution>> = Vec::new();
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--answers" => {
+                i += 1;
+                match args.get(i) {
+                    Some(p) => path = p.clone(),
+                    None => usage_error("--answers needs a path"),
+                }
+            }
+            "--record" => record = true,
+            arg => solutions.push(solution_for(parse_day(arg))),
+        }
+        i += 1;
+    }
+
+    if solutions.is_empty() {
+        solutions = days::all();
+    }
+
+    let mut answers = match Answers::load(&path) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("{}", e);
+            exit(1);
+        }
+    };
+
+    let mut tally = Tally::default();
+
+    for mut solution in solutions {
+        verify_solution(solution.as_mut(), &mut answers, record, &mut tally);
+    }
+
+    println!(
+        "{} passed, {} changed, {} failed, {} new",
+        tally.passed, tally.changed, tally.failed, tally.new
+    );
+
+    if record && tally.new > 0 {
+        if let Err(e) = answers.save(&path) {
+            eprintln!("{}", e);
+            exit(1);
+        }
+        println!("saved {} answer(s) to {}", answers.len(), path);
+    }
+
+    if tally.changed > 0 || tally.failed > 0 {
+        exit(1);
+    }
+}
+
+fn bench(args: &[String]) {
+    let mut path = bench::DEFAULT_PATH.to_string();
+    let mut runs: usize = 5;
+    let mut threshold: f64 = 0.25;
+    let mut save = true;
+    let mut solutions: Vec<Box<dyn Solution>> = Vec::new();
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--runs" => {
+                i += 1;
+                match args.get(i).and_then(|s| s.parse::<usize>().ok()) {
+                    Some(n) if n > 0 => runs = n,
+                    _ => usage_error("--runs needs a positive number"),
+                }
+            }
+            "--history" => {
+                i += 1;
+                match args.get(i) {
+                    Some(p) => path = p.clone(),
+                    None => usage_error("--history needs a path"),
+                }
+            }
+            "--threshold" => {
+                i += 1;
+                match args.get(i).and_then(|s| s.parse::<f64>().ok()) {
+                    Some(percent) if percent >= 0.0 => threshold = percent / 100.0,
+                    _ => usage_error("--threshold needs a percentage"),
+                }
+            }
+            "--no-save" => save = false,
+            arg => solutions.push(solution_for(parse_day(arg))),
+        }
+        i += 1;
+    }
+
+    if solutions.is_empty() {
+        solutions = days::all();
+    }
+
+    let mut history = match History::load(&path) {
+        Ok(history) => history,
+        Err(e) => {
+            eprintln!("{}", e);
+            exit(1);
+        }
+    };
+
+    let mut current = BenchRun::new(runs);
+    let mut regressed = 0;
+
+    println!(
+        "day  {:<36} {:>19} {:>19} {:>19}",
+        "(median / min)", "parse", "part 1", "part 2"
+    );
+
+    for mut solution in solutions {
+        let source = Source::from_arg(&solution.default_input());
+
+        // as with verify, a panicking day is reported and the rest are still timed
+        let day = solution.day();
+        let timed = panic::catch_unwind(AssertUnwindSafe(|| {
+            bench::bench_solution(solution.as_mut(), &source, runs)
+        }));
+        let timing = match timed {
+            Ok(Ok(timing)) => timing,
+            Ok(Err(e)) => {
+                println!("{:>3}  {}", day, e);
+                continue;
+            }
+            Err(_) => {
+                println!("{:>3}  panicked", day);
+                continue;
+            }
+        };
+
+        let columns: Vec<String> = timing
+            .phases()
+            .iter()
+            .map(|(_, phase)| {
+                format!(
+                    "{} / {}",
+                    bench::format_duration(phase.median_ns),
+                    bench::format_duration(phase.min_ns)
+                )
+            })
+            .collect();
+
+        println!(
+            "{:>3}  {:<36} {:>19} {:>19} {:>19}",
+            timing.day, timing.title, columns[0], columns[1], columns[2]
+        );
+
+        if let Some(previous) = history.previous(timing.day) {
+            for regression in bench::regressions(previous, &timing, threshold) {
+                println!(
+                    "     REGRESSION {}: {} -> {}",
+                    regression.phase,
+                    bench::format_duration(regression.previous_ns),
+                    bench::format_duration(regression.current_ns)
+                );
+                regressed += 1;
+            }
+        }
+
+        current.days.push(timing);
+    }
+
+    if save && !current.days.is_empty() {
+        history.runs.push(current);
+        if let Err(e) = history.save(&path) {
+            eprintln!("{}", e);
+            exit(1);
+        }
+        println!("saved to {}", path);
+    }
+
+    if regressed > 0 {
+        println!("{} phase(s) slower than the previous run", regressed);
+        exit(1);
+    }
+}
+
+// Text renderings go to stdout, images are saved as dayNN-<picture>.<extension> in the output
+// directory.
+fn render(args: &[String]) {
+    let mut day: Option<u32> = None;
+    let mut format = "ascii".to_string();
+    let mut scale: usize = 8;
+    let mut output = ".".to_string();
+    let mut input: Option<String> = None;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--as" => {
+                i += 1;
+                match args.get(i) {
+                    Some(name) if render::RENDERERS.contains(&name.as_str()) => {
+                        format = name.clone()
+                    }
+                    _ => usage_error(&format!(
+                        "--as must be one of {}",
+                        render::RENDERERS.join(", ")
+                    )),
+                }
+            }
+            "--scale" => {
+                i += 1;
+                match args.get(i).and_then(|s| s.parse::<usize>().ok()) {
+                    Some(n) if n > 0 => scale = n,
+                    _ => usage_error("--scale needs a positive number"),
+                }
+            }
+            "--output" => {
+                i += 1;
+                match args.get(i) {
+                    Some(dir) => output = dir.clone(),
+                    None => usage_error("--output needs a directory"),
+                }
+            }
+            "--input" => input = Some(input_arg(args, &mut i)),
+            arg => day_arg(&mut day, arg),
+        }
+        i += 1;
+    }
+
+    let day = which_day(day);
+
+    let solution = parsed_solution(day, input.as_deref());
+
+    let pictures = solution.pictures();
+    if pictures.is_empty() {
+        eprintln!("day {} has nothing to render", day);
+        exit(1);
+    }
+
+    let renderer = render::renderer::<Pixel>(&format, scale).expect("checked above");
+
+    for picture in pictures {
+        let bytes = picture.render(renderer.as_ref());
+
+        if format == "ascii" || format == "ansi" {
+            println!("{}:", picture.name);
+            let _ = io::stdout().write_all(&bytes);
+            continue;
+        }
+
+        let file_name = format!(
+            "day{:02}-{}.{}",
+            day,
+            picture.name.replace(' ', "-"),
+            renderer.extension()
+        );
+        let path = Path::new(&output).join(file_name);
+
+        if let Err(e) = fs::write(&path, &bytes) {
+            eprintln!("could not write {}: {}", path.display(), e);
+            exit(1);
+        }
+        println!("wrote {}", path.display());
+    }
+}
+
+// Plays the day's simulation full screen, for as long as it runs or until it's quit.
+fn watch(args: &[String]) {
+    let mut day: Option<u32> = None;
+    let mut delay = Duration::from_millis(50);
+    let mut input: Option<String> = None;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--delay" => {
+                i += 1;
+                match args.get(i).and_then(|s| s.parse::<u64>().ok()) {
+                    Some(ms) => delay = Duration::from_millis(ms),
+                    _ => usage_error("--delay needs a number of milliseconds"),
+                }
+            }
+            "--input" => input = Some(input_arg(args, &mut i)),
+            arg => day_arg(&mut day, arg),
+        }
+        i += 1;
+    }
+
+    let day = which_day(day);
+
+    if !io::stdout().is_terminal() {
+        eprintln!("watch needs a terminal, try record instead");
+        exit(1);
+    }
+
+    let solution = parsed_solution(day, input.as_deref());
+
+    let steps = {
+        let mut viewer = match Viewer::new(delay) {
+            Ok(viewer) => viewer,
+            Err(e) => {
+                eprintln!("could not set up the terminal: {}", e);
+                exit(1);
+            }
+        };
+
+        solution.watch(&mut viewer);
+        if viewer.steps() > 0 {
+            let _ = viewer.finish();
+        }
+        viewer.steps()
+    };
+
+    if steps == 0 {
+        eprintln!("day {} has nothing to watch", day);
+        exit(1);
+    }
+}
+
+// Saves an animation of the day's simulation. Without --delay, frames are shown for as long as they
+// took to compute. ppm writes a directory of numbered frames instead of a single file.
+fn record(args: &[String]) {
+    let mut day: Option<u32> = None;
+    let mut format = "gif".to_string();
+    let mut every: usize = 1;
+    let mut delay: Option<Duration> = None;
+    let mut scale: usize = 4;
+    let mut output: Option<String> = None;
+    let mut input: Option<String> = None;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--as" => {
+                i += 1;
+                match args.get(i) {
+                    Some(name) if record::FORMATS.contains(&name.as_str()) => format = name.clone(),
+                    _ => usage_error(&format!(
+                        "--as must be one of {}",
+                        record::FORMATS.join(", ")
+                    )),
+                }
+            }
+            "--every" => {
+                i += 1;
+                match args.get(i).and_then(|s| s.parse::<usize>().ok()) {
+                    Some(n) if n > 0 => every = n,
+                    _ => usage_error("--every needs a positive number"),
+                }
+            }
+            "--delay" => {
+                i += 1;
+                match args.get(i).and_then(|s| s.parse::<u64>().ok()) {
+                    Some(ms) => delay = Some(Duration::from_millis(ms)),
+                    _ => usage_error("--delay needs a number of milliseconds"),
+                }
+            }
+            "--scale" => {
+                i += 1;
+                match args.get(i).and_then(|s| s.parse::<usize>().ok()) {
+                    Some(n) if n > 0 => scale = n,
+                    _ => usage_error("--scale needs a positive number"),
+                }
+            }
+            "--output" => {
+                i += 1;
+                match args.get(i) {
+                    Some(path) => output = Some(path.clone()),
+                    None => usage_error("--output needs a path"),
+                }
+            }
+            "--input" => input = Some(input_arg(args, &mut i)),
+            arg => day_arg(&mut day, arg),
+        }
+        i += 1;
+    }
+
+    let day = which_day(day);
+
+    let solution = parsed_solution(day, input.as_deref());
+
+    let mut recorder = Recorder::new(every);
+    solution.watch(&mut recorder);
+
+    if recorder.is_empty() {
+        eprintln!("day {} has nothing to record", day);
+        exit(1);
+    }
+
+    let output = match output {
+        Some(path) => path,
+        None if format == "ppm" => format!("day{:02}-frames", day),
+        None if format == "apng" => format!("day{:02}.png", day),
+        None => format!("day{:02}.{}", day, format),
+    };
+
+    let written = match format.as_str() {
+        "gif" => record::gif(&recorder, scale, delay).map(|bytes| fs::write(&output, bytes)),
+        "apng" => record::apng(&recorder, scale, delay).map(|bytes| fs::write(&output, bytes)),
+        "cast" => record::cast(&recorder, delay).map(|text| fs::write(&output, text)),
+        _ => record::ppm_frames(&recorder, scale).map(|(images, index)| {
+            fs::create_dir_all(&output)?;
+            for (n, image) in images.iter().enumerate() {
+                fs::write(Path::new(&output).join(format!("{:05}.ppm", n)), image)?;
+            }
+            fs::write(Path::new(&output).join("frames.tsv"), index)
+        }),
+    };
+
+    match written {
+        Ok(Ok(())) => println!("wrote {} frames to {}", recorder.len(), output),
+        Ok(Err(e)) => {
+            eprintln!("could not write {}: {}", output, e);
+            exit(1);
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            exit(1);
+        }
+    }
+}
+
+// a day's program, from --input or the usual place
+fn day_program(day: u32, input: Option<String>) -> Vec<i64> {
+    let source = day_source(solution_for(day).as_ref(), input.as_deref());
+
+    match loader::load_program(&source) {
+        Ok(program) => return program,
+        Err(e) => {
+            eprintln!("{}", e);
+            exit(1);
+        }
+    }
+}
+
+// Plays day 13's arcade game once with each controller asked for (all of them if none are), as
+// fast as the machine runs, and reports how each did. --timeline also writes what happened every
+// tick as CSV.
+fn arcade(args: &[String], options: &Options) {
+    let mut names: Vec<String> = Vec::new();
+    let mut max_ticks = controller::MAX_TICKS;
+    let mut timeline: Option<String> = None;
+    let mut input: Option<String> = None;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--ticks" => {
+                i += 1;
+                match args.get(i).and_then(|s| s.parse::<u64>().ok()) {
+                    Some(n) => max_ticks = n,
+                    _ => usage_error("--ticks needs a number"),
+                }
+            }
+            "--timeline" => {
+                i += 1;
+                match args.get(i) {
+                    Some(path) => timeline = Some(path.clone()),
+                    None => usage_error("--timeline needs a path"),
+                }
+            }
+            "--input" => input = Some(input_arg(args, &mut i)),
+            name => {
+                if controller::controller(name).is_none() {
+                    usage_error(&format!(
+                        "unknown controller {:?}, try one of {}",
+                        name,
+                        controller::NAMES.join(", ")
+                    ));
+                }
+                names.push(name.to_string());
+            }
+        }
+        i += 1;
+    }
+
+    if names.is_empty() {
+        names = controller::NAMES.iter().map(|s| s.to_string()).collect();
+    }
+
+    let program = day_program(13, input);
+
+    let mut reports: Vec<controller::Report> = Vec::new();
+    for name in names {
+        let mut controller = controller::controller(&name).unwrap();
+        let arcade = Arcade::new(&program, true);
+        reports.push(controller::run(
+            arcade,
+            controller.as_mut(),
+            max_ticks,
+            |_| true,
+        ));
+    }
+
+    if let Some(path) = timeline {
+        let csv: String = reports
+            .iter()
+            .enumerate()
+            .map(|(n, report)| {
+                let csv = report.timeline_csv();
+                // one header for the lot
+                if n == 0 {
+                    return csv;
+                }
+                return csv
+                    .split_once('\n')
+                    .map(|(_, rows)| rows.to_string())
+                    .unwrap_or_default();
+            })
+            .collect();
+
+        if let Err(e) = fs::write(&path, csv) {
+            eprintln!("could not write {}: {}", path, e);
+            exit(1);
+        }
+        eprintln!("wrote {}", path);
+    }
+
+    // part 1 is the blocks on the screen of a game with no quarters in, which should be the same
+    // as the blocks at the start of one that's played
+    let part1 = Arcade::new(&program, false).blocks();
+    let mismatched: Vec<&controller::Report> = reports
+        .iter()
+        .filter(|r| r.initial_blocks != part1)
+        .collect();
+
+    if options.format == OutputFormat::Json {
+        print_json(&reports);
+    } else {
+        print_arcade_reports(&reports, max_ticks);
+        println!();
+        println!("part 1: {} blocks at the start", part1);
+    }
+
+    for report in mismatched {
+        eprintln!(
+            "{} started with {} blocks, not {}",
+            report.controller, report.initial_blocks, part1
+        );
+    }
+}
+
+fn print_arcade_reports(reports: &[controller::Report], max_ticks: u64) {
+    println!(
+        "{:<12} {:>8} {:>8} {:>8} {:>10} {:>8} {:>16}  result",
+        "controller", "score", "start", "left", "ticks", "moves", "points/block"
+    );
+    for report in reports {
+        let points = match report.points_per_block() {
+            Some((min, max, mean)) => format!("{}..{} ~{:.1}", min, max, mean),
+            None => "-".to_string(),
+        };
+        let result = if report.won {
+            "won"
+        } else if report.ticks >= max_ticks {
+            "out of ticks"
+        } else {
+            "lost"
+        };
+        println!(
+            "{:<12} {:>8} {:>8} {:>8} {:>10} {:>8} {:>16}  {}",
+            report.controller,
+            report.score,
+            report.initial_blocks,
+            report.blocks,
+            report.ticks,
+            report.paddle_moves,
+            points,
+            result
+        );
+    }
+}
+
+// Explores day 15's area with each strategy asked for (all of them if none are), and reports how
+// many moves each sent. --save writes the map the first to explore everywhere made, which can be
+// given to run 15 in place of the program; saved as .dot, .graphml or .json it's the map's graph
+// instead. --maze explores a map drawn the same way instead, with a droid that pretends to be the
+// program. --droids also sends out that many droids together, and compares them with one doing the
+// same on its own.
+fn explore(args: &[String], options: &Options) {
+    let mut names: Vec<String> = Vec::new();
+    let mut droids: Option<usize> = None;
+    let mut save: Option<String> = None;
+    let mut maze: Option<String> = None;
+    let mut input: Option<String> = None;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--save" => {
+                i += 1;
+                match args.get(i) {
+                    Some(path) => save = Some(path.clone()),
+                    None => usage_error("--save needs a path"),
+                }
+            }
+            "--droids" => {
+                i += 1;
+                match args.get(i).and_then(|s| s.parse::<usize>().ok()) {
+                    Some(n) if n > 0 => droids = Some(n),
+                    _ => usage_error("--droids needs a number, at least 1"),
+                }
+            }
+            "--maze" => {
+                i += 1;
+                match args.get(i) {
+                    Some(path) => maze = Some(path.clone()),
+                    None => usage_error("--maze needs a path"),
+                }
+            }
+            "--input" => input = Some(input_arg(args, &mut i)),
+            name => {
+                if droid::strategy(name).is_none() {
+                    usage_error(&format!(
+                        "unknown strategy {:?}, try one of {}",
+                        name,
+                        droid::STRATEGIES.join(", ")
+                    ));
+                }
+                names.push(name.to_string());
+            }
+        }
+        i += 1;
+    }
+
+    if names.is_empty() {
+        names = droid::STRATEGIES.iter().map(|s| s.to_string()).collect();
+    }
+
+    let maze = maze.map(|path| match Map::load(&path) {
+        Ok(maze) => maze,
+        Err(e) => {
+            eprintln!("{}", e);
+            exit(1);
+        }
+    });
+    let program = match maze {
+        Some(_) => Vec::new(),
+        None => day_program(15, input),
+    };
+
+    let mut reports: Vec<droid::Report> = Vec::new();
+    let mut explored: Option<Map> = None;
+    for name in names {
+        let (report, map) = match &maze {
+            Some(maze) => explore_with(MockDroid::new(maze), &name),
+            None => explore_with(run_intcode_computer(name.clone(), program.clone()), &name),
+        };
+        reports.push(report);
+
+        if explored.is_none() && map.is_some() {
+            explored = map;
+        }
+    }
+
+    let mut summary: Option<String> = None;
+    if let Some(n) = droids {
+        let (report, line) = match &maze {
+            Some(maze) => explore_fleet(|| MockDroid::new(maze), n),
+            None => explore_fleet(
+                || run_intcode_computer("fleet".to_string(), program.clone()),
+                n,
+            ),
+        };
+        reports.push(report);
+        summary = Some(line);
+    }
+
+    if let Some(path) = save {
+        let map = match explored {
+            Some(map) => map,
+            None => {
+                eprintln!("nothing explored everywhere, so there's no map to save");
+                exit(1);
+            }
+        };
+        let saved = match GraphFormat::from_extension(&path) {
+            Some(format) => fs::write(&path, map.export(format))
+                .map_err(|e| format!("could not write {}: {}", path, e)),
+            None => map.save(&path).map_err(|e| e.to_string()),
+        };
+        if let Err(e) = saved {
+            eprintln!("{}", e);
+            exit(1);
+        }
+        eprintln!("wrote {}", path);
+    }
+
+    if options.format == OutputFormat::Json {
+        print_json(&reports);
+        return;
+    }
+
+    println!(
+        "{:<12} {:>8} {:>8} {:>10} {:>10}  result",
+        "strategy", "moves", "open", "oxygen", "distance"
+    );
+    for report in reports {
+        let oxygen = match report.oxygen {
+            Some((x, y)) => format!("{},{}", x, y),
+            None => "-".to_string(),
+        };
+        let distance = match report.distance {
+            Some(distance) => distance.to_string(),
+            None => "-".to_string(),
+        };
+        let result = if report.complete {
+            "explored"
+        } else {
+            "unexplored left"
+        };
+        println!(
+            "{:<12} {:>8} {:>8} {:>10} {:>10}  {}",
+            report.strategy, report.moves, report.open, oxygen, distance, result
+        );
+    }
+
+    if let Some(summary) = summary {
+        println!();
+        println!("{}", summary);
+    }
+}
+
+// How a fleet of droids did, and a line comparing it with one droid coordinated the same way.
+fn explore_fleet<C: Computer>(droid: impl Fn() -> C, n: usize) -> (droid::Report, String) {
+    let mut alone = Fleet::new(vec![droid()]);
+    alone.explore(|_| true);
+
+    let mut fleet = Fleet::new((0..n).map(|_| droid()).collect());
+    fleet.explore(|_| true);
+
+    let moves: Vec<String> = fleet.moves().iter().map(|m| m.to_string()).collect();
+    let mut line = format!(
+        "{} droids: {} moves ({}) in {} rounds, against {} moves in {} rounds for one",
+        n,
+        fleet.total_moves(),
+        moves.join(", "),
+        fleet.rounds,
+        alone.total_moves(),
+        alone.rounds
+    );
+    if let Some((droid, round)) = fleet.found.first() {
+        line.push_str(&format!(
+            "\ndroid {} found the oxygen system in round {}",
+            droid, round
+        ));
+    }
+
+    return (fleet.report(), line);
+}
+
+// how one strategy did, and the map if it explored everywhere
+fn explore_with<C: Computer>(droid: C, name: &str) -> (droid::Report, Option<Map>) {
+    let mut strategy = droid::strategy(name).unwrap();
+    let mut explorer = Explorer::new(droid);
+    explorer.explore(strategy.as_mut(), |_| true);
+
+    let report = explorer.report(name);
+    if explorer.complete() {
+        return (report, Some(explorer.chart.map));
+    }
+    return (report, None);
+}
+
+// Plays day 13's arcade game by hand. s saves the game and l loads it again; a game played to the
+// end goes in the high score table.
+fn play(args: &[String]) {
+    let mut tick = Duration::from_millis(150);
+    let mut load: Option<String> = None;
+    let mut save = play::SAVE_PATH.to_string();
+    let mut scores = arcade::HIGH_SCORES_PATH.to_string();
+    let mut input: Option<String> = None;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--tick" => {
+                i += 1;
+                match args.get(i).and_then(|s| s.parse::<u64>().ok()) {
+                    Some(ms) if ms > 0 => tick = Duration::from_millis(ms),
+                    _ => usage_error("--tick needs a number of milliseconds"),
+                }
+            }
+            "--load" => {
+                i += 1;
+                match args.get(i) {
+                    Some(path) => load = Some(path.clone()),
+                    None => usage_error("--load needs a path"),
+                }
+            }
+            "--save" => {
+                i += 1;
+                match args.get(i) {
+                    Some(path) => save = path.clone(),
+                    None => usage_error("--save needs a path"),
+                }
+            }
+            "--scores" => {
+                i += 1;
+                match args.get(i) {
+                    Some(path) => scores = path.clone(),
+                    None => usage_error("--scores needs a path"),
+                }
+            }
+            "--input" => input = Some(input_arg(args, &mut i)),
+            arg => usage_error(&format!("unexpected argument {:?}", arg)),
+        }
+        i += 1;
+    }
+
+    if !io::stdout().is_terminal() {
+        eprintln!("play needs a terminal");
+        exit(1);
+    }
+
+    let arcade = match load {
+        Some(path) => Arcade::load(&path),
+        None => Ok(Arcade::new(&day_program(13, input), true)),
+    };
+    let arcade = match arcade {
+        Ok(arcade) => arcade,
+        Err(e) => {
+            eprintln!("{}", e);
+            exit(1);
+        }
+    };
+
+    let mut table = match HighScores::load(&scores) {
+        Ok(table) => table,
+        Err(e) => {
+            eprintln!("{}", e);
+            exit(1);
+        }
+    };
+
+    let mut game = Game::new(arcade, tick, &save, table.best());
+    let finished = match game.play() {
+        Ok(finished) => finished,
+        Err(e) => {
+            eprintln!("could not use the terminal: {}", e);
+            exit(1);
+        }
+    };
+
+    let score = game.arcade.score.unwrap_or_default();
+    println!("score: {}", score);
+    if !finished {
+        return;
+    }
+
+    if let Some(place) = table.add(&game.arcade) {
+        if let Err(e) = table.save(&scores) {
+            eprintln!("{}", e);
+            exit(1);
+        }
+        println!("number {} in the high score table", place + 1);
+    }
+}
+
 fn main() {
-    println!("Hello, world!");
+    let (options, args) = take_options(env::args().skip(1).collect());
+
+    match args.first().map(|s| s.as_str()) {
+        Some("run") => run(&args[1..], &options),
+        Some("list") => list(),
+        Some("all") => all(&options),
+        Some("verify") => verify(&args[1..]),
+        Some("bench") => bench(&args[1..]),
+        Some("render") => render(&args[1..]),
+        Some("watch") => watch(&args[1..]),
+        Some("record") => record(&args[1..]),
+        Some("play") => play(&args[1..]),
+        Some("arcade") => arcade(&args[1..], &options),
+        Some("explore") => explore(&args[1..], &options),
+        Some("help") | Some("--help") | Some("-h") => println!("{}", USAGE),
+        Some(command) => usage_error(&format!("unknown command {:?}", command)),
+        None => usage_error("no command given"),
+    }
 }
@@ -0,0 +1,1067 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::fmt;
+use std::fs;
+use std::io;
+
+use petgraph::graph::{DefaultIx, NodeIndex};
+use petgraph::graph::{Graph, UnGraph};
+use petgraph::visit::EdgeRef;
+use serde_json::json;
+
+use crate::corridors;
+use crate::grid::Grid;
+use crate::render::{Rgb, Style};
+
+// The area the day 15 repair droid explores, as a graph: a node for every cell it knows about and
+// an edge between cells it can move between. Nodes can be found by their coordinates as well as
+// by their index in the graph.
+//
+// A map can be saved as text, drawn the way day 15 draws it: '#' for a wall, '.' for open floor,
+// 'O' for the oxygen system and a space for anywhere nobody has been. The droid starts at (0, 0),
+// which is drawn as a 'D'.
+
+// where the droid starts, in a saved map
+pub const START: char = 'D';
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum NodeStatus {
+    NotSure,
+    Empty,
+    Wall,
+    Oxygen,
+}
+
+impl NodeStatus {
+    pub fn glyph(self) -> char {
+        match self {
+            NodeStatus::NotSure => return ' ',
+            NodeStatus::Wall => return '#',
+            NodeStatus::Empty => return '.',
+            NodeStatus::Oxygen => return 'O',
+        }
+    }
+
+    // what it's called in exported graphs
+    pub fn name(self) -> &'static str {
+        match self {
+            NodeStatus::NotSure => return "unknown",
+            NodeStatus::Wall => return "wall",
+            NodeStatus::Empty => return "empty",
+            NodeStatus::Oxygen => return "oxygen",
+        }
+    }
+
+    pub fn from_glyph(c: char) -> Option<NodeStatus> {
+        match c {
+            ' ' => return Some(NodeStatus::NotSure),
+            '#' => return Some(NodeStatus::Wall),
+            '.' | START => return Some(NodeStatus::Empty),
+            'O' => return Some(NodeStatus::Oxygen),
+            _ => return None,
+        }
+    }
+
+    // the droid can go there, as far as anyone knows
+    pub fn is_open(self) -> bool {
+        return self == NodeStatus::Empty || self == NodeStatus::Oxygen;
+    }
+}
+
+pub struct MapStyle;
+
+impl Style<NodeStatus> for MapStyle {
+    fn glyph(&self, cell: &NodeStatus) -> char {
+        return cell.glyph();
+    }
+
+    fn color(&self, cell: &NodeStatus) -> Rgb {
+        match cell {
+            NodeStatus::NotSure => return Rgb::BLACK,
+            NodeStatus::Wall => return Rgb(110, 110, 110),
+            NodeStatus::Empty => return Rgb(220, 220, 220),
+            NodeStatus::Oxygen => return Rgb(60, 120, 230),
+        }
+    }
+}
+
+// Formats for looking at a map's graph in other tools.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum GraphFormat {
+    // Graphviz, with every node pinned to its cell; draw it with neato
+    Dot,
+    GraphMl,
+
+    // nodes and links, as networkx and d3 read them
+    Json,
+}
+
+impl GraphFormat {
+    pub fn from_extension(path: &str) -> Option<GraphFormat> {
+        let extension = path.rsplit_once('.')?.1;
+        match extension {
+            "dot" | "gv" => return Some(GraphFormat::Dot),
+            "graphml" => return Some(GraphFormat::GraphMl),
+            "json" => return Some(GraphFormat::Json),
+            _ => return None,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Heuristic {
+    // plain breadth first search
+    None,
+    Manhattan,
+}
+
+// A way through the map.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path {
+    // the nodes to go through after the start, ending at the target reached
+    pub nodes: Vec<NodeIndex<DefaultIx>>,
+
+    // the edge weights along it, added up
+    pub length: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Node {
+    pub x: i32,
+    pub y: i32,
+    pub status: NodeStatus,
+    pub index: NodeIndex<DefaultIx>,
+}
+
+// the cells in between the ends of a corridor, by its ends
+type CorridorCells = HashMap<((i32, i32), (i32, i32)), Vec<(i32, i32)>>;
+
+#[derive(Clone, Default)]
+pub struct Map {
+    pub graph: UnGraph<Node, usize>,
+
+    // where each node is in the graph, by its coordinates
+    positions: HashMap<(i32, i32), NodeIndex<DefaultIx>>,
+
+    // on a contracted map, the cells in between the ends of the shortest corridor from one node
+    // to another, both ways round
+    corridors: CorridorCells,
+}
+
+impl Map {
+    pub fn new() -> Map {
+        return Map {
+            graph: Graph::new_undirected(),
+            positions: HashMap::new(),
+            corridors: HashMap::new(),
+        };
+    }
+
+    pub fn add_node(&mut self, x: i32, y: i32, status: NodeStatus) -> NodeIndex<DefaultIx> {
+        assert!(
+            !self.positions.contains_key(&(x, y)),
+            "{},{} is already in the map",
+            x,
+            y
+        );
+
+        let index = self.graph.add_node(Node {
+            x,
+            y,
+            status,
+            index: NodeIndex::end(),
+        });
+        self.graph[index].index = index;
+        self.positions.insert((x, y), index);
+
+        return index;
+    }
+
+    // Takes a node and its edges out of the map. The graph fills the gap with its last node, so
+    // that one's index changes.
+    pub fn remove_node(&mut self, x: i32, y: i32) -> Option<Node> {
+        let index = self.positions.remove(&(x, y))?;
+        let node = self.graph.remove_node(index)?;
+
+        if let Some(moved) = self.graph.node_weight_mut(index) {
+            moved.index = index;
+            self.positions.insert((moved.x, moved.y), index);
+        }
+
+        return Some(node);
+    }
+
+    pub fn add_edge(&mut self, x1: i32, y1: i32, x2: i32, y2: i32) {
+        self.graph.add_edge(
+            self.node_index(x1, y1).unwrap(),
+            self.node_index(x2, y2).unwrap(),
+            1,
+        );
+    }
+
+    pub fn node_index(&self, x: i32, y: i32) -> Option<NodeIndex<DefaultIx>> {
+        return self.positions.get(&(x, y)).copied();
+    }
+
+    pub fn find_oxygen_node(&self) -> NodeIndex<DefaultIx> {
+        for node_index in self.graph.node_indices() {
+            let node = self.graph.node_weight(node_index).unwrap();
+            if node.status == NodeStatus::Oxygen {
+                return node_index;
+            }
+        }
+        panic!("bad!");
+    }
+
+    pub fn node_exists(&self, x: i32, y: i32) -> bool {
+        match self.node_index(x, y) {
+            Some(_) => {
+                return true;
+            }
+            None => {
+                return false;
+            }
+        }
+    }
+
+    pub fn status(&self, x: i32, y: i32) -> Option<NodeStatus> {
+        return self.node_index(x, y).map(|i| self.graph[i].status);
+    }
+
+    pub fn has_edge(&self, x1: i32, y1: i32, x2: i32, y2: i32) -> bool {
+        match (self.node_index(x1, y1), self.node_index(x2, y2)) {
+            (Some(a), Some(b)) => return self.graph.find_edge(a, b).is_some(),
+            _ => return false,
+        }
+    }
+
+    pub fn get_node_by_index(&self, i: NodeIndex<DefaultIx>) -> &Node {
+        return self.graph.node_weight(i).unwrap();
+    }
+
+    pub fn get_node_by_index_mut(&mut self, i: NodeIndex<DefaultIx>) -> &mut Node {
+        return self.graph.node_weight_mut(i).unwrap();
+    }
+
+    pub fn update_node(&mut self, x: i32, y: i32, status: NodeStatus) {
+        let index = self.node_index(x, y).unwrap();
+        self.get_node_by_index_mut(index).status = status;
+    }
+
+    pub fn remove_edge(&mut self, x1: i32, y1: i32, x2: i32, y2: i32) {
+        let e = self
+            .graph
+            .find_edge(
+                self.node_index(x1, y1).unwrap(),
+                self.node_index(x2, y2).unwrap(),
+            )
+            .unwrap();
+        self.graph.remove_edge(e);
+    }
+
+    // The shortest way from one cell to whichever of the targets is nearest, found with A*.
+    // Edges are taken at their weights, so this works on a map whose corridors have been joined
+    // up into longer edges too. Manhattan distance never overestimates on a grid, and with it
+    // fewer nodes are looked at; without it this is a breadth first search.
+    pub fn find_path(
+        &self,
+        from: (i32, i32),
+        targets: &[(i32, i32)],
+        heuristic: Heuristic,
+    ) -> Option<Path> {
+        let start = self.node_index(from.0, from.1)?;
+        let goals: Vec<NodeIndex<DefaultIx>> = targets
+            .iter()
+            .filter_map(|(x, y)| self.node_index(*x, *y))
+            .collect();
+        if goals.is_empty() {
+            return None;
+        }
+
+        // how far at least from a node to the nearest target
+        let estimate = |index: NodeIndex<DefaultIx>| -> usize {
+            match heuristic {
+                Heuristic::None => return 0,
+                Heuristic::Manhattan => {
+                    let node = self.get_node_by_index(index);
+                    return targets
+                        .iter()
+                        .map(|(x, y)| ((node.x - x).abs() + (node.y - y).abs()) as usize)
+                        .min()
+                        .unwrap_or(0);
+                }
+            }
+        };
+
+        let mut best: HashMap<NodeIndex<DefaultIx>, usize> = HashMap::new();
+        let mut came_from: HashMap<NodeIndex<DefaultIx>, NodeIndex<DefaultIx>> = HashMap::new();
+        let mut queue = BinaryHeap::new();
+
+        best.insert(start, 0);
+        queue.push(Reverse((estimate(start), 0, start)));
+
+        while let Some(Reverse((_, cost, node))) = queue.pop() {
+            // there was a shorter way here, found after this was queued
+            if cost > best[&node] {
+                continue;
+            }
+
+            if goals.contains(&node) {
+                // back along the predecessors to the start
+                let mut nodes = Vec::new();
+                let mut current = node;
+                while current != start {
+                    nodes.push(current);
+                    current = came_from[&current];
+                }
+                nodes.reverse();
+
+                return Some(Path {
+                    nodes,
+                    length: cost,
+                });
+            }
+
+            for edge in self.graph.edges(node) {
+                let next = if edge.source() == node {
+                    edge.target()
+                } else {
+                    edge.source()
+                };
+                let next_cost = cost + *edge.weight();
+
+                if best.get(&next).is_none_or(|c| next_cost < *c) {
+                    best.insert(next, next_cost);
+                    came_from.insert(next, node);
+                    queue.push(Reverse((next_cost + estimate(next), next_cost, next)));
+                }
+            }
+        }
+
+        return None;
+    }
+
+    pub fn return_shortest_path_length(&self, x1: i32, y1: i32, x2: i32, y2: i32) -> Option<usize> {
+        return self
+            .find_path((x1, y1), &[(x2, y2)], Heuristic::Manhattan)
+            .map(|path| path.length);
+    }
+
+    // the nodes to go through, not counting the one at x1, y1
+    pub fn return_shortest_path(
+        &self,
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+    ) -> Option<Vec<NodeIndex<DefaultIx>>> {
+        return self
+            .find_path((x1, y1), &[(x2, y2)], Heuristic::Manhattan)
+            .map(|path| path.nodes);
+    }
+
+    // The map as text, one line per row from the top, with trailing spaces left off.
+    pub fn to_text(&self) -> String {
+        let mut output = String::new();
+
+        // unexplored cells are left blank, so they don't need room
+        let mut known: Vec<(i32, i32)> = self
+            .positions
+            .iter()
+            .filter(|(_, i)| self.graph[**i].status != NodeStatus::NotSure)
+            .map(|(p, _)| *p)
+            .collect();
+        known.push((0, 0));
+
+        let min_x = known.iter().map(|p| p.0).min().unwrap();
+        let max_x = known.iter().map(|p| p.0).max().unwrap();
+        let min_y = known.iter().map(|p| p.1).min().unwrap();
+        let max_y = known.iter().map(|p| p.1).max().unwrap();
+
+        for y in min_y..=max_y {
+            let mut line = String::new();
+            for x in min_x..=max_x {
+                if (x, y) == (0, 0) {
+                    line.push(START);
+                } else {
+                    line.push(self.status(x, y).unwrap_or(NodeStatus::NotSure).glyph());
+                }
+            }
+            output.push_str(line.trim_end());
+            output.push('\n');
+        }
+
+        return output;
+    }
+
+    // Reads a map back from text, putting the 'D' at (0, 0). It comes back as the droid would have
+    // left it: open cells are joined to their neighbours, and the spaces next to them are nodes
+    // nobody has been to yet.
+    pub fn from_text(text: &str) -> Result<Map, MapError> {
+        let mut cells: Vec<((i32, i32), NodeStatus)> = Vec::new();
+        let mut starts: Vec<(i32, i32)> = Vec::new();
+
+        for (y, line) in text.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                let status = match NodeStatus::from_glyph(c) {
+                    Some(NodeStatus::NotSure) => continue,
+                    Some(status) => status,
+                    None => {
+                        return Err(MapError::Glyph {
+                            line: y + 1,
+                            column: x + 1,
+                            glyph: c,
+                        })
+                    }
+                };
+                if c == START {
+                    starts.push((x as i32, y as i32));
+                }
+                cells.push(((x as i32, y as i32), status));
+            }
+        }
+
+        let (sx, sy) = match starts.as_slice() {
+            [start] => *start,
+            _ => return Err(MapError::Starts(starts.len())),
+        };
+
+        let mut map = Map::new();
+        for ((x, y), status) in cells.iter() {
+            map.add_node(x - sx, y - sy, *status);
+        }
+
+        for ((x, y), status) in cells.iter() {
+            if !status.is_open() {
+                continue;
+            }
+            let (x, y) = (x - sx, y - sy);
+
+            for (nx, ny) in [(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)].iter() {
+                match map.status(*nx, *ny) {
+                    None => {
+                        map.add_node(*nx, *ny, NodeStatus::NotSure);
+                    }
+                    Some(NodeStatus::Wall) => continue,
+                    Some(_) => {}
+                }
+                if !map.has_edge(x, y, *nx, *ny) {
+                    map.add_edge(x, y, *nx, *ny);
+                }
+            }
+        }
+
+        return Ok(map);
+    }
+
+    // The explored part of the map with its corridors joined up. The start, the oxygen system and
+    // any cells in keep stay, along with the junctions between them, and each corridor becomes an
+    // edge as long as it is; walls, dead ends and anywhere unexplored go.
+    pub fn contract(&self, keep: &[(i32, i32)]) -> Map {
+        let mut grid = Grid::new(NodeStatus::NotSure);
+        for index in self.graph.node_indices() {
+            let node = &self.graph[index];
+            grid.set(node.x, node.y, node.status);
+        }
+
+        let corridors = corridors::contract(
+            &grid,
+            |status| status.is_open(),
+            |position, status| {
+                return position == (0, 0)
+                    || *status == NodeStatus::Oxygen
+                    || keep.contains(&position);
+            },
+            |(x, y), status| Node {
+                x,
+                y,
+                status: *status,
+                index: NodeIndex::end(),
+            },
+        );
+
+        let mut cells: CorridorCells = HashMap::new();
+        for edge in corridors.graph.edge_references() {
+            let from = corridors.position(edge.source());
+            let to = corridors.position(edge.target());
+            let along = &corridors.cells[edge.id().index()];
+
+            // only the shortest of two corridors between the same nodes is ever taken
+            if cells
+                .get(&(from, to))
+                .is_some_and(|c| c.len() <= along.len())
+            {
+                continue;
+            }
+            cells.insert((to, from), along.iter().rev().copied().collect());
+            cells.insert((from, to), along.clone());
+        }
+
+        let mut graph = corridors.graph;
+        for index in graph.node_indices() {
+            graph[index].index = index;
+        }
+
+        return Map {
+            graph,
+            positions: corridors.positions,
+            corridors: cells,
+        };
+    }
+
+    // The cells along a path found in the map, not counting the one it starts from, the same as
+    // the nodes return_shortest_path gives. On a contracted map, the corridors each step stands
+    // for are put back.
+    pub fn expand(&self, from: (i32, i32), nodes: &[NodeIndex<DefaultIx>]) -> Vec<(i32, i32)> {
+        let mut cells = Vec::new();
+        let mut previous = from;
+
+        for index in nodes {
+            let node = self.get_node_by_index(*index);
+            let here = (node.x, node.y);
+            if let Some(corridor) = self.corridors.get(&(previous, here)) {
+                cells.extend(corridor.iter().copied());
+            }
+            cells.push(here);
+            previous = here;
+        }
+
+        return cells;
+    }
+
+    // every node in index order, and every edge as the indexes it joins and its weight
+    fn nodes_and_edges(&self) -> (Vec<&Node>, Vec<(usize, usize, usize)>) {
+        let nodes = self.graph.node_indices().map(|i| &self.graph[i]).collect();
+        let edges = self
+            .graph
+            .edge_references()
+            .map(|e| (e.source().index(), e.target().index(), *e.weight()))
+            .collect();
+        return (nodes, edges);
+    }
+
+    pub fn export(&self, format: GraphFormat) -> String {
+        match format {
+            GraphFormat::Dot => return self.to_dot(),
+            GraphFormat::GraphMl => return self.to_graphml(),
+            GraphFormat::Json => {
+                return serde_json::to_string_pretty(&self.to_node_link()).unwrap() + "\n";
+            }
+        }
+    }
+
+    // Cells are a unit apart, with y going up the page as it does in Graphviz. Edges longer than
+    // a step are labelled with their weight.
+    pub fn to_dot(&self) -> String {
+        let (nodes, edges) = self.nodes_and_edges();
+        let mut output = String::new();
+
+        output.push_str("graph map {\n");
+        output.push_str("    node [shape=square, style=filled, fixedsize=true, width=0.9];\n");
+        for node in nodes {
+            let label = if (node.x, node.y) == (0, 0) {
+                START
+            } else {
+                node.status.glyph()
+            };
+            output.push_str(&format!(
+                "    n{} [label=\"{}\", pos=\"{},{}!\", fillcolor=\"{}\", status={}];\n",
+                node.index.index(),
+                label,
+                node.x,
+                -node.y,
+                MapStyle.color(&node.status).hex(),
+                node.status.name()
+            ));
+        }
+        for (a, b, weight) in edges {
+            if weight == 1 {
+                output.push_str(&format!("    n{} -- n{};\n", a, b));
+            } else {
+                output.push_str(&format!(
+                    "    n{} -- n{} [label=\"{}\", weight={}];\n",
+                    a, b, weight, weight
+                ));
+            }
+        }
+        output.push_str("}\n");
+
+        return output;
+    }
+
+    pub fn to_graphml(&self) -> String {
+        let (nodes, edges) = self.nodes_and_edges();
+        let mut output = String::new();
+
+        output.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        output.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+        output.push_str("  <key id=\"x\" for=\"node\" attr.name=\"x\" attr.type=\"int\"/>\n");
+        output.push_str("  <key id=\"y\" for=\"node\" attr.name=\"y\" attr.type=\"int\"/>\n");
+        output.push_str(
+            "  <key id=\"status\" for=\"node\" attr.name=\"status\" attr.type=\"string\"/>\n",
+        );
+        output.push_str(
+            "  <key id=\"weight\" for=\"edge\" attr.name=\"weight\" attr.type=\"int\"/>\n",
+        );
+        output.push_str("  <graph id=\"map\" edgedefault=\"undirected\">\n");
+        for node in nodes {
+            output.push_str(&format!(
+                "    <node id=\"n{}\"><data key=\"x\">{}</data><data key=\"y\">{}</data><data key=\"status\">{}</data></node>\n",
+                node.index.index(),
+                node.x,
+                node.y,
+                node.status.name()
+            ));
+        }
+        for (a, b, weight) in edges {
+            output.push_str(&format!(
+                "    <edge source=\"n{}\" target=\"n{}\"><data key=\"weight\">{}</data></edge>\n",
+                a, b, weight
+            ));
+        }
+        output.push_str("  </graph>\n");
+        output.push_str("</graphml>\n");
+
+        return output;
+    }
+
+    pub fn to_node_link(&self) -> serde_json::Value {
+        let (nodes, edges) = self.nodes_and_edges();
+
+        let nodes: Vec<serde_json::Value> = nodes
+            .iter()
+            .map(|node| {
+                return json!({
+                    "id": node.index.index(),
+                    "x": node.x,
+                    "y": node.y,
+                    "status": node.status.name(),
+                });
+            })
+            .collect();
+        let links: Vec<serde_json::Value> = edges
+            .iter()
+            .map(|(a, b, weight)| json!({"source": a, "target": b, "weight": weight}))
+            .collect();
+
+        return json!({
+            "directed": false,
+            "multigraph": false,
+            "graph": {},
+            "nodes": nodes,
+            "links": links,
+        });
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), MapError> {
+        return fs::write(path, self.to_text()).map_err(|e| MapError::Io(path.to_string(), e));
+    }
+
+    pub fn load(path: &str) -> Result<Map, MapError> {
+        let text = fs::read_to_string(path).map_err(|e| MapError::Io(path.to_string(), e))?;
+        return Map::from_text(&text);
+    }
+}
+
+#[derive(Debug)]
+pub enum MapError {
+    Io(String, io::Error),
+
+    // a character that isn't part of a map, counting lines and columns from 1
+    Glyph {
+        line: usize,
+        column: usize,
+        glyph: char,
+    },
+
+    // how many places the droid starts from, when it isn't one
+    Starts(usize),
+}
+
+impl fmt::Display for MapError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MapError::Io(path, e) => write!(f, "could not access {}: {}", path, e),
+            MapError::Glyph {
+                line,
+                column,
+                glyph,
+            } => write!(
+                f,
+                "line {} column {}: {:?} is not part of a map",
+                line, column, glyph
+            ),
+            MapError::Starts(0) => write!(f, "the map has no {} for the droid", START),
+            MapError::Starts(n) => write!(f, "the map has {} {}s, not one", n, START),
+        }
+    }
+}
+
+impl std::error::Error for MapError {}
+
+// every node is where the index says, and every indexed position has a node
+#[cfg(test)]
+fn check_positions(map: &Map) {
+    assert_eq!(map.positions.len(), map.graph.node_count());
+
+    for index in map.graph.node_indices() {
+        let node = map.get_node_by_index(index);
+        assert_eq!(node.index, index);
+        assert_eq!(map.node_index(node.x, node.y), Some(index));
+    }
+}
+
+#[test]
+fn test_map_positions() {
+    let mut map = Map::new();
+    for x in 0..5 {
+        map.add_node(x, 0, NodeStatus::Empty);
+        map.add_node(x, 1, NodeStatus::Wall);
+    }
+    for x in 1..5 {
+        map.add_edge(x - 1, 0, x, 0);
+    }
+    check_positions(&map);
+    assert!(map.node_exists(4, 1));
+    assert!(!map.node_exists(5, 0));
+
+    // from the middle, so the last node is moved into its place
+    let removed = map.remove_node(2, 0).unwrap();
+    assert_eq!((removed.x, removed.y), (2, 0));
+    check_positions(&map);
+    assert!(!map.node_exists(2, 0));
+    assert_eq!(map.graph.edge_count(), 2);
+
+    // the last node, and one that isn't there
+    map.remove_node(4, 1).unwrap();
+    check_positions(&map);
+    assert!(map.remove_node(2, 0).is_none());
+
+    // and the edges still join the right cells
+    assert_eq!(map.return_shortest_path_length(3, 0, 4, 0), Some(1));
+    map.update_node(3, 0, NodeStatus::Oxygen);
+    let oxygen = map.get_node_by_index(map.find_oxygen_node());
+    assert_eq!((oxygen.x, oxygen.y), (3, 0));
+
+    // a position can be used again once it's free
+    map.add_node(2, 0, NodeStatus::Empty);
+    check_positions(&map);
+}
+
+#[test]
+fn test_map_text() {
+    // explored up to the walls, except on the right
+    let text = "  ###\n ##.#.\n#..D.\n ####.\n";
+    let map = Map::from_text(text).unwrap();
+    assert_eq!(map.to_text(), text);
+    check_positions(&map);
+
+    assert_eq!(map.status(0, 0), Some(NodeStatus::Empty));
+    assert_eq!(map.status(-2, 0), Some(NodeStatus::Empty));
+    assert_eq!(map.status(-1, -1), Some(NodeStatus::Wall));
+    assert_eq!(map.status(2, 0), Some(NodeStatus::NotSure));
+    assert_eq!(map.status(5, 5), None);
+    assert!(map.has_edge(0, 0, 0, -1));
+    assert!(map.has_edge(1, 0, 2, 0));
+    assert!(map.has_edge(2, 0, 2, 1));
+    assert!(!map.has_edge(0, 0, 0, 1));
+
+    // paths can go through cells nobody has been to yet, as they can while exploring
+    assert_eq!(map.return_shortest_path_length(-2, 0, 2, 1), Some(5));
+
+    // with the start off to one side, and an oxygen system
+    let map = Map::from_text("#O\n.D#\n").unwrap();
+    assert_eq!(map.status(-1, 0), Some(NodeStatus::Empty));
+    assert_eq!(map.status(0, -1), Some(NodeStatus::Oxygen));
+    let oxygen = map.get_node_by_index(map.find_oxygen_node());
+    assert_eq!((oxygen.x, oxygen.y), (0, -1));
+    assert_eq!(map.to_text(), "#O\n.D#\n");
+
+    let path = std::env::temp_dir().join(format!("maze-{}.map", std::process::id()));
+    let path = path.to_str().unwrap();
+    map.save(path).unwrap();
+    assert_eq!(Map::load(path).unwrap().to_text(), map.to_text());
+    std::fs::remove_file(path).unwrap();
+
+    match Map::from_text("#D\n#x") {
+        Err(MapError::Glyph {
+            line: 2,
+            column: 2,
+            glyph: 'x',
+        }) => {}
+        other => panic!("{:?}", other.map(|m| m.to_text())),
+    }
+    assert_eq!(
+        Map::from_text("#..#").err().unwrap().to_string(),
+        "the map has no D for the droid"
+    );
+    assert!(matches!(Map::from_text("D.D"), Err(MapError::Starts(2))));
+    assert!(Map::load("does-not-exist.map").is_err());
+}
+
+#[test]
+fn test_export() {
+    let map = Map::from_text("#O\n.D#\n").unwrap();
+    let start = map.node_index(0, 0).unwrap().index();
+    let oxygen = map.find_oxygen_node().index();
+
+    let dot = map.to_dot();
+    assert!(dot.starts_with("graph map {\n"));
+    assert!(dot.contains(&format!(
+        "n{} [label=\"D\", pos=\"0,0!\", fillcolor=\"#dcdcdc\", status=empty];",
+        start
+    )));
+    assert!(dot.contains("pos=\"0,1!\", fillcolor=\"#3c78e6\", status=oxygen"));
+    assert!(dot.contains(&format!(
+        "n{} -- n{};",
+        start.min(oxygen),
+        start.max(oxygen)
+    )));
+    assert_eq!(dot.matches(" -- ").count(), map.graph.edge_count());
+
+    let graphml = map.to_graphml();
+    assert_eq!(graphml.matches("<node ").count(), map.graph.node_count());
+    assert_eq!(graphml.matches("<edge ").count(), map.graph.edge_count());
+    assert!(graphml.contains("<data key=\"status\">oxygen</data>"));
+
+    let json = map.to_node_link();
+    assert_eq!(json["directed"], false);
+    assert_eq!(
+        json["nodes"].as_array().unwrap().len(),
+        map.graph.node_count()
+    );
+    assert_eq!(json["nodes"][oxygen]["y"], -1);
+    assert_eq!(json["nodes"][oxygen]["status"], "oxygen");
+    assert_eq!(
+        json["links"].as_array().unwrap().len(),
+        map.graph.edge_count()
+    );
+
+    // longer edges say how long
+    let mut map = Map::new();
+    map.add_node(0, 0, NodeStatus::Empty);
+    map.add_node(3, 0, NodeStatus::Empty);
+    map.graph.add_edge(
+        map.node_index(0, 0).unwrap(),
+        map.node_index(3, 0).unwrap(),
+        3,
+    );
+    assert!(map.to_dot().contains("n0 -- n1 [label=\"3\", weight=3];"));
+
+    assert_eq!(
+        GraphFormat::from_extension("map.gv"),
+        Some(GraphFormat::Dot)
+    );
+    assert_eq!(
+        GraphFormat::from_extension("out/map.graphml"),
+        Some(GraphFormat::GraphMl)
+    );
+    assert_eq!(GraphFormat::from_extension("map.txt"), None);
+    assert_eq!(GraphFormat::from_extension("map"), None);
+    assert!(map.export(GraphFormat::Json).ends_with("}\n"));
+}
+
+// A fully explored maze in the style of the puzzle's: odd cells are open, and a depth first walk
+// from the middle knocks through walls between them. The walk's choices come from a small LCG, so
+// it's the same maze every time.
+#[cfg(test)]
+pub fn maze(size: i32) -> Map {
+    let mut seed: u64 = 15;
+    let mut random = || {
+        seed = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        return (seed >> 33) as usize;
+    };
+
+    let mut open: Grid<bool> = Grid::new(false);
+    let start = (size / 2) | 1;
+    let mut stack = vec![(start, start)];
+    open.set(start, start, true);
+
+    while let Some(&(x, y)) = stack.last() {
+        let next: Vec<(i32, i32)> = [(0, -2), (0, 2), (-2, 0), (2, 0)]
+            .iter()
+            .map(|(ox, oy)| (x + ox, y + oy))
+            .filter(|&(nx, ny)| nx > 0 && ny > 0 && nx < size && ny < size)
+            .filter(|&(nx, ny)| !*open.get(nx, ny))
+            .collect();
+
+        if next.is_empty() {
+            stack.pop();
+            continue;
+        }
+
+        let (nx, ny) = next[random() % next.len()];
+        open.set((x + nx) / 2, (y + ny) / 2, true);
+        open.set(nx, ny, true);
+        stack.push((nx, ny));
+    }
+
+    let mut map = Map::new();
+    for y in 0..=size {
+        for x in 0..=size {
+            let status = if *open.get(x, y) {
+                NodeStatus::Empty
+            } else {
+                NodeStatus::Wall
+            };
+            map.add_node(x, y, status);
+        }
+    }
+    for y in 0..=size {
+        for x in 0..=size {
+            if !*open.get(x, y) {
+                continue;
+            }
+            if *open.get(x + 1, y) {
+                map.add_edge(x, y, x + 1, y);
+            }
+            if *open.get(x, y + 1) {
+                map.add_edge(x, y, x, y + 1);
+            }
+        }
+    }
+
+    return map;
+}
+
+#[test]
+fn test_find_path() {
+    // a loop, with a dead end off it
+    let picture = [".....", ".#.#.", ".....", "    ."];
+
+    let mut map = Map::new();
+    let mut open: Vec<(i32, i32)> = Vec::new();
+    for (y, row) in picture.iter().enumerate() {
+        for (x, c) in row.chars().enumerate() {
+            if c == '.' {
+                open.push((x as i32, y as i32));
+                map.add_node(x as i32, y as i32, NodeStatus::Empty);
+            }
+        }
+    }
+    for (x, y) in open.iter() {
+        if map.node_exists(x + 1, *y) {
+            map.add_edge(*x, *y, x + 1, *y);
+        }
+        if map.node_exists(*x, y + 1) {
+            map.add_edge(*x, *y, *x, y + 1);
+        }
+    }
+    map.add_node(9, 9, NodeStatus::Empty);
+
+    let cells = |path: &Path| -> Vec<(i32, i32)> {
+        return path
+            .nodes
+            .iter()
+            .map(|i| (map.graph[*i].x, map.graph[*i].y))
+            .collect();
+    };
+
+    let path = map
+        .find_path((0, 0), &[(4, 3)], Heuristic::Manhattan)
+        .unwrap();
+    assert_eq!(path.length, 7);
+    assert_eq!(path.nodes.len(), 7);
+    assert_eq!(cells(&path).last(), Some(&(4, 3)));
+
+    // each step is to a neighbour
+    let mut previous = (0, 0);
+    for (x, y) in cells(&path) {
+        assert_eq!((x - previous.0).abs() + (y - previous.1).abs(), 1);
+        previous = (x, y);
+    }
+
+    // the nearest of several targets
+    let path = map
+        .find_path((2, 1), &[(4, 3), (0, 0), (1, 2)], Heuristic::Manhattan)
+        .unwrap();
+    assert_eq!(path.length, 2);
+    assert_eq!(cells(&path), vec![(2, 2), (1, 2)]);
+
+    // already there
+    let path = map.find_path((1, 2), &[(1, 2)], Heuristic::None).unwrap();
+    assert_eq!(path.length, 0);
+    assert!(path.nodes.is_empty());
+
+    // not joined up, or not in the map at all
+    assert_eq!(map.find_path((0, 0), &[(9, 9)], Heuristic::Manhattan), None);
+    assert_eq!(map.find_path((0, 0), &[(7, 7)], Heuristic::Manhattan), None);
+    assert_eq!(map.find_path((7, 7), &[(0, 0)], Heuristic::Manhattan), None);
+    assert_eq!(map.return_shortest_path_length(0, 0, 9, 9), None);
+
+    // A* finds paths as short as breadth first search does
+    let maze = maze(20);
+    let open: Vec<(i32, i32)> = maze
+        .graph
+        .node_indices()
+        .map(|i| &maze.graph[i])
+        .filter(|n| n.status == NodeStatus::Empty)
+        .map(|n| (n.x, n.y))
+        .collect();
+    for to in open.iter().step_by(7) {
+        let bfs = maze.find_path(open[0], &[*to], Heuristic::None).unwrap();
+        let astar = maze
+            .find_path(open[0], &[*to], Heuristic::Manhattan)
+            .unwrap();
+        assert_eq!(astar.length, bfs.length);
+        assert_eq!(astar.nodes.len(), astar.length);
+    }
+}
+
+#[test]
+fn test_contract() {
+    // the part 2 example: the start and the oxygen system are one corridor apart
+    let map = Map::from_text(" ##\n#D.##\n#.#..#\n#.O.#\n ###\n").unwrap();
+    let contracted = map.contract(&[]);
+    assert_eq!(contracted.graph.node_count(), 2);
+    assert_eq!(contracted.return_shortest_path_length(0, 0, 1, 2), Some(3));
+    assert_eq!(contracted.status(1, 2), Some(NodeStatus::Oxygen));
+    check_positions(&contracted);
+
+    // the corridor is put back into the path
+    let path = contracted.return_shortest_path(0, 0, 1, 2).unwrap();
+    assert_eq!(path.len(), 1);
+    assert_eq!(
+        contracted.expand((0, 0), &path),
+        vec![(0, 1), (0, 2), (1, 2)]
+    );
+    assert_eq!(
+        map.expand((0, 0), &map.return_shortest_path(0, 0, 1, 2).unwrap()),
+        vec![(0, 1), (0, 2), (1, 2)]
+    );
+
+    // paths through a maze are as long as they were, with far fewer nodes to go through
+    let maze = maze(20);
+    let open: Vec<(i32, i32)> = maze
+        .graph
+        .node_indices()
+        .map(|i| &maze.graph[i])
+        .filter(|n| n.status == NodeStatus::Empty)
+        .map(|n| (n.x, n.y))
+        .step_by(7)
+        .collect();
+    let contracted = maze.contract(&open);
+    check_positions(&contracted);
+    assert!(contracted.graph.node_count() < open.len() * 2);
+    for to in open.iter() {
+        let path = contracted
+            .find_path(open[0], &[*to], Heuristic::Manhattan)
+            .unwrap();
+        assert_eq!(
+            Some(path.length),
+            maze.find_path(open[0], &[*to], Heuristic::Manhattan)
+                .map(|p| p.length),
+        );
+
+        // and put back, a step at a time
+        let cells = contracted.expand(open[0], &path.nodes);
+        assert_eq!(cells.len(), path.length);
+        let mut previous = open[0];
+        for (x, y) in cells {
+            assert!(maze.has_edge(previous.0, previous.1, x, y));
+            previous = (x, y);
+        }
+    }
+}
@@ -0,0 +1,378 @@
+use std::fmt;
+
+use crate::grid::Grid;
+
+// Reads the block capitals that some puzzles draw instead of giving a number. There are two fonts:
+// the usual one is 6 rows tall with letters 4 columns wide (plus a blank column between them), the
+// large one is 10 rows tall with letters 6 columns wide (plus two blank columns). Which one is used
+// is decided by the height of the lit area.
+
+const SMALL: [(char, [&str; 6]); 18] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+const LARGE: [(char, [&str; 10]); 15] = [
+    (
+        'A',
+        [
+            "..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#",
+            "#....#", "#....#",
+        ],
+    ),
+    (
+        'B',
+        [
+            "#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#",
+            "#....#", "#####.",
+        ],
+    ),
+    (
+        'C',
+        [
+            ".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....",
+            "#....#", ".####.",
+        ],
+    ),
+    (
+        'E',
+        [
+            "######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....",
+            "#.....", "######",
+        ],
+    ),
+    (
+        'F',
+        [
+            "######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....",
+            "#.....", "#.....",
+        ],
+    ),
+    (
+        'G',
+        [
+            ".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#",
+            "#...##", ".###.#",
+        ],
+    ),
+    (
+        'H',
+        [
+            "#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#",
+            "#....#", "#....#",
+        ],
+    ),
+    (
+        'J',
+        [
+            "...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.",
+            "#...#.", ".###..",
+        ],
+    ),
+    (
+        'K',
+        [
+            "#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..",
+            "#...#.", "#....#",
+        ],
+    ),
+    (
+        'L',
+        [
+            "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....",
+            "#.....", "######",
+        ],
+    ),
+    (
+        'N',
+        [
+            "#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##",
+            "#...##", "#....#",
+        ],
+    ),
+    (
+        'P',
+        [
+            "#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....",
+            "#.....", "#.....",
+        ],
+    ),
+    (
+        'R',
+        [
+            "#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.",
+            "#....#", "#....#",
+        ],
+    ),
+    (
+        'X',
+        [
+            "#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.",
+            "#....#", "#....#",
+        ],
+    ),
+    (
+        'Z',
+        [
+            "######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....",
+            "#.....", "######",
+        ],
+    ),
+];
+
+// A glyph that isn't in the font, kept so it can be shown and added.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownGlyph {
+    // which letter it was, counting from 0
+    pub position: usize,
+
+    // '#' for lit, '.' for unlit, one string per row
+    pub rows: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OcrError {
+    // nothing is lit
+    Blank,
+
+    // lit rows that match neither font
+    Height(usize),
+
+    // the text read so far, with '?' for each glyph that wasn't recognized
+    Unknown(String, Vec<UnknownGlyph>),
+}
+
+impl fmt::Display for OcrError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OcrError::Blank => write!(f, "there are no letters, nothing is lit"),
+            OcrError::Height(height) => write!(
+                f,
+                "letters are {} rows tall, only 6 and 10 rows can be read",
+                height
+            ),
+            OcrError::Unknown(text, glyphs) => {
+                write!(f, "could not read all of {:?}", text)?;
+                for glyph in glyphs {
+                    write!(
+                        f,
+                        "\n\nletter {}:\n{}",
+                        glyph.position,
+                        glyph.rows.join("\n")
+                    )?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for OcrError {}
+
+// Drops the blank columns on either side of a glyph, so letters are compared by their lit part
+// only.
+fn trim(rows: &[String]) -> Vec<String> {
+    let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    let lit = |column: usize| {
+        rows.iter()
+            .any(|row| row.as_bytes().get(column) == Some(&b'#'))
+    };
+
+    let first = (0..width).find(|c| lit(*c));
+    let last = (0..width).rev().find(|c| lit(*c));
+
+    match (first, last) {
+        (Some(first), Some(last)) => {
+            return rows
+                .iter()
+                .map(|row| row[first..=last].to_string())
+                .collect()
+        }
+        _ => return Vec::new(),
+    }
+}
+
+fn lookup(rows: &[String]) -> Option<char> {
+    let matches = |pattern: &[&str]| {
+        let pattern: Vec<String> = pattern.iter().map(|s| s.to_string()).collect();
+        return trim(&pattern) == rows;
+    };
+
+    match rows.len() {
+        6 => return SMALL.iter().find(|(_, p)| matches(p)).map(|(c, _)| *c),
+        10 => return LARGE.iter().find(|(_, p)| matches(p)).map(|(c, _)| *c),
+        _ => return None,
+    }
+}
+
+// Reads the letters in a grid, where lit says which cells are part of a letter. Only the lit area
+// is looked at, so margins and unlit panels around it don't matter. Letters are told apart by the
+// blank columns between them.
+pub fn read<T: Clone, F: Fn(&T) -> bool>(grid: &Grid<T>, lit: F) -> Result<String, OcrError> {
+    let on: Vec<(i32, i32)> = grid
+        .iter()
+        .filter(|(_, v)| lit(v))
+        .map(|(p, _)| p)
+        .collect();
+    if on.is_empty() {
+        return Err(OcrError::Blank);
+    }
+
+    let min_x = on.iter().map(|p| p.0).min().unwrap();
+    let max_x = on.iter().map(|p| p.0).max().unwrap();
+    let min_y = on.iter().map(|p| p.1).min().unwrap();
+    let max_y = on.iter().map(|p| p.1).max().unwrap();
+
+    let height = (max_y - min_y + 1) as usize;
+    if height != 6 && height != 10 {
+        return Err(OcrError::Height(height));
+    }
+
+    let column = |x: i32| -> String {
+        return (min_y..=max_y)
+            .map(|y| if lit(grid.get(x, y)) { '#' } else { '.' })
+            .collect();
+    };
+
+    // split into runs of columns that have something lit in them
+    let mut glyphs: Vec<Vec<String>> = Vec::new();
+    let mut current: Vec<String> = Vec::new();
+    for x in min_x..=max_x {
+        let c = column(x);
+        if c.contains('#') {
+            current.push(c);
+        } else if !current.is_empty() {
+            glyphs.push(current);
+            current = Vec::new();
+        }
+    }
+    glyphs.push(current);
+
+    let mut text = String::new();
+    let mut unknown: Vec<UnknownGlyph> = Vec::new();
+
+    for (position, columns) in glyphs.iter().enumerate() {
+        // the columns turned back into rows
+        let rows: Vec<String> = (0..height)
+            .map(|r| columns.iter().map(|c| c.as_bytes()[r] as char).collect())
+            .collect();
+
+        match lookup(&rows) {
+            Some(c) => text.push(c),
+            None => {
+                text.push('?');
+                unknown.push(UnknownGlyph { position, rows });
+            }
+        }
+    }
+
+    if !unknown.is_empty() {
+        return Err(OcrError::Unknown(text, unknown));
+    }
+
+    return Ok(text);
+}
+
+// the same, for letters drawn as lines of '#' and '.' (or ' ')
+pub fn read_text(picture: &str) -> Result<String, OcrError> {
+    let mut grid: Grid<bool> = Grid::new(false);
+
+    for (y, line) in picture.lines().enumerate() {
+        for (x, c) in line.chars().enumerate() {
+            grid.set(x as i32, y as i32, c == '#');
+        }
+    }
+
+    return read(&grid, |v| *v);
+}
+
+#[test]
+fn test_read_small() {
+    // every letter of the small font, with a blank column between each
+    let mut rows = vec![String::new(); 6];
+    let mut expected = String::new();
+    for (c, pattern) in SMALL.iter() {
+        for (row, line) in rows.iter_mut().zip(pattern.iter()) {
+            row.push_str(line);
+            row.push('.');
+        }
+        expected.push(*c);
+    }
+    assert_eq!(read_text(&rows.join("\n")), Ok(expected));
+
+    // a hull as painted, with unlit margins all round
+    let hull = "\
+..........
+.#..#.###.
+.#..#.#..#
+.####.###.
+.#..#.#..#
+.#..#.#..#
+.#..#.###.
+..........";
+    assert_eq!(read_text(hull), Ok("HB".to_string()));
+}
+
+#[test]
+fn test_read_large() {
+    let mut rows = vec![String::new(); 10];
+    let mut expected = String::new();
+    for (c, pattern) in LARGE.iter() {
+        for (row, line) in rows.iter_mut().zip(pattern.iter()) {
+            row.push_str(line);
+            row.push_str("..");
+        }
+        expected.push(*c);
+    }
+    assert_eq!(read_text(&rows.join("\n")), Ok(expected));
+}
+
+#[test]
+fn test_read_errors() {
+    assert_eq!(read_text("....\n...."), Err(OcrError::Blank));
+    assert_eq!(read_text("#\n#\n#"), Err(OcrError::Height(3)));
+
+    // a C, then something that isn't a letter
+    let picture = "\
+.##..#..#
+#..#.#.#.
+#....##..
+#....#...
+#..#.#...
+.##..####";
+    let error = read_text(picture).unwrap_err();
+    assert_eq!(
+        error,
+        OcrError::Unknown(
+            "C?".to_string(),
+            vec![UnknownGlyph {
+                position: 1,
+                rows: vec![
+                    "#..#".to_string(),
+                    "#.#.".to_string(),
+                    "##..".to_string(),
+                    "#...".to_string(),
+                    "#...".to_string(),
+                    "####".to_string(),
+                ],
+            }]
+        )
+    );
+    assert!(error.to_string().contains("letter 1:\n#..#\n#.#.\n##.."));
+}
@@ -0,0 +1,217 @@
+use std::io;
+use std::time::{Duration, Instant};
+
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+
+use crate::arcade::{Arcade, Joystick, ScreenStyle};
+use crate::render;
+use crate::watch::{self, Screen, Terminal};
+
+// Day 13's arcade cabinet, played by hand in the terminal. The game moves on one joystick read
+// every tick; whichever way was pressed last during the tick is what the joystick reads, and
+// nothing pressed is neutral.
+//
+//   left and right, or a and d   move the paddle
+//   space or p                   pause and resume
+//   s                            save the game
+//   l                            load the saved game
+//   q or esc                     quit
+
+pub const KEYS: &str = "left/right: move  space: pause  s: save  l: load  q: quit";
+
+pub const SAVE_PATH: &str = "day13.save";
+
+// while paused, how often to look for keys
+const PAUSED_POLL: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Input {
+    Move(Joystick),
+    Pause,
+    Save,
+    Load,
+    Quit,
+}
+
+pub fn input(key: KeyEvent) -> Option<Input> {
+    // raw mode means ctrl-c is just another key
+    if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+        return Some(Input::Quit);
+    }
+
+    match key.code {
+        KeyCode::Left | KeyCode::Char('a') => return Some(Input::Move(Joystick::Left)),
+        KeyCode::Right | KeyCode::Char('d') => return Some(Input::Move(Joystick::Right)),
+        KeyCode::Char(' ') | KeyCode::Char('p') => return Some(Input::Pause),
+        KeyCode::Char('s') => return Some(Input::Save),
+        KeyCode::Char('l') => return Some(Input::Load),
+        KeyCode::Char('q') | KeyCode::Esc => return Some(Input::Quit),
+        _ => return None,
+    }
+}
+
+pub struct Game {
+    pub arcade: Arcade,
+    pub tick: Duration,
+    pub save_path: String,
+
+    // the best score in the high score table, to beat
+    pub best: Option<i64>,
+
+    paused: bool,
+
+    // what happened last, such as a save
+    note: String,
+}
+
+impl Game {
+    pub fn new(arcade: Arcade, tick: Duration, save_path: &str, best: Option<i64>) -> Game {
+        return Game {
+            arcade,
+            tick,
+            save_path: save_path.to_string(),
+            best,
+            paused: false,
+            note: String::new(),
+        };
+    }
+
+    fn status(&self, keys: &str) -> Vec<String> {
+        let score = self.arcade.score.unwrap_or_default();
+        let best = match self.best {
+            Some(best) => best.max(score),
+            None => score,
+        };
+
+        let mut lines = vec![
+            format!("score: {}", score),
+            format!("best: {}", best),
+            format!("blocks: {}", self.arcade.blocks()),
+            format!("ticks: {}", self.arcade.ticks),
+        ];
+        if self.paused {
+            lines.push("paused".to_string());
+        } else {
+            lines.push(String::new());
+        }
+        lines.push(self.note.clone());
+        lines.push(String::new());
+        lines.push(keys.to_string());
+
+        return lines;
+    }
+
+    fn draw(&self, terminal: &mut Terminal, screen: &mut Screen, keys: &str) -> io::Result<()> {
+        let pixels = render::pixels(&self.arcade.screen, &ScreenStyle);
+        let output = screen.update(&pixels, &self.status(keys));
+        return terminal.write(&output);
+    }
+
+    // Does what a key asks, other than moving. Returns false to quit.
+    fn handle(&mut self, input: Input) -> bool {
+        match input {
+            Input::Move(_) => {}
+            Input::Pause => self.paused = !self.paused,
+            Input::Save => match self.arcade.save(&self.save_path) {
+                Ok(()) => self.note = format!("saved to {}", self.save_path),
+                Err(e) => self.note = e.to_string(),
+            },
+            Input::Load => match Arcade::load(&self.save_path) {
+                Ok(arcade) => {
+                    self.arcade = arcade;
+                    self.note = format!("loaded {}", self.save_path);
+                }
+                Err(e) => self.note = e.to_string(),
+            },
+            Input::Quit => return false,
+        }
+        return true;
+    }
+
+    // Waits out one tick taking keys, and says which way the joystick was pushed. None means quit.
+    fn read_joystick(
+        &mut self,
+        terminal: &mut Terminal,
+        screen: &mut Screen,
+    ) -> io::Result<Option<Joystick>> {
+        let deadline = Instant::now() + self.tick;
+        let mut joystick = Joystick::Neutral;
+
+        loop {
+            let timeout = if self.paused {
+                PAUSED_POLL
+            } else {
+                deadline.saturating_duration_since(Instant::now())
+            };
+
+            if !event::poll(timeout)? {
+                if self.paused {
+                    continue;
+                }
+                return Ok(Some(joystick));
+            }
+
+            let key = match event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => key,
+                _ => continue,
+            };
+
+            match input(key) {
+                // moves don't count while paused
+                Some(Input::Move(j)) if !self.paused => joystick = j,
+                Some(Input::Move(_)) | None => {}
+                Some(other) => {
+                    if !self.handle(other) {
+                        return Ok(None);
+                    }
+                    self.draw(terminal, screen, KEYS)?;
+                }
+            }
+        }
+    }
+
+    // Plays until the game is over or quit. Returns whether it was played to the end.
+    pub fn play(&mut self) -> io::Result<bool> {
+        let mut terminal = Terminal::new()?;
+        let mut screen = Screen::default();
+
+        while !self.arcade.is_over() {
+            self.draw(&mut terminal, &mut screen, KEYS)?;
+
+            match self.read_joystick(&mut terminal, &mut screen)? {
+                Some(joystick) => self.arcade.tick(joystick),
+                None => return Ok(false),
+            }
+        }
+
+        self.note = if self.arcade.won() {
+            "every block is broken, you win".to_string()
+        } else {
+            "game over".to_string()
+        };
+        self.draw(&mut terminal, &mut screen, "press any key")?;
+        watch::any_key()?;
+
+        return Ok(true);
+    }
+}
+
+#[test]
+fn test_input() {
+    let key = |code: KeyCode| KeyEvent::new(code, KeyModifiers::NONE);
+
+    assert_eq!(input(key(KeyCode::Left)), Some(Input::Move(Joystick::Left)));
+    assert_eq!(
+        input(key(KeyCode::Char('d'))),
+        Some(Input::Move(Joystick::Right))
+    );
+    assert_eq!(input(key(KeyCode::Char(' '))), Some(Input::Pause));
+    assert_eq!(input(key(KeyCode::Char('s'))), Some(Input::Save));
+    assert_eq!(input(key(KeyCode::Char('l'))), Some(Input::Load));
+    assert_eq!(input(key(KeyCode::Esc)), Some(Input::Quit));
+    assert_eq!(input(key(KeyCode::Up)), None);
+    assert_eq!(
+        input(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+        Some(Input::Quit)
+    );
+}
@@ -0,0 +1,578 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+
+use crate::grid::{Bounds, Grid};
+use crate::render::{self, Pixel, PixelStyle, Style};
+
+// Records a simulation one frame at a time, so it can be watched afterwards instead of scrolling
+// past on the terminal. Only the cells that changed since the previous frame are kept. Long runs
+// can keep just every nth frame; the last frame is always kept, so the final state isn't lost.
+
+pub const FORMATS: [&str; 4] = ["gif", "apng", "cast", "ppm"];
+
+// Anything that wants to see a simulation frame by frame, like the recorder or the live viewer.
+pub trait Observer {
+    // meta data is shown alongside the picture, such as the score or the droid position. Returns
+    // false if the simulation should stop early.
+    fn frame(&mut self, grid: Grid<Pixel>, meta: &[(&str, String)]) -> bool;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    // which capture this was, counting skipped ones
+    pub index: usize,
+
+    // since the recorder was made
+    pub elapsed: Duration,
+
+    // such as the score or the droid position, in the order given
+    pub meta: Vec<(String, String)>,
+
+    // cells that differ from the previous frame
+    pub changes: Vec<((i32, i32), Pixel)>,
+}
+
+// A frame with the whole picture put back together.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    pub index: usize,
+    pub elapsed: Duration,
+    pub meta: Vec<(String, String)>,
+    pub grid: Grid<Pixel>,
+}
+
+impl Snapshot {
+    // the meta data on one line, "score: 12  droid: 3,4"
+    pub fn status(&self) -> String {
+        let parts: Vec<String> = self
+            .meta
+            .iter()
+            .map(|(k, v)| format!("{}: {}", k, v))
+            .collect();
+        return parts.join("  ");
+    }
+}
+
+pub struct Recorder {
+    every: usize,
+    captured: usize,
+    start: Instant,
+
+    // the picture as of the last kept frame
+    current: Grid<Pixel>,
+    frames: Vec<Frame>,
+
+    // the latest capture, if it was skipped
+    skipped: Option<Snapshot>,
+}
+
+impl Recorder {
+    // keeps one in every `every` captures
+    pub fn new(every: usize) -> Recorder {
+        assert!(every > 0, "can't keep one frame in every 0");
+
+        return Recorder {
+            every,
+            captured: 0,
+            start: Instant::now(),
+            current: Grid::new(Pixel {
+                glyph: ' ',
+                color: render::Rgb::BLACK,
+            }),
+            frames: Vec::new(),
+            skipped: None,
+        };
+    }
+
+    pub fn capture<T: Clone>(
+        &mut self,
+        grid: &Grid<T>,
+        style: &dyn Style<T>,
+        meta: &[(&str, String)],
+    ) {
+        self.capture_pixels(render::pixels(grid, style), meta);
+    }
+
+    // for pictures with something drawn over the grid, like the droid
+    pub fn capture_pixels(&mut self, grid: Grid<Pixel>, meta: &[(&str, String)]) {
+        let index = self.captured;
+        let elapsed = self.start.elapsed();
+        let meta: Vec<(String, String)> = meta
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.clone()))
+            .collect();
+
+        self.captured += 1;
+
+        if !index.is_multiple_of(self.every) {
+            self.skipped = Some(Snapshot {
+                index,
+                elapsed,
+                meta,
+                grid,
+            });
+            return;
+        }
+
+        self.skipped = None;
+
+        if self.frames.is_empty() {
+            self.current = Grid::new(*grid.default_value());
+        }
+
+        let mut changes: Vec<((i32, i32), Pixel)> = Vec::new();
+        for (p, v) in grid.iter() {
+            if self.current.get(p.0, p.1) != v {
+                changes.push((p, *v));
+            }
+        }
+        // cells that are gone read as the default now
+        for (p, v) in self.current.iter() {
+            if grid.get(p.0, p.1) != v {
+                changes.push((p, *grid.get(p.0, p.1)));
+            }
+        }
+        // the same order every time, whatever order the grid keeps its cells in
+        changes.sort_by_key(|((x, y), _)| (*y, *x));
+        changes.dedup_by_key(|(p, _)| *p);
+
+        for (p, v) in changes.iter() {
+            self.current.set(p.0, p.1, *v);
+        }
+
+        self.frames.push(Frame {
+            index,
+            elapsed,
+            meta,
+            changes,
+        });
+    }
+
+    pub fn frames(&self) -> &[Frame] {
+        return &self.frames;
+    }
+
+    // how many frames will be exported
+    pub fn len(&self) -> usize {
+        return self.frames.len() + self.skipped.iter().count();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.len() == 0;
+    }
+
+    // every kept frame as a whole picture, including the last one even if it was skipped
+    pub fn snapshots(&self) -> Vec<Snapshot> {
+        let mut output: Vec<Snapshot> = Vec::with_capacity(self.len());
+
+        if !self.frames.is_empty() {
+            let mut grid: Grid<Pixel> = Grid::new(*self.current.default_value());
+
+            for frame in self.frames.iter() {
+                for (p, v) in frame.changes.iter() {
+                    grid.set(p.0, p.1, *v);
+                }
+                output.push(Snapshot {
+                    index: frame.index,
+                    elapsed: frame.elapsed,
+                    meta: frame.meta.clone(),
+                    grid: grid.clone(),
+                });
+            }
+        }
+
+        if let Some(skipped) = &self.skipped {
+            output.push(skipped.clone());
+        }
+
+        return output;
+    }
+}
+
+impl Observer for Recorder {
+    fn frame(&mut self, grid: Grid<Pixel>, meta: &[(&str, String)]) -> bool {
+        self.capture_pixels(grid, meta);
+        return true;
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecordError {
+    Empty,
+
+    // GIFs have a palette of 256 colours at most
+    TooManyColors(usize),
+
+    // GIFs are at most 65535 pixels across
+    TooLarge(usize, usize),
+}
+
+impl fmt::Display for RecordError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RecordError::Empty => write!(f, "nothing was recorded"),
+            RecordError::TooManyColors(n) => {
+                write!(f, "{} colours is too many for a GIF, 256 at most", n)
+            }
+            RecordError::TooLarge(w, h) => write!(f, "{}x{} is too large for a GIF", w, h),
+        }
+    }
+}
+
+impl std::error::Error for RecordError {}
+
+// Every frame is drawn at the same size, big enough for all of them.
+fn common_bounds(snapshots: &[Snapshot]) -> Bounds {
+    let mut bounds: Option<Bounds> = None;
+
+    for snapshot in snapshots {
+        if let Some(b) = snapshot.grid.bounds() {
+            match &mut bounds {
+                Some(bounds) => {
+                    bounds.include(b.min_x, b.min_y);
+                    bounds.include(b.max_x, b.max_y);
+                }
+                None => bounds = Some(b),
+            }
+        }
+    }
+
+    return bounds.unwrap_or_else(|| Bounds::point(0, 0));
+}
+
+// How long each frame stays up: a fixed delay, or None to replay at the speed it was recorded.
+fn durations(snapshots: &[Snapshot], delay: Option<Duration>) -> Vec<Duration> {
+    return (0..snapshots.len())
+        .map(|i| match delay {
+            Some(delay) => delay,
+            None => match snapshots.get(i + 1) {
+                Some(next) => next.elapsed.saturating_sub(snapshots[i].elapsed),
+                None => Duration::from_millis(1000),
+            },
+        })
+        .collect();
+}
+
+pub fn gif(
+    recorder: &Recorder,
+    scale: usize,
+    delay: Option<Duration>,
+) -> Result<Vec<u8>, RecordError> {
+    let snapshots = recorder.snapshots();
+    if snapshots.is_empty() {
+        return Err(RecordError::Empty);
+    }
+
+    let bounds = common_bounds(&snapshots);
+    let width = bounds.width() * scale;
+    let height = bounds.height() * scale;
+    if width > u16::MAX as usize || height > u16::MAX as usize {
+        return Err(RecordError::TooLarge(width, height));
+    }
+
+    // the styles only use a handful of colours, so they make the palette as they are
+    let mut palette: BTreeMap<(u8, u8, u8), u8> = BTreeMap::new();
+    for snapshot in snapshots.iter() {
+        for (_, pixel) in snapshot.grid.iter() {
+            let c = pixel.color;
+            palette.insert((c.0, c.1, c.2), 0);
+        }
+        let c = snapshot.grid.default_value().color;
+        palette.insert((c.0, c.1, c.2), 0);
+    }
+    if palette.len() > 256 {
+        return Err(RecordError::TooManyColors(palette.len()));
+    }
+
+    let mut colors: Vec<u8> = Vec::with_capacity(palette.len() * 3);
+    for (i, (c, index)) in palette.iter_mut().enumerate() {
+        *index = i as u8;
+        colors.extend_from_slice(&[c.0, c.1, c.2]);
+    }
+
+    let mut output: Vec<u8> = Vec::new();
+    {
+        // writing into memory can't fail, and the sizes were checked above
+        let mut encoder = gif::Encoder::new(&mut output, width as u16, height as u16, &colors)
+            .expect("gif header");
+        encoder
+            .set_repeat(gif::Repeat::Infinite)
+            .expect("gif repeat");
+
+        for (snapshot, duration) in snapshots.iter().zip(durations(&snapshots, delay)) {
+            let (_, _, rgb) = render::rgb_pixels(&snapshot.grid, &PixelStyle, bounds, scale);
+            let indexed: Vec<u8> = rgb
+                .chunks(3)
+                .map(|c| palette[&(c[0], c[1], c[2])])
+                .collect();
+
+            let mut frame =
+                gif::Frame::from_indexed_pixels(width as u16, height as u16, indexed, None);
+            // in hundredths of a second, and most viewers won't go faster than 2
+            frame.delay = (duration.as_millis() / 10).clamp(2, u16::MAX as u128) as u16;
+            encoder.write_frame(&frame).expect("gif frame");
+        }
+    }
+
+    return Ok(output);
+}
+
+pub fn apng(
+    recorder: &Recorder,
+    scale: usize,
+    delay: Option<Duration>,
+) -> Result<Vec<u8>, RecordError> {
+    let snapshots = recorder.snapshots();
+    if snapshots.is_empty() {
+        return Err(RecordError::Empty);
+    }
+
+    let bounds = common_bounds(&snapshots);
+    let width = bounds.width() * scale;
+    let height = bounds.height() * scale;
+
+    let mut output: Vec<u8> = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut output, width as u32, height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        // 0 plays forever
+        encoder
+            .set_animated(snapshots.len() as u32, 0)
+            .expect("apng frame count");
+
+        let mut writer = encoder.write_header().expect("png header");
+
+        for (snapshot, duration) in snapshots.iter().zip(durations(&snapshots, delay)) {
+            let millis = duration.as_millis().min(u16::MAX as u128) as u16;
+            writer.set_frame_delay(millis, 1000).expect("apng delay");
+
+            let (_, _, rgb) = render::rgb_pixels(&snapshot.grid, &PixelStyle, bounds, scale);
+            writer.write_image_data(&rgb).expect("png data");
+        }
+    }
+
+    return Ok(output);
+}
+
+// An asciinema (v2) recording: the first frame is drawn in full, after that only the cells that
+// changed are redrawn. The meta data goes on a status line underneath.
+pub fn cast(recorder: &Recorder, delay: Option<Duration>) -> Result<String, RecordError> {
+    let snapshots = recorder.snapshots();
+    if snapshots.is_empty() {
+        return Err(RecordError::Empty);
+    }
+
+    let bounds = common_bounds(&snapshots);
+    let status_row = bounds.height() + 1;
+
+    let mut output = String::new();
+    let _ = writeln!(
+        output,
+        "{{\"version\": 2, \"width\": {}, \"height\": {}}}",
+        bounds.width().max(40),
+        status_row
+    );
+
+    let mut time = Duration::from_secs(0);
+    let mut previous: Option<&Snapshot> = None;
+
+    for (snapshot, duration) in snapshots.iter().zip(durations(&snapshots, delay)) {
+        let mut data = String::new();
+
+        match previous {
+            None => {
+                data.push_str("\x1b[2J\x1b[H");
+                for y in bounds.min_y..=bounds.max_y {
+                    for x in bounds.min_x..=bounds.max_x {
+                        data.push(snapshot.grid.get(x, y).glyph);
+                    }
+                    data.push_str("\r\n");
+                }
+            }
+            Some(previous) => {
+                for y in bounds.min_y..=bounds.max_y {
+                    for x in bounds.min_x..=bounds.max_x {
+                        let glyph = snapshot.grid.get(x, y).glyph;
+                        if previous.grid.get(x, y).glyph != glyph {
+                            let _ = write!(
+                                data,
+                                "\x1b[{};{}H{}",
+                                y - bounds.min_y + 1,
+                                x - bounds.min_x + 1,
+                                glyph
+                            );
+                        }
+                    }
+                }
+            }
+        }
+
+        let _ = write!(data, "\x1b[{};1H\x1b[2K{}", status_row, snapshot.status());
+
+        let _ = writeln!(
+            output,
+            "[{:.6}, \"o\", {}]",
+            time.as_secs_f64(),
+            serde_json::to_string(&data).expect("strings serialize")
+        );
+
+        time += duration;
+        previous = Some(snapshot);
+    }
+
+    return Ok(output);
+}
+
+// One PPM per frame, all the same size, plus an index of when each was taken and its meta data.
+pub fn ppm_frames(
+    recorder: &Recorder,
+    scale: usize,
+) -> Result<(Vec<Vec<u8>>, String), RecordError> {
+    let snapshots = recorder.snapshots();
+    if snapshots.is_empty() {
+        return Err(RecordError::Empty);
+    }
+
+    let bounds = common_bounds(&snapshots);
+
+    let mut images: Vec<Vec<u8>> = Vec::with_capacity(snapshots.len());
+    let mut index = String::from("frame\tcapture\telapsed_ms\tstatus\n");
+
+    for (i, snapshot) in snapshots.iter().enumerate() {
+        let (width, height, rgb) = render::rgb_pixels(&snapshot.grid, &PixelStyle, bounds, scale);
+
+        let mut image = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+        image.extend_from_slice(&rgb);
+        images.push(image);
+
+        let _ = writeln!(
+            index,
+            "{}\t{}\t{}\t{}",
+            i,
+            snapshot.index,
+            snapshot.elapsed.as_millis(),
+            snapshot.status()
+        );
+    }
+
+    return Ok((images, index));
+}
+
+#[cfg(test)]
+fn test_recorder() -> Recorder {
+    use crate::render::Rgb;
+
+    struct OnOff;
+
+    impl Style<i32> for OnOff {
+        fn glyph(&self, cell: &i32) -> char {
+            return if *cell == 1 { '#' } else { '.' };
+        }
+
+        fn color(&self, cell: &i32) -> Rgb {
+            return if *cell == 1 { Rgb::WHITE } else { Rgb::BLACK };
+        }
+    }
+
+    // a dot walking right, five steps, keeping every other one
+    let mut recorder = Recorder::new(2);
+    let mut grid: Grid<i32> = Grid::new(0);
+
+    for step in 0..5 {
+        if step > 0 {
+            grid.set(step - 1, 0, 0);
+        }
+        grid.set(step, 0, 1);
+        recorder.capture(&grid, &OnOff, &[("step", step.to_string())]);
+    }
+
+    return recorder;
+}
+
+#[test]
+fn test_recorder_diffs() {
+    let recorder = test_recorder();
+
+    // steps 0, 2 and 4
+    let frames = recorder.frames();
+    assert_eq!(frames.len(), 3);
+    assert_eq!(
+        frames.iter().map(|f| f.index).collect::<Vec<usize>>(),
+        vec![0, 2, 4]
+    );
+    assert_eq!(frames[0].changes.len(), 1);
+
+    // the dot moved two cells: one goes out, one comes on
+    let changes: Vec<((i32, i32), char)> = frames[1]
+        .changes
+        .iter()
+        .map(|(p, v)| (*p, v.glyph))
+        .collect();
+    assert_eq!(changes, vec![((0, 0), '.'), ((2, 0), '#')]);
+
+    let snapshots = recorder.snapshots();
+    assert_eq!(snapshots.len(), 3);
+    assert_eq!(snapshots[2].status(), "step: 4");
+    assert_eq!(
+        render::ascii(&snapshots[2].grid, &PixelStyle),
+        "....#\n".to_string()
+    );
+
+    // with a skipped last capture, the final state is still kept
+    let mut recorder = test_recorder();
+    let mut grid: Grid<i32> = Grid::new(0);
+    grid.set(5, 0, 1);
+    recorder.capture_pixels(
+        grid.map(|v| Pixel {
+            glyph: if *v == 1 { '#' } else { '.' },
+            color: render::Rgb::BLACK,
+        }),
+        &[("step", "5".to_string())],
+    );
+    assert_eq!(recorder.len(), 4);
+    assert_eq!(recorder.snapshots()[3].index, 5);
+}
+
+#[test]
+fn test_exports() {
+    let recorder = test_recorder();
+    let delay = Some(Duration::from_millis(100));
+
+    let gif = gif(&recorder, 2, delay).unwrap();
+    assert_eq!(&gif[..6], b"GIF89a");
+    let mut decoder = gif::DecodeOptions::new().read_info(&gif[..]).unwrap();
+    assert_eq!((decoder.width(), decoder.height()), (10, 2));
+    let mut count = 0;
+    while let Some(frame) = decoder.read_next_frame().unwrap() {
+        assert_eq!(frame.delay, 10);
+        count += 1;
+    }
+    assert_eq!(count, 3);
+
+    let apng = apng(&recorder, 1, delay).unwrap();
+    let reader = png::Decoder::new(&apng[..]).read_info().unwrap();
+    assert_eq!(reader.info().animation_control().unwrap().num_frames, 3);
+
+    let recording = cast(&recorder, delay).unwrap();
+    let lines: Vec<&str> = recording.lines().collect();
+    assert_eq!(lines.len(), 4);
+    assert_eq!(lines[0], "{\"version\": 2, \"width\": 40, \"height\": 2}");
+    let event: serde_json::Value = serde_json::from_str(lines[2]).unwrap();
+    assert_eq!(event[0], 0.1);
+    assert_eq!(
+        event[2],
+        "\u{1b}[1;1H.\u{1b}[1;3H#\u{1b}[2;1H\u{1b}[2Kstep: 2"
+    );
+
+    let (images, index) = ppm_frames(&recorder, 1).unwrap();
+    assert_eq!(images.len(), 3);
+    assert!(images
+        .iter()
+        .all(|image| image.starts_with(b"P6\n5 1\n255\n")));
+    assert_eq!(index.lines().nth(3).unwrap().split('\t').nth(1), Some("4"));
+
+    assert_eq!(cast(&Recorder::new(1), None), Err(RecordError::Empty));
+}
@@ -0,0 +1,406 @@
+use std::fmt::Write;
+
+use crate::grid::{Bounds, Grid};
+
+// Turns a grid into text or an image. What each cell looks like is up to a Style, which every day
+// defines once for its own cell type; the renderers only deal with glyphs and colours.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+
+    pub fn hex(&self) -> String {
+        return format!("#{:02x}{:02x}{:02x}", self.0, self.1, self.2);
+    }
+}
+
+pub trait Style<T> {
+    // for text renderers
+    fn glyph(&self, cell: &T) -> char;
+
+    // for colour renderers
+    fn color(&self, cell: &T) -> Rgb;
+}
+
+// A cell that already knows how it looks, so a grid of them can be rendered without knowing what
+// the cells originally were.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pixel {
+    pub glyph: char,
+    pub color: Rgb,
+}
+
+pub struct PixelStyle;
+
+impl Style<Pixel> for PixelStyle {
+    fn glyph(&self, cell: &Pixel) -> char {
+        return cell.glyph;
+    }
+
+    fn color(&self, cell: &Pixel) -> Rgb {
+        return cell.color;
+    }
+}
+
+// the grid with every cell's look worked out
+pub fn pixels<T: Clone>(grid: &Grid<T>, style: &dyn Style<T>) -> Grid<Pixel> {
+    return grid.map(|cell| Pixel {
+        glyph: style.glyph(cell),
+        color: style.color(cell),
+    });
+}
+
+// A named, styled grid: the hull, the arcade screen, the explored maze.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Picture {
+    pub name: String,
+    pub grid: Grid<Pixel>,
+}
+
+impl Picture {
+    pub fn new<T: Clone>(name: &str, grid: &Grid<T>, style: &dyn Style<T>) -> Picture {
+        return Picture {
+            name: name.to_string(),
+            grid: pixels(grid, style),
+        };
+    }
+
+    pub fn render(&self, renderer: &dyn Renderer<Pixel>) -> Vec<u8> {
+        return renderer.render(&self.grid, &PixelStyle);
+    }
+}
+
+// A path drawn over a picture: every cell along it takes the line's colour, and those showing the
+// floor take its glyph too, so anything else on the path still shows.
+pub fn overlay(grid: &mut Grid<Pixel>, path: &[(i32, i32)], floor: char, line: Pixel) {
+    for (x, y) in path {
+        let mut pixel = *grid.get(*x, *y);
+        if pixel.glyph == floor {
+            pixel.glyph = line.glyph;
+        }
+        pixel.color = line.color;
+        grid.set(*x, *y, pixel);
+    }
+}
+
+pub trait Renderer<T> {
+    // for naming saved files
+    fn extension(&self) -> &'static str;
+
+    fn render(&self, grid: &Grid<T>, style: &dyn Style<T>) -> Vec<u8>;
+}
+
+// the grid as text, using the style's glyphs
+pub fn ascii<T: Clone>(grid: &Grid<T>, style: &dyn Style<T>) -> String {
+    return String::from_utf8(Ascii.render(grid, style)).expect("glyphs are valid UTF-8");
+}
+
+pub const RENDERERS: [&str; 5] = ["ascii", "ansi", "ppm", "png", "svg"];
+
+// image renderers draw each cell as a scale by scale square
+pub fn renderer<T: Clone>(name: &str, scale: usize) -> Option<Box<dyn Renderer<T>>> {
+    match name {
+        "ascii" => return Some(Box::new(Ascii)),
+        "ansi" => return Some(Box::new(Ansi)),
+        "ppm" => return Some(Box::new(Ppm { scale })),
+        "png" => return Some(Box::new(Png { scale })),
+        "svg" => return Some(Box::new(Svg { scale })),
+        _ => return None,
+    }
+}
+
+// Images can't be empty, so an empty grid is drawn as a single cell of the default value.
+pub fn image_bounds<T: Clone>(grid: &Grid<T>) -> Bounds {
+    return grid.bounds().unwrap_or_else(|| Bounds::point(0, 0));
+}
+
+// Row-major RGB bytes of the cells inside bounds, scale pixels per cell in each direction. The
+// bounds don't have to be the grid's own, animations draw every frame at the size of the largest.
+pub fn rgb_pixels<T: Clone>(
+    grid: &Grid<T>,
+    style: &dyn Style<T>,
+    bounds: Bounds,
+    scale: usize,
+) -> (usize, usize, Vec<u8>) {
+    let width = bounds.width() * scale;
+    let height = bounds.height() * scale;
+
+    let mut data: Vec<u8> = Vec::with_capacity(width * height * 3);
+
+    for y in bounds.min_y..=bounds.max_y {
+        let mut row: Vec<u8> = Vec::with_capacity(width * 3);
+        for x in bounds.min_x..=bounds.max_x {
+            let c = style.color(grid.get(x, y));
+            for _ in 0..scale {
+                row.extend_from_slice(&[c.0, c.1, c.2]);
+            }
+        }
+        for _ in 0..scale {
+            data.extend_from_slice(&row);
+        }
+    }
+
+    return (width, height, data);
+}
+
+// one character per cell, one line per row
+pub struct Ascii;
+
+impl<T: Clone> Renderer<T> for Ascii {
+    fn extension(&self) -> &'static str {
+        return "txt";
+    }
+
+    fn render(&self, grid: &Grid<T>, style: &dyn Style<T>) -> Vec<u8> {
+        let mut output = String::new();
+
+        if let Some(bounds) = grid.bounds() {
+            for y in bounds.min_y..=bounds.max_y {
+                for x in bounds.min_x..=bounds.max_x {
+                    output.push(style.glyph(grid.get(x, y)));
+                }
+                output.push('\n');
+            }
+        }
+
+        return output.into_bytes();
+    }
+}
+
+// 24-bit colour terminal output: each cell is two spaces on a coloured background, which comes
+// out roughly square in most terminal fonts
+pub struct Ansi;
+
+impl<T: Clone> Renderer<T> for Ansi {
+    fn extension(&self) -> &'static str {
+        return "ans";
+    }
+
+    fn render(&self, grid: &Grid<T>, style: &dyn Style<T>) -> Vec<u8> {
+        let mut output = String::new();
+
+        if let Some(bounds) = grid.bounds() {
+            for y in bounds.min_y..=bounds.max_y {
+                let mut current: Option<Rgb> = None;
+
+                for x in bounds.min_x..=bounds.max_x {
+                    let c = style.color(grid.get(x, y));
+
+                    // only switch colour when it changes
+                    if current != Some(c) {
+                        let _ = write!(output, "\x1b[48;2;{};{};{}m", c.0, c.1, c.2);
+                        current = Some(c);
+                    }
+                    output.push_str("  ");
+                }
+                output.push_str("\x1b[0m\n");
+            }
+        }
+
+        return output.into_bytes();
+    }
+}
+
+// binary PPM (P6)
+pub struct Ppm {
+    pub scale: usize,
+}
+
+impl<T: Clone> Renderer<T> for Ppm {
+    fn extension(&self) -> &'static str {
+        return "ppm";
+    }
+
+    fn render(&self, grid: &Grid<T>, style: &dyn Style<T>) -> Vec<u8> {
+        let (width, height, data) = rgb_pixels(grid, style, image_bounds(grid), self.scale);
+
+        let mut output = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+        output.extend_from_slice(&data);
+
+        return output;
+    }
+}
+
+pub struct Png {
+    pub scale: usize,
+}
+
+impl<T: Clone> Renderer<T> for Png {
+    fn extension(&self) -> &'static str {
+        return "png";
+    }
+
+    fn render(&self, grid: &Grid<T>, style: &dyn Style<T>) -> Vec<u8> {
+        let (width, height, data) = rgb_pixels(grid, style, image_bounds(grid), self.scale);
+
+        let mut output: Vec<u8> = Vec::new();
+        {
+            let mut encoder = png::Encoder::new(&mut output, width as u32, height as u32);
+            encoder.set_color(png::ColorType::Rgb);
+            encoder.set_depth(png::BitDepth::Eight);
+
+            // writing into memory can't fail, and the size always matches the data
+            let mut writer = encoder.write_header().expect("png header");
+            writer.write_image_data(&data).expect("png data");
+        }
+
+        return output;
+    }
+}
+
+// one rectangle per horizontal run of same-coloured cells
+pub struct Svg {
+    pub scale: usize,
+}
+
+impl<T: Clone> Renderer<T> for Svg {
+    fn extension(&self) -> &'static str {
+        return "svg";
+    }
+
+    fn render(&self, grid: &Grid<T>, style: &dyn Style<T>) -> Vec<u8> {
+        let bounds = image_bounds(grid);
+        let scale = self.scale;
+
+        let mut output = String::new();
+        let _ = writeln!(
+            output,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" shape-rendering=\"crispEdges\">",
+            bounds.width() * scale,
+            bounds.height() * scale
+        );
+
+        for (row, y) in (bounds.min_y..=bounds.max_y).enumerate() {
+            let mut x = bounds.min_x;
+
+            while x <= bounds.max_x {
+                let c = style.color(grid.get(x, y));
+
+                let mut run = 1;
+                while x + run <= bounds.max_x && style.color(grid.get(x + run, y)) == c {
+                    run += 1;
+                }
+
+                let _ = writeln!(
+                    output,
+                    "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+                    (x - bounds.min_x) as usize * scale,
+                    row * scale,
+                    run as usize * scale,
+                    scale,
+                    c.hex()
+                );
+
+                x += run;
+            }
+        }
+
+        output.push_str("</svg>\n");
+
+        return output.into_bytes();
+    }
+}
+
+#[cfg(test)]
+struct OnOff;
+
+#[cfg(test)]
+impl Style<i32> for OnOff {
+    fn glyph(&self, cell: &i32) -> char {
+        return if *cell == 1 { '#' } else { '.' };
+    }
+
+    fn color(&self, cell: &i32) -> Rgb {
+        return if *cell == 1 { Rgb::WHITE } else { Rgb::BLACK };
+    }
+}
+
+#[cfg(test)]
+fn test_grid() -> Grid<i32> {
+    // #.
+    // .#
+    // ##
+    let mut grid = Grid::new(0);
+    grid.set(0, 0, 1);
+    grid.set(1, 1, 1);
+    grid.set(0, 2, 1);
+    grid.set(1, 2, 1);
+    return grid;
+}
+
+#[test]
+fn test_text_renderers() {
+    let grid = test_grid();
+
+    assert_eq!(Ascii.render(&grid, &OnOff), b"#.\n.#\n##\n".to_vec());
+    assert_eq!(Ascii.render(&Grid::new(0), &OnOff), b"".to_vec());
+
+    let ansi = String::from_utf8(Ansi.render(&grid, &OnOff)).unwrap();
+    let lines: Vec<&str> = ansi.lines().collect();
+    assert_eq!(
+        lines[0],
+        "\x1b[48;2;255;255;255m  \x1b[48;2;0;0;0m  \x1b[0m"
+    );
+
+    // the colour isn't repeated for a run of the same colour
+    assert_eq!(lines[2], "\x1b[48;2;255;255;255m    \x1b[0m");
+}
+
+#[test]
+fn test_image_renderers() {
+    let grid = test_grid();
+
+    let ppm = Ppm { scale: 2 }.render(&grid, &OnOff);
+    let header = b"P6\n4 6\n255\n";
+    assert_eq!(&ppm[..header.len()], header);
+    assert_eq!(ppm.len(), header.len() + 4 * 6 * 3);
+
+    // top left 2x2 block is white, top right is black
+    let data = &ppm[header.len()..];
+    assert_eq!(&data[0..6], &[255; 6]);
+    assert_eq!(&data[6..12], &[0; 6]);
+    assert_eq!(&data[12..18], &[255; 6]);
+
+    let png = Png { scale: 1 }.render(&grid, &OnOff);
+    assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+
+    let decoder = png::Decoder::new(&png[..]);
+    let mut reader = decoder.read_info().unwrap();
+    let mut buf = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buf).unwrap();
+    assert_eq!((info.width, info.height), (2, 3));
+    assert_eq!(&buf[..6], &[255, 255, 255, 0, 0, 0]);
+
+    let svg = String::from_utf8(Svg { scale: 10 }.render(&grid, &OnOff)).unwrap();
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"20\" height=\"30\""));
+    assert!(svg.contains("<rect x=\"0\" y=\"20\" width=\"20\" height=\"10\" fill=\"#ffffff\"/>"));
+    assert_eq!(svg.matches("<rect").count(), 5);
+
+    // empty grids still make a valid image
+    let png = Png { scale: 1 }.render(&Grid::new(0), &OnOff);
+    assert!(png::Decoder::new(&png[..]).read_info().is_ok());
+}
+
+#[test]
+fn test_picture() {
+    let picture = Picture::new("hull", &test_grid(), &OnOff);
+    assert_eq!(picture.render(&Ascii), b"#.\n.#\n##\n".to_vec());
+
+    // a path from the bottom left, round the corner
+    let mut picture = Picture::new("hull", &test_grid(), &OnOff);
+    let line = Pixel {
+        glyph: '*',
+        color: Rgb(255, 0, 0),
+    };
+    overlay(&mut picture.grid, &[(0, 2), (1, 2), (1, 1)], '#', line);
+    assert_eq!(picture.render(&Ascii), b"#.\n.*\n**\n".to_vec());
+    assert_eq!(picture.grid.get(0, 0).color, Rgb::WHITE);
+    assert_eq!(picture.grid.get(1, 1).color, Rgb(255, 0, 0));
+
+    let svg = renderer::<Pixel>("svg", 1).unwrap();
+    assert_eq!(svg.extension(), "svg");
+    assert!(renderer::<Pixel>("bmp", 1).is_none());
+}
@@ -0,0 +1,108 @@
+use std::collections::BTreeMap;
+use std::time::Instant;
+
+use serde::Serialize;
+
+use crate::loader::Source;
+use crate::solution::{Answer, Artifact, Solution};
+
+// Everything a single run of one day produced, ready to be printed as text or serialized as JSON.
+#[derive(Debug, Clone, Serialize)]
+pub struct DayReport {
+    pub day: u32,
+    pub title: String,
+    pub input: String,
+
+    // keyed by "part1" and "part2"
+    pub answers: BTreeMap<String, Answer>,
+
+    // nanoseconds, keyed by "parse", "part1" and "part2"
+    pub timings_ns: BTreeMap<String, u64>,
+
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub artifacts: Vec<Artifact>,
+
+    // why the day could not be run, if it couldn't
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+pub fn run(
+    solution: &mut dyn Solution,
+    source: &Source,
+    parts: &[u32],
+    with_artifacts: bool,
+) -> DayReport {
+    let mut report = DayReport {
+        day: solution.day(),
+        title: solution.title().to_string(),
+        input: source.to_string(),
+        answers: BTreeMap::new(),
+        timings_ns: BTreeMap::new(),
+        artifacts: Vec::new(),
+        error: None,
+    };
+
+    let start = Instant::now();
+    if let Err(e) = solution.parse(source) {
+        report.error = Some(e.to_string());
+        return report;
+    }
+    report
+        .timings_ns
+        .insert("parse".to_string(), start.elapsed().as_nanos() as u64);
+
+    for part in parts {
+        let name = format!("part{}", part);
+
+        let start = Instant::now();
+        let answer = solution.part(*part);
+        report
+            .timings_ns
+            .insert(name.clone(), start.elapsed().as_nanos() as u64);
+
+        report.answers.insert(name, answer);
+    }
+
+    if with_artifacts {
+        report.artifacts = solution.artifacts();
+    }
+
+    return report;
+}
+
+#[test]
+fn test_report_json() {
+    use crate::days;
+
+    let path = std::env::temp_dir().join(format!("report-{}.input", std::process::id()));
+    std::fs::write(&path, "12\n14\n").unwrap();
+    let source = Source::from_arg(path.to_str().unwrap());
+
+    let mut solution = days::get(1).unwrap();
+    let report = run(solution.as_mut(), &source, &[1, 2], true);
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(report.error, None);
+    assert_eq!(report.answers["part1"], Answer::Number(4));
+    assert_eq!(report.answers["part2"], Answer::Number(4));
+
+    let keys: Vec<&String> = report.timings_ns.keys().collect();
+    assert_eq!(keys, vec!["parse", "part1", "part2"]);
+
+    let json: serde_json::Value = serde_json::to_value(&report).unwrap();
+    assert_eq!(json["day"], 1);
+    assert_eq!(json["answers"]["part2"], 4);
+    assert!(json.get("artifacts").is_none());
+    assert!(json.get("error").is_none());
+
+    let mut solution = days::get(1).unwrap();
+    let report = run(
+        solution.as_mut(),
+        &Source::from_arg("does-not-exist.input"),
+        &[1],
+        false,
+    );
+    assert!(report.answers.is_empty());
+    assert!(report.error.unwrap().contains("does-not-exist.input"));
+}
@@ -0,0 +1,637 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Debug;
+use std::hash::Hash;
+
+use crate::grid::{Grid, Voxels, HEX_OFFSETS};
+use crate::intcode::{Machine, Step};
+
+// The emergency hull painting robot from day 11. It needs to be able to move around on the grid of
+// square panels on the side of your ship, detect the color of its current panel, and paint its
+// current panel black or white. What to paint and where to turn comes from an instruction source,
+// usually the Intcode program, but a fixed script works too.
+//
+// The panels don't have to be square. A Space says where the robot can be, which ways it can face
+// and how it turns: the day 11 hull is Square, Hex is a hull of hexagons and Cube is a block of
+// voxels that the robot can fly through.
+
+// 0 means black, 1 means white
+pub const BLACK: i32 = 0;
+pub const WHITE: i32 = 1;
+
+// The panels a robot paints on.
+pub trait Hull<P> {
+    fn blank() -> Self;
+    fn color(&self, p: P) -> i32;
+    fn paint(&mut self, p: P, color: i32);
+}
+
+impl Hull<(i32, i32)> for Grid<i32> {
+    fn blank() -> Self {
+        return Grid::new(BLACK);
+    }
+
+    fn color(&self, p: (i32, i32)) -> i32 {
+        return *self.get(p.0, p.1);
+    }
+
+    fn paint(&mut self, p: (i32, i32), color: i32) {
+        self.set(p.0, p.1, color);
+    }
+}
+
+impl Hull<(i32, i32, i32)> for Voxels<i32> {
+    fn blank() -> Self {
+        return Voxels::new(BLACK);
+    }
+
+    fn color(&self, p: (i32, i32, i32)) -> i32 {
+        return *self.get(p.0, p.1, p.2);
+    }
+
+    fn paint(&mut self, p: (i32, i32, i32), color: i32) {
+        self.set(p.0, p.1, p.2, color);
+    }
+}
+
+// How a turn is sent by a program: as this many output values after the colour.
+pub trait TurnCode: Sized {
+    const OUTPUTS: usize;
+
+    fn decode(values: &[i64]) -> Self;
+}
+
+pub trait Space {
+    type Position: Copy + Eq + Hash + Debug;
+    type Heading: Copy + Eq + Debug;
+    type Turn: TurnCode + Copy + Debug;
+    type Hull: Hull<Self::Position>;
+
+    // where the robot starts, and which way it faces
+    fn start() -> (Self::Position, Self::Heading);
+
+    fn turn(heading: Self::Heading, turn: Self::Turn) -> Self::Heading;
+
+    // one panel on
+    fn forward(p: Self::Position, heading: Self::Heading) -> Self::Position;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Heading {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Heading {
+    pub fn turn_left(self) -> Heading {
+        match self {
+            Heading::Up => return Heading::Left,
+            Heading::Right => return Heading::Up,
+            Heading::Down => return Heading::Right,
+            Heading::Left => return Heading::Down,
+        }
+    }
+
+    pub fn turn_right(self) -> Heading {
+        match self {
+            Heading::Up => return Heading::Right,
+            Heading::Right => return Heading::Down,
+            Heading::Down => return Heading::Left,
+            Heading::Left => return Heading::Up,
+        }
+    }
+
+    // one panel forward, with y growing downwards
+    pub fn offset(self) -> (i32, i32) {
+        match self {
+            Heading::Up => return (0, -1),
+            Heading::Right => return (1, 0),
+            Heading::Down => return (0, 1),
+            Heading::Left => return (-1, 0),
+        }
+    }
+}
+
+// 0 means it should turn left 90 degrees, and 1 means it should turn right 90 degrees. On a hex
+// hull it's 60 degrees.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Turn {
+    Left,
+    Right,
+}
+
+impl Turn {
+    pub fn from_output(v: i64) -> Turn {
+        match v {
+            0 => return Turn::Left,
+            1 => return Turn::Right,
+            _ => panic!("bad turn {}", v),
+        }
+    }
+}
+
+impl TurnCode for Turn {
+    const OUTPUTS: usize = 1;
+
+    fn decode(values: &[i64]) -> Turn {
+        return Turn::from_output(values[0]);
+    }
+}
+
+// The day 11 hull.
+pub struct Square;
+
+impl Space for Square {
+    type Position = (i32, i32);
+    type Heading = Heading;
+    type Turn = Turn;
+    type Hull = Grid<i32>;
+
+    // The robot starts facing up.
+    fn start() -> ((i32, i32), Heading) {
+        return ((0, 0), Heading::Up);
+    }
+
+    fn turn(heading: Heading, turn: Turn) -> Heading {
+        match turn {
+            Turn::Left => return heading.turn_left(),
+            Turn::Right => return heading.turn_right(),
+        }
+    }
+
+    fn forward(p: (i32, i32), heading: Heading) -> (i32, i32) {
+        let (dx, dy) = heading.offset();
+        return (p.0 + dx, p.1 + dy);
+    }
+}
+
+// Flat-topped hexagons in axial coordinates, so the hull is still a Grid. Headings count clockwise
+// from north, as an index into HEX_OFFSETS, and every turn is 60 degrees.
+pub struct Hex;
+
+impl Space for Hex {
+    type Position = (i32, i32);
+    type Heading = usize;
+    type Turn = Turn;
+    type Hull = Grid<i32>;
+
+    fn start() -> ((i32, i32), usize) {
+        return ((0, 0), 0);
+    }
+
+    fn turn(heading: usize, turn: Turn) -> usize {
+        match turn {
+            Turn::Left => return (heading + 5) % 6,
+            Turn::Right => return (heading + 1) % 6,
+        }
+    }
+
+    fn forward(p: (i32, i32), heading: usize) -> (i32, i32) {
+        let (dq, dr) = HEX_OFFSETS[heading];
+        return (p.0 + dq, p.1 + dr);
+    }
+}
+
+// Which way the robot faces in three dimensions: where its nose points, and where the top of its
+// head points. Both are unit vectors along an axis, at right angles to each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Attitude {
+    pub forward: (i32, i32, i32),
+    pub up: (i32, i32, i32),
+}
+
+fn cross(a: (i32, i32, i32), b: (i32, i32, i32)) -> (i32, i32, i32) {
+    return (
+        a.1 * b.2 - a.2 * b.1,
+        a.2 * b.0 - a.0 * b.2,
+        a.0 * b.1 - a.1 * b.0,
+    );
+}
+
+fn negate(a: (i32, i32, i32)) -> (i32, i32, i32) {
+    return (-a.0, -a.1, -a.2);
+}
+
+impl Attitude {
+    // out of the robot's right hand side
+    pub fn right(&self) -> (i32, i32, i32) {
+        return cross(self.up, self.forward);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pitch {
+    Down,
+    Up,
+}
+
+// Two outputs after the colour. First the yaw: 0 turns left, 1 turns right and 2 keeps straight on.
+// Then the pitch: 0 noses down, 1 noses up and 2 stays level. Yaw happens first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Turn3 {
+    pub yaw: Option<Turn>,
+    pub pitch: Option<Pitch>,
+}
+
+impl TurnCode for Turn3 {
+    const OUTPUTS: usize = 2;
+
+    fn decode(values: &[i64]) -> Turn3 {
+        let yaw = match values[0] {
+            2 => None,
+            v => Some(Turn::from_output(v)),
+        };
+        let pitch = match values[1] {
+            0 => Some(Pitch::Down),
+            1 => Some(Pitch::Up),
+            2 => None,
+            v => panic!("bad pitch {}", v),
+        };
+        return Turn3 { yaw, pitch };
+    }
+}
+
+// Voxels, with x to the right and y down the page as on a square hull, and z coming up out of it.
+// A robot that never pitches stays on the z = 0 layer and paints it the same as Square would.
+pub struct Cube;
+
+impl Space for Cube {
+    type Position = (i32, i32, i32);
+    type Heading = Attitude;
+    type Turn = Turn3;
+    type Hull = Voxels<i32>;
+
+    fn start() -> ((i32, i32, i32), Attitude) {
+        return (
+            (0, 0, 0),
+            Attitude {
+                forward: (0, -1, 0),
+                up: (0, 0, 1),
+            },
+        );
+    }
+
+    fn turn(heading: Attitude, turn: Turn3) -> Attitude {
+        let mut a = heading;
+
+        // yaw turns about the up axis
+        match turn.yaw {
+            Some(Turn::Left) => a.forward = negate(a.right()),
+            Some(Turn::Right) => a.forward = a.right(),
+            None => {}
+        }
+
+        // pitch turns about the right hand axis
+        match turn.pitch {
+            Some(Pitch::Up) => {
+                let forward = a.forward;
+                a.forward = a.up;
+                a.up = negate(forward);
+            }
+            Some(Pitch::Down) => {
+                let forward = a.forward;
+                a.forward = negate(a.up);
+                a.up = forward;
+            }
+            None => {}
+        }
+
+        return a;
+    }
+
+    fn forward(p: (i32, i32, i32), heading: Attitude) -> (i32, i32, i32) {
+        let (dx, dy, dz) = heading.forward;
+        return (p.0 + dx, p.1 + dy, p.2 + dz);
+    }
+}
+
+// Where the robot's instructions come from.
+pub trait InstructionSource<T> {
+    // Given what the camera sees (0 over a black panel, 1 over a white one), the colour to paint and
+    // the way to turn, or None once there are no more.
+    fn next(&mut self, camera: i32) -> Option<(i32, T)>;
+}
+
+pub struct IntcodeBrain {
+    machine: Machine,
+
+    // output but not made into an instruction yet
+    pending: VecDeque<i64>,
+}
+
+impl IntcodeBrain {
+    pub fn new(program: &[i64]) -> IntcodeBrain {
+        return IntcodeBrain {
+            machine: Machine::new(program),
+            pending: VecDeque::new(),
+        };
+    }
+}
+
+impl<T: TurnCode> InstructionSource<T> for IntcodeBrain {
+    // The program uses input instructions to access the robot's camera, then it will output the
+    // color to paint the panel the robot is over, followed by the direction the robot should turn.
+    fn next(&mut self, camera: i32) -> Option<(i32, T)> {
+        // run it until it's said all of one instruction, showing it the camera whenever it looks
+        while self.pending.len() < 1 + T::OUTPUTS {
+            match self.machine.step() {
+                Step::Ran => {}
+                Step::Output(v) => self.pending.push_back(v),
+                Step::NeedsInput => self.machine.send(camera as i64),
+                Step::Halted => return None,
+            }
+        }
+
+        let color = self.pending.pop_front().unwrap();
+        let turn: Vec<i64> = self.pending.drain(..T::OUTPUTS).collect();
+
+        return Some((color as i32, T::decode(&turn)));
+    }
+}
+
+// The same instructions whatever the camera sees.
+pub struct Script<T> {
+    instructions: VecDeque<(i32, T)>,
+}
+
+impl<T: Clone> Script<T> {
+    pub fn new(instructions: &[(i32, T)]) -> Script<T> {
+        return Script {
+            instructions: instructions.iter().cloned().collect(),
+        };
+    }
+}
+
+impl<T> InstructionSource<T> for Script<T> {
+    fn next(&mut self, _camera: i32) -> Option<(i32, T)> {
+        return self.instructions.pop_front();
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    // panels painted at least once
+    pub painted: usize,
+
+    // panels painted more than once
+    pub repainted: usize,
+
+    // every paint instruction, including ones that didn't change the colour
+    pub paints: usize,
+
+    // panels moved
+    pub path_length: usize,
+}
+
+pub struct Robot<S: Space> {
+    position: S::Position,
+    heading: S::Heading,
+    hull: S::Hull,
+
+    // how many times each panel was painted
+    paint_counts: HashMap<S::Position, usize>,
+    moves: usize,
+}
+
+pub type PaintingRobot = Robot<Square>;
+pub type HexRobot = Robot<Hex>;
+pub type VoxelRobot = Robot<Cube>;
+
+impl<S: Space> Robot<S> {
+    // The robot starts on a panel of the given colour.
+    pub fn new(start_color: i32) -> Robot<S> {
+        let (position, heading) = S::start();
+
+        let mut hull = S::Hull::blank();
+        hull.paint(position, start_color);
+
+        return Robot {
+            position,
+            heading,
+            hull,
+            paint_counts: HashMap::new(),
+            moves: 0,
+        };
+    }
+
+    pub fn position(&self) -> S::Position {
+        return self.position;
+    }
+
+    pub fn heading(&self) -> S::Heading {
+        return self.heading;
+    }
+
+    pub fn hull(&self) -> &S::Hull {
+        return &self.hull;
+    }
+
+    pub fn into_hull(self) -> S::Hull {
+        return self.hull;
+    }
+
+    // the colour of the panel underneath
+    pub fn camera(&self) -> i32 {
+        return self.hull.color(self.position);
+    }
+
+    pub fn paint_count(&self, p: S::Position) -> usize {
+        return self.paint_counts.get(&p).cloned().unwrap_or(0);
+    }
+
+    // Paint the panel underneath, turn, then move forward exactly one panel.
+    pub fn step(&mut self, color: i32, turn: S::Turn) {
+        self.hull.paint(self.position, color);
+        *self.paint_counts.entry(self.position).or_insert(0) += 1;
+
+        self.heading = S::turn(self.heading, turn);
+        self.position = S::forward(self.position, self.heading);
+        self.moves += 1;
+    }
+
+    // Runs until the source has no more instructions. Returns how many steps were taken.
+    pub fn run(&mut self, source: &mut dyn InstructionSource<S::Turn>) -> usize {
+        let mut steps = 0;
+
+        while let Some((color, turn)) = source.next(self.camera()) {
+            self.step(color, turn);
+            steps += 1;
+        }
+
+        return steps;
+    }
+
+    pub fn stats(&self) -> Stats {
+        return Stats {
+            painted: self.paint_counts.len(),
+            repainted: self.paint_counts.values().filter(|n| **n > 1).count(),
+            paints: self.paint_counts.values().sum(),
+            path_length: self.moves,
+        };
+    }
+}
+
+#[test]
+fn test_turning() {
+    let headings = [Heading::Up, Heading::Right, Heading::Down, Heading::Left];
+
+    for heading in headings.iter() {
+        // a left undoes a right
+        assert_eq!(heading.turn_right().turn_left(), *heading);
+        assert_eq!(heading.turn_left().turn_right(), *heading);
+
+        // four of the same turn go all the way round
+        let mut h = *heading;
+        for _ in 0..4 {
+            h = h.turn_right();
+        }
+        assert_eq!(h, *heading);
+
+        // two turns face the other way
+        let (dx, dy) = heading.offset();
+        assert_eq!(heading.turn_left().turn_left().offset(), (-dx, -dy));
+    }
+
+    // clockwise from up
+    assert_eq!(Heading::Up.turn_right(), Heading::Right);
+    assert_eq!(Heading::Right.turn_right(), Heading::Down);
+    assert_eq!(Heading::Up.turn_left(), Heading::Left);
+    assert_eq!(Heading::Up.offset(), (0, -1));
+
+    assert_eq!(Turn::from_output(0), Turn::Left);
+    assert_eq!(Turn::from_output(1), Turn::Right);
+}
+
+#[test]
+fn test_painting_robot() {
+    // the example from the puzzle
+    let mut script = Script::new(&[
+        (WHITE, Turn::Left),
+        (BLACK, Turn::Left),
+        (WHITE, Turn::Left),
+        (WHITE, Turn::Left),
+        (BLACK, Turn::Right),
+        (WHITE, Turn::Left),
+        (WHITE, Turn::Left),
+    ]);
+
+    let mut robot = PaintingRobot::new(BLACK);
+    assert_eq!(robot.run(&mut script), 7);
+
+    assert_eq!(robot.position(), (0, -1));
+    assert_eq!(robot.heading(), Heading::Left);
+
+    let stats = robot.stats();
+    assert_eq!(stats.painted, 6);
+    assert_eq!(stats.repainted, 1);
+    assert_eq!(stats.paints, 7);
+    assert_eq!(stats.path_length, 7);
+
+    // the start panel was painted white, then back to black
+    assert_eq!(robot.paint_count((0, 0)), 2);
+    assert_eq!(*robot.hull().get(0, 0), BLACK);
+    assert_eq!(robot.paint_count((5, 5)), 0);
+
+    // .....
+    // ..#..
+    // ...#.
+    // .##..
+    // .....
+    assert_eq!(robot.hull().count_where(|c| *c == WHITE), 4);
+}
+
+#[test]
+fn test_intcode_brain() {
+    // reads the camera, paints it white and turns right, twice
+    let program = vec![3, 100, 104, 1, 104, 1, 3, 100, 104, 1, 104, 1, 99];
+
+    let mut robot = PaintingRobot::new(BLACK);
+    assert_eq!(robot.run(&mut IntcodeBrain::new(&program)), 2);
+    assert_eq!(robot.position(), (1, 1));
+    assert_eq!(robot.heading(), Heading::Down);
+    assert_eq!(robot.stats().painted, 2);
+
+    // the same program on hexes turns 60 degrees at a time
+    let mut robot = HexRobot::new(BLACK);
+    assert_eq!(robot.run(&mut IntcodeBrain::new(&program)), 2);
+    assert_eq!(robot.heading(), 2);
+    assert_eq!(robot.position(), (2, -1));
+}
+
+#[test]
+fn test_hex_robot() {
+    // six right turns go round a ring of six hexes, back to the start
+    let mut robot = HexRobot::new(BLACK);
+    for _ in 0..6 {
+        robot.step(WHITE, Turn::Right);
+    }
+    assert_eq!(robot.position(), (0, 0));
+    assert_eq!(robot.heading(), 0);
+    assert_eq!(robot.stats().painted, 6);
+
+    // the hull is a grid in axial coordinates, and each hex of the ring touches two others
+    let hull = robot.hull();
+    for (p, _) in hull.iter() {
+        assert_eq!(
+            hull.neighbors_hex(p.0, p.1)
+                .filter(|(_, c)| **c == WHITE)
+                .count(),
+            2
+        );
+    }
+
+    // right, left, left undoes the first right
+    assert_eq!(
+        Hex::turn(Hex::turn(Hex::turn(0, Turn::Right), Turn::Left), Turn::Left),
+        5
+    );
+}
+
+#[test]
+fn test_voxel_robot() {
+    let level = |yaw| Turn3 { yaw, pitch: None };
+
+    // without pitching it paints the same as the square robot
+    let turns = [Turn::Left, Turn::Left, Turn::Right, Turn::Left, Turn::Right];
+    let mut square = PaintingRobot::new(BLACK);
+    let mut voxel = VoxelRobot::new(BLACK);
+    for turn in turns.iter() {
+        square.step(WHITE, *turn);
+        voxel.step(WHITE, level(Some(*turn)));
+    }
+    let (x, y) = square.position();
+    assert_eq!(voxel.position(), (x, y, 0));
+    assert_eq!(voxel.hull().layer(0), *square.hull());
+
+    // four pitches up loop the loop, back to the start facing the same way
+    let mut robot = VoxelRobot::new(BLACK);
+    let up = Turn3 {
+        yaw: None,
+        pitch: Some(Pitch::Up),
+    };
+    for _ in 0..4 {
+        robot.step(WHITE, up);
+    }
+    assert_eq!(robot.position(), (0, 0, 0));
+    assert_eq!(robot.heading(), Cube::start().1);
+    assert_eq!(robot.hull().z_range(), Some((0, 1)));
+
+    // turning keeps the axes at right angles, whatever the order
+    let mut a = Cube::start().1;
+    for (yaw, pitch) in [(0, 1), (1, 0), (2, 1), (0, 2), (1, 1)].iter() {
+        a = Cube::turn(a, Turn3::decode(&[*yaw, *pitch]));
+        let f = a.forward;
+        let u = a.up;
+        assert_eq!(f.0 * u.0 + f.1 * u.1 + f.2 * u.2, 0);
+        assert_eq!(f.0.abs() + f.1.abs() + f.2.abs(), 1);
+    }
+
+    // a program that paints, then heads up out of the page, twice
+    let program = vec![
+        3, 100, 104, 1, 104, 2, 104, 1, 3, 100, 104, 1, 104, 2, 104, 2, 99,
+    ];
+    let mut robot = VoxelRobot::new(BLACK);
+    assert_eq!(robot.run(&mut IntcodeBrain::new(&program)), 2);
+    assert_eq!(robot.position(), (0, 0, 2));
+}
@@ -0,0 +1,179 @@
+use std::fmt;
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::loader::{LoadError, Source};
+use crate::record::Observer;
+use crate::render::Picture;
+
+// What a puzzle part produces: almost always a number, sometimes text (a message of digits, a
+// picture of letters).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(v: i64) -> Self {
+        return Answer::Number(v);
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(v: i32) -> Self {
+        return Answer::Number(v as i64);
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(v: usize) -> Self {
+        return Answer::Number(v as i64);
+    }
+}
+
+impl From<String> for Answer {
+    fn from(v: String) -> Self {
+        return Answer::Text(v);
+    }
+}
+
+// Something worth looking at besides the answers, such as the hull that was painted or the path the
+// droid took to the oxygen system.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Artifact {
+    // a rendered picture, one string per row
+    Grid {
+        name: String,
+        rows: Vec<String>,
+    },
+
+    // positions visited in order
+    Path {
+        name: String,
+        points: Vec<(i64, i64)>,
+    },
+
+    // anything else, for example a graph in DOT format
+    Text {
+        name: String,
+        text: String,
+    },
+}
+
+impl Artifact {
+    pub fn grid(name: &str, picture: &str) -> Artifact {
+        return Artifact::Grid {
+            name: name.to_string(),
+            rows: picture.lines().map(|s| s.to_string()).collect(),
+        };
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Artifact::Grid { name, .. } => name,
+            Artifact::Path { name, .. } => name,
+            Artifact::Text { name, .. } => name,
+        }
+    }
+}
+
+impl fmt::Display for Artifact {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Artifact::Grid { rows, .. } => write!(f, "{}", rows.join("\n")),
+            Artifact::Path { points, .. } => {
+                let points: Vec<String> =
+                    points.iter().map(|(x, y)| format!("{},{}", x, y)).collect();
+                write!(f, "{}", points.join(" "))
+            }
+            Artifact::Text { text, .. } => write!(f, "{}", text.trim_end()),
+        }
+    }
+}
+
+#[test]
+fn test_artifact_json() {
+    let grid = Artifact::grid("hull", "#.\n.#\n");
+    assert_eq!(
+        serde_json::to_string(&grid).unwrap(),
+        r##"{"kind":"grid","name":"hull","rows":["#.",".#"]}"##
+    );
+
+    let path = Artifact::Path {
+        name: "droid".to_string(),
+        points: vec![(0, 0), (0, -1)],
+    };
+    assert_eq!(path.to_string(), "0,0 0,-1");
+    assert_eq!(
+        serde_json::to_string(&path).unwrap(),
+        r#"{"kind":"path","name":"droid","points":[[0,0],[0,-1]]}"#
+    );
+
+    assert_eq!(serde_json::to_string(&Answer::Number(42)).unwrap(), "42");
+    assert_eq!(
+        serde_json::to_string(&Answer::Text("ABC".to_string())).unwrap(),
+        r#""ABC""#
+    );
+}
+
+// Every day is solved in the same three steps: read the puzzle input, then answer each part from
+// it. Parsing is kept separate so that parts can be run (and timed) on their own.
+pub trait Solution {
+    fn day(&self) -> u32;
+
+    // the puzzle title
+    fn title(&self) -> &'static str;
+
+    fn parse(&mut self, source: &Source) -> Result<(), LoadError>;
+
+    fn part1(&self) -> Answer;
+
+    fn part2(&self) -> Answer;
+
+    // Extras produced from the parsed input. They are only worked out when asked for, because that
+    // can mean solving the puzzle again.
+    fn artifacts(&self) -> Vec<Artifact> {
+        return Vec::new();
+    }
+
+    // Grids worth saving as images, styled so that any renderer can draw them.
+    fn pictures(&self) -> Vec<Picture> {
+        return Vec::new();
+    }
+
+    // Runs whatever the day simulates (a game, a droid, a spreading gas) showing every step to the
+    // observer. Days with nothing to watch never call it.
+    fn watch(&self, _observer: &mut dyn Observer) {}
+
+    // Input file used when none is given on the command line. Inputs saved before the days were
+    // numbered with two digits are still picked up.
+    fn default_input(&self) -> String {
+        let padded = format!("day{:02}.input", self.day());
+        let unpadded = format!("day{}.input", self.day());
+        if !Path::new(&padded).exists() && Path::new(&unpadded).exists() {
+            return unpadded;
+        }
+        return padded;
+    }
+
+    fn part(&self, part: u32) -> Answer {
+        match part {
+            1 => self.part1(),
+            2 => self.part2(),
+            _ => panic!("day {} has no part {}", self.day(), part),
+        }
+    }
+}
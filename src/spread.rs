@@ -0,0 +1,245 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+
+use petgraph::graph::{Graph, IndexType, NodeIndex};
+use petgraph::visit::EdgeRef;
+use petgraph::EdgeType;
+
+use crate::grid::Grid;
+
+// Something spreading out from a few places at once, like day 15's oxygen: each step it reaches
+// whatever is next to what it has reached already. Moves can take longer than one step, in which
+// case it's a breadth first search with the layers in order of time rather than of moves, and
+// barriers are places it never goes.
+//
+// The step callback is shown each layer as it's reached: the time, and the cells reached then, the
+// sources at time 0 first. It can stop the spread by returning false.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Spread<N: Hash + Eq> {
+    // when the spread got to each cell it reached
+    pub arrivals: HashMap<N, u64>,
+
+    // when it reached the last of them, which is the time to fill everywhere it can reach
+    pub time: u64,
+
+    // the callback stopped it, so there may be more it would have reached
+    pub stopped: bool,
+}
+
+impl<N: Hash + Eq> Spread<N> {
+    pub fn arrival(&self, cell: &N) -> Option<u64> {
+        return self.arrivals.get(cell).copied();
+    }
+}
+
+// The spread from the sources, given the cells next to each one and how long it takes to get
+// there. A Dijkstra search, so a cell's arrival is the soonest it can be reached from any source.
+pub fn spread<N, I>(
+    sources: &[N],
+    mut neighbours: impl FnMut(N) -> I,
+    mut step: impl FnMut(u64, &[N]) -> bool,
+) -> Spread<N>
+where
+    N: Copy + Hash + Ord,
+    I: IntoIterator<Item = (N, u64)>,
+{
+    let mut arrivals: HashMap<N, u64> = HashMap::new();
+    let mut soonest: HashMap<N, u64> = HashMap::new();
+    let mut queue = BinaryHeap::new();
+
+    for source in sources {
+        soonest.insert(*source, 0);
+        queue.push(Reverse((0, *source)));
+    }
+
+    let mut time = 0;
+    let mut layer: Vec<N> = Vec::new();
+
+    while let Some(Reverse((arrival, cell))) = queue.pop() {
+        // reached sooner some other way
+        if arrivals.contains_key(&cell) {
+            continue;
+        }
+
+        if arrival != time && !layer.is_empty() {
+            if !step(time, &layer) {
+                return Spread {
+                    arrivals,
+                    time,
+                    stopped: true,
+                };
+            }
+            layer.clear();
+        }
+        time = arrival;
+
+        arrivals.insert(cell, arrival);
+        layer.push(cell);
+
+        for (next, delay) in neighbours(cell) {
+            let next_arrival = arrival + delay;
+            if arrivals.contains_key(&next)
+                || soonest.get(&next).is_some_and(|t| *t <= next_arrival)
+            {
+                continue;
+            }
+            soonest.insert(next, next_arrival);
+            queue.push(Reverse((next_arrival, next)));
+        }
+    }
+
+    let stopped = !layer.is_empty() && !step(time, &layer);
+
+    return Spread {
+        arrivals,
+        time,
+        stopped,
+    };
+}
+
+// The spread through a graph, taking delay(weight) to cross each edge and never going into a node
+// that's a barrier. A directed graph is only spread along its edges' directions.
+pub fn spread_graph<N, E, Ty, Ix>(
+    graph: &Graph<N, E, Ty, Ix>,
+    sources: &[NodeIndex<Ix>],
+    delay: impl Fn(&E) -> u64,
+    barrier: impl Fn(&N) -> bool,
+    step: impl FnMut(u64, &[NodeIndex<Ix>]) -> bool,
+) -> Spread<NodeIndex<Ix>>
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    let neighbours = |node: NodeIndex<Ix>| {
+        return graph
+            .edges(node)
+            .map(|edge| {
+                let next = if edge.source() == node {
+                    edge.target()
+                } else {
+                    edge.source()
+                };
+                return (next, delay(edge.weight()));
+            })
+            .filter(|(next, _)| !barrier(&graph[*next]))
+            .collect::<Vec<_>>();
+    };
+
+    return spread(sources, neighbours, step);
+}
+
+// The spread through a grid, up, down, left and right, taking delay(cell) to move into a cell and
+// never going into one that's a barrier. It stays inside the cells that have been set.
+pub fn spread_grid<T: Clone>(
+    grid: &Grid<T>,
+    sources: &[(i32, i32)],
+    delay: impl Fn(&T) -> u64,
+    barrier: impl Fn(&T) -> bool,
+    step: impl FnMut(u64, &[(i32, i32)]) -> bool,
+) -> Spread<(i32, i32)> {
+    let bounds = match grid.bounds() {
+        Some(bounds) => bounds,
+        None => return spread(&[], |_| Vec::new(), step),
+    };
+
+    let neighbours = |(x, y): (i32, i32)| {
+        return grid
+            .neighbors4(x, y)
+            .filter(|((nx, ny), cell)| bounds.contains(*nx, *ny) && !barrier(cell))
+            .map(|(next, cell)| (next, delay(cell)))
+            .collect::<Vec<_>>();
+    };
+
+    return spread(sources, neighbours, step);
+}
+
+#[cfg(test)]
+fn picture(rows: &[&str]) -> Grid<char> {
+    let mut grid = Grid::new(' ');
+    for (y, row) in rows.iter().enumerate() {
+        for (x, c) in row.chars().enumerate() {
+            grid.set(x as i32, y as i32, c);
+        }
+    }
+    return grid;
+}
+
+#[test]
+fn test_spread_grid() {
+    // oxygen from the O in the part 2 example takes 4 minutes to fill it
+    let grid = picture(&[" ##   ", "#..## ", "#.#..#", "#.O.# ", " ###  "]);
+    let oxygen = grid.find(|c| *c == 'O').unwrap();
+
+    let mut layers: Vec<(u64, usize)> = Vec::new();
+    let filled = spread_grid(
+        &grid,
+        &[oxygen],
+        |_| 1,
+        |c| *c != '.',
+        |time, cells| {
+            layers.push((time, cells.len()));
+            return true;
+        },
+    );
+    assert_eq!(filled.time, 4);
+    assert!(!filled.stopped);
+    assert_eq!(filled.arrivals.len(), 8);
+    assert_eq!(filled.arrival(&(3, 2)), Some(2));
+    assert_eq!(filled.arrival(&(0, 0)), None);
+    assert_eq!(layers, vec![(0, 1), (1, 2), (2, 2), (3, 2), (4, 1)]);
+
+    // from both ends of a corridor, with a slow cell in it
+    let grid = picture(&["a...~..b"]);
+    let ends = [(0, 0), (7, 0)];
+    let slow = |c: &char| if *c == '~' { 5 } else { 1 };
+    let filled = spread_grid(&grid, &ends, slow, |_| false, |_, _| true);
+    assert_eq!(filled.arrival(&(3, 0)), Some(3));
+    assert_eq!(filled.arrival(&(4, 0)), Some(7));
+    assert_eq!(filled.time, 7);
+
+    // stopped part way
+    let filled = spread_grid(&grid, &ends, slow, |_| false, |time, _| time < 2);
+    assert!(filled.stopped);
+    assert_eq!(filled.time, 2);
+    assert_eq!(filled.arrivals.len(), 6);
+
+    // nowhere to go
+    let filled = spread_grid(&Grid::new('.'), &[], |_| 1, |_| false, |_, _| true);
+    assert_eq!(filled.time, 0);
+    assert!(filled.arrivals.is_empty());
+}
+
+#[test]
+fn test_spread_graph() {
+    // a ring of four with a long way round, and a locked door off it
+    let mut graph: Graph<bool, u64> = Graph::new();
+    let nodes: Vec<NodeIndex> = (0..5).map(|n| graph.add_node(n == 4)).collect();
+    graph.add_edge(nodes[0], nodes[1], 1);
+    graph.add_edge(nodes[1], nodes[2], 1);
+    graph.add_edge(nodes[2], nodes[3], 1);
+    graph.add_edge(nodes[3], nodes[0], 10);
+    graph.add_edge(nodes[1], nodes[4], 1);
+
+    // directed, so going on from 3 means the long edge back to 0
+    let filled = spread_graph(&graph, &[nodes[0]], |w| *w, |locked| *locked, |_, _| true);
+    assert_eq!(filled.arrival(&nodes[3]), Some(3));
+    assert_eq!(filled.arrival(&nodes[4]), None);
+    assert_eq!(filled.time, 3);
+    let filled = spread_graph(&graph, &[nodes[3]], |w| *w, |locked| *locked, |_, _| true);
+    assert_eq!(filled.arrival(&nodes[2]), Some(12));
+
+    // both ways
+    let graph = graph.into_edge_type::<petgraph::Undirected>();
+    let filled = spread_graph(&graph, &[nodes[3]], |w| *w, |locked| *locked, |_, _| true);
+    assert_eq!(filled.arrival(&nodes[2]), Some(1));
+    assert_eq!(filled.arrival(&nodes[0]), Some(3));
+    assert_eq!(filled.time, 3);
+
+    // every edge one step, from two places at once
+    let filled = spread_graph(&graph, &[nodes[0], nodes[4]], |_| 1, |_| false, |_, _| true);
+    assert_eq!(filled.arrival(&nodes[1]), Some(1));
+    assert_eq!(filled.arrival(&nodes[2]), Some(2));
+    assert_eq!(filled.time, 2);
+}
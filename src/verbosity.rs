@@ -0,0 +1,38 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// How much progress chatter the solutions write. It always goes to stderr, so that stdout only
+// carries the answers and can be scripted against.
+//
+//   0: nothing (the default)
+//   1: progress, such as new best results and final scores
+//   2: everything, every frame and every search step
+
+static LEVEL: AtomicUsize = AtomicUsize::new(0);
+
+pub fn set_level(level: usize) {
+    LEVEL.store(level, Ordering::Relaxed);
+}
+
+pub fn level() -> usize {
+    return LEVEL.load(Ordering::Relaxed);
+}
+
+// progress, shown with -v
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)*) => {
+        if $crate::verbosity::level() >= 1 {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+// step by step detail, shown with -vv
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        if $crate::verbosity::level() >= 2 {
+            eprintln!($($arg)*);
+        }
+    };
+}
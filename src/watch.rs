@@ -0,0 +1,358 @@
+use std::fmt::Write as _;
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::{cursor, execute, terminal};
+
+use crate::grid::{Bounds, Grid};
+use crate::record::Observer;
+use crate::render::{Pixel, Rgb};
+
+// Shows a simulation live, full screen in the terminal. Cells are drawn the same way as the ANSI
+// renderer draws them, and after the first frame only the cells that changed are redrawn. The
+// keys control how fast it goes:
+//
+//   space or p   pause and resume
+//   n or .       one frame on, while paused
+//   + and -      faster and slower
+//   q or esc     quit, which stops the simulation too
+
+pub const KEYS: &str = "space: pause  n: step  +/-: speed  q: quit";
+
+// the slowest it will go
+const MAX_DELAY: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Pause,
+    Step,
+    Faster,
+    Slower,
+    Quit,
+}
+
+pub fn command(key: KeyEvent) -> Option<Command> {
+    // raw mode means ctrl-c is just another key
+    if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+        return Some(Command::Quit);
+    }
+
+    match key.code {
+        KeyCode::Char(' ') | KeyCode::Char('p') => return Some(Command::Pause),
+        KeyCode::Char('n') | KeyCode::Char('.') | KeyCode::Right => return Some(Command::Step),
+        KeyCode::Char('+') | KeyCode::Char('=') | KeyCode::Up => return Some(Command::Faster),
+        KeyCode::Char('-') | KeyCode::Down => return Some(Command::Slower),
+        KeyCode::Char('q') | KeyCode::Esc => return Some(Command::Quit),
+        _ => return None,
+    }
+}
+
+// What is on the terminal, so the next frame only has to draw the difference.
+#[derive(Default)]
+pub struct Screen {
+    drawn: Option<(Bounds, Grid<Pixel>)>,
+    status_lines: usize,
+}
+
+fn draw_cell(output: &mut String, bounds: &Bounds, x: i32, y: i32, c: Rgb) {
+    // rows and columns count from 1, and each cell is two columns wide
+    let _ = write!(
+        output,
+        "\x1b[{};{}H\x1b[48;2;{};{};{}m  ",
+        y - bounds.min_y + 1,
+        (x - bounds.min_x) * 2 + 1,
+        c.0,
+        c.1,
+        c.2
+    );
+}
+
+impl Screen {
+    // The escape sequences that take the terminal from the last frame to this one. The whole
+    // picture is drawn again only if its bounds changed.
+    pub fn update(&mut self, grid: &Grid<Pixel>, status: &[String]) -> String {
+        let mut output = String::new();
+        let bounds = grid.bounds().unwrap_or_else(|| Bounds::point(0, 0));
+
+        match &self.drawn {
+            Some((drawn_bounds, drawn)) if *drawn_bounds == bounds => {
+                for y in bounds.min_y..=bounds.max_y {
+                    for x in bounds.min_x..=bounds.max_x {
+                        let c = grid.get(x, y).color;
+                        if drawn.get(x, y).color != c {
+                            draw_cell(&mut output, &bounds, x, y, c);
+                        }
+                    }
+                }
+            }
+            _ => {
+                output.push_str("\x1b[2J");
+                for y in bounds.min_y..=bounds.max_y {
+                    for x in bounds.min_x..=bounds.max_x {
+                        draw_cell(&mut output, &bounds, x, y, grid.get(x, y).color);
+                    }
+                }
+                self.status_lines = 0;
+            }
+        }
+        output.push_str("\x1b[0m");
+
+        // the status panel goes underneath, with a blank line between
+        let first_row = bounds.height() + 2;
+        for row in 0..status.len().max(self.status_lines) {
+            let line = status.get(row).map(|s| s.as_str()).unwrap_or("");
+            let _ = write!(output, "\x1b[{};1H\x1b[2K{}", first_row + row, line);
+        }
+        self.status_lines = status.len();
+
+        self.drawn = Some((bounds, grid.clone()));
+
+        return output;
+    }
+}
+
+// The terminal taken over: raw mode, the alternate screen and no cursor, until it's dropped.
+pub struct Terminal {
+    out: io::Stdout,
+}
+
+impl Terminal {
+    pub fn new() -> io::Result<Terminal> {
+        let mut out = io::stdout();
+
+        terminal::enable_raw_mode()?;
+        execute!(out, terminal::EnterAlternateScreen, cursor::Hide)?;
+
+        return Ok(Terminal { out });
+    }
+
+    pub fn write(&mut self, output: &str) -> io::Result<()> {
+        self.out.write_all(output.as_bytes())?;
+        return self.out.flush();
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        let _ = execute!(self.out, cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+// waits for any key to be pressed
+pub fn any_key() -> io::Result<()> {
+    loop {
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Press {
+                return Ok(());
+            }
+        }
+    }
+}
+
+pub struct Viewer {
+    terminal: Terminal,
+    screen: Screen,
+    steps: usize,
+    delay: Duration,
+    paused: bool,
+    quit: bool,
+
+    // the latest status from the simulation
+    meta: Vec<(String, String)>,
+}
+
+impl Viewer {
+    // Takes over the terminal until the viewer is dropped. delay is how long each frame stays up.
+    pub fn new(delay: Duration) -> io::Result<Viewer> {
+        return Ok(Viewer {
+            terminal: Terminal::new()?,
+            screen: Screen::default(),
+            steps: 0,
+            delay,
+            paused: false,
+            quit: false,
+            meta: Vec::new(),
+        });
+    }
+
+    pub fn steps(&self) -> usize {
+        return self.steps;
+    }
+
+    fn status(&self, note: &str) -> Vec<String> {
+        let mut lines: Vec<String> = vec![format!("steps: {}", self.steps)];
+        for (k, v) in self.meta.iter() {
+            lines.push(format!("{}: {}", k, v));
+        }
+
+        let speed = if self.paused {
+            "paused".to_string()
+        } else {
+            format!("{}ms a frame", self.delay.as_millis())
+        };
+        lines.push(format!("speed: {}", speed));
+
+        lines.push(String::new());
+        lines.push(note.to_string());
+
+        return lines;
+    }
+
+    fn draw(&mut self, grid: &Grid<Pixel>, note: &str) -> io::Result<()> {
+        let status = self.status(note);
+        let output = self.screen.update(grid, &status);
+        return self.terminal.write(&output);
+    }
+
+    fn handle(&mut self, command: Command) {
+        match command {
+            Command::Pause => self.paused = !self.paused,
+            Command::Faster => self.delay /= 2,
+            Command::Slower => {
+                self.delay = (self.delay * 2)
+                    .max(Duration::from_millis(1))
+                    .min(MAX_DELAY)
+            }
+            Command::Quit => self.quit = true,
+            Command::Step => {}
+        }
+    }
+
+    // Shows the frame, then waits out the delay (or, while paused, for a key) taking commands.
+    fn show(&mut self, grid: &Grid<Pixel>) -> io::Result<bool> {
+        self.draw(grid, KEYS)?;
+
+        let deadline = Instant::now() + self.delay;
+
+        loop {
+            let timeout = if self.paused {
+                MAX_DELAY
+            } else {
+                deadline.saturating_duration_since(Instant::now())
+            };
+
+            if !event::poll(timeout)? {
+                if self.paused {
+                    continue;
+                }
+                return Ok(true);
+            }
+
+            let key = match event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => key,
+                _ => continue,
+            };
+
+            let command = match command(key) {
+                Some(command) => command,
+                None => continue,
+            };
+
+            self.handle(command);
+
+            match command {
+                Command::Quit => return Ok(false),
+                Command::Step if self.paused => return Ok(true),
+                _ => self.draw(grid, KEYS)?,
+            }
+        }
+    }
+
+    // Leaves the last frame up until a key is pressed, unless the viewer was quit already.
+    pub fn finish(&mut self) -> io::Result<()> {
+        if self.quit {
+            return Ok(());
+        }
+
+        if let Some((_, grid)) = self.screen.drawn.clone() {
+            self.paused = true;
+            self.draw(&grid, "finished, press any key")?;
+        }
+
+        return any_key();
+    }
+}
+
+impl Observer for Viewer {
+    fn frame(&mut self, grid: Grid<Pixel>, meta: &[(&str, String)]) -> bool {
+        if self.quit {
+            return false;
+        }
+
+        self.steps += 1;
+        self.meta = meta
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.clone()))
+            .collect();
+
+        // a terminal that went away is as good as quitting
+        match self.show(&grid) {
+            Ok(carry_on) => return carry_on,
+            Err(_) => {
+                self.quit = true;
+                return false;
+            }
+        }
+    }
+}
+
+#[test]
+fn test_commands() {
+    let key = |code: KeyCode| KeyEvent::new(code, KeyModifiers::NONE);
+
+    assert_eq!(command(key(KeyCode::Char(' '))), Some(Command::Pause));
+    assert_eq!(command(key(KeyCode::Char('n'))), Some(Command::Step));
+    assert_eq!(command(key(KeyCode::Char('+'))), Some(Command::Faster));
+    assert_eq!(command(key(KeyCode::Down)), Some(Command::Slower));
+    assert_eq!(command(key(KeyCode::Esc)), Some(Command::Quit));
+    assert_eq!(command(key(KeyCode::Char('x'))), None);
+    assert_eq!(
+        command(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+        Some(Command::Quit)
+    );
+}
+
+#[test]
+fn test_screen_updates() {
+    let pixel = |color: Rgb| Pixel { glyph: ' ', color };
+    let white = Rgb::WHITE;
+    let black = Rgb::BLACK;
+
+    let mut grid: Grid<Pixel> = Grid::new(pixel(black));
+    grid.set(0, 0, pixel(white));
+    grid.set(1, 1, pixel(black));
+
+    let mut screen = Screen::default();
+    let status = vec!["score: 0".to_string()];
+
+    // the first frame clears the screen and draws every cell
+    let first = screen.update(&grid, &status);
+    assert!(first.starts_with("\x1b[2J"));
+    assert_eq!(first.matches("  ").count(), 4);
+    assert!(first.ends_with("\x1b[4;1H\x1b[2Kscore: 0"));
+
+    // nothing changed, only the status is drawn
+    let same = screen.update(&grid, &status);
+    assert_eq!(same, "\x1b[0m\x1b[4;1H\x1b[2Kscore: 0");
+
+    // one cell changed
+    grid.set(1, 0, pixel(white));
+    let status = vec!["score: 10".to_string(), "paused".to_string()];
+    let next = screen.update(&grid, &status);
+    assert_eq!(
+        next,
+        "\x1b[1;3H\x1b[48;2;255;255;255m  \x1b[0m\x1b[4;1H\x1b[2Kscore: 10\x1b[5;1H\x1b[2Kpaused"
+    );
+
+    // a shorter status clears the lines that are left over
+    let status = vec!["score: 10".to_string()];
+    assert!(screen.update(&grid, &status).ends_with("\x1b[5;1H\x1b[2K"));
+
+    // the grid grew, so everything is drawn again
+    grid.set(2, 0, pixel(white));
+    let grown = screen.update(&grid, &status);
+    assert!(grown.starts_with("\x1b[2J"));
+    assert_eq!(grown.matches("  ").count(), 6);
+}